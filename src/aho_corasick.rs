@@ -0,0 +1,314 @@
+use super::{Transition, State, Symbol, FiniteStateMachine, DeterministicFiniteAutomaton, Match};
+use super::search::find_non_overlapping;
+use std::collections::{BTreeMap, HashMap, HashSet, VecDeque};
+
+/// Sémantique utilisée par `AhoCorasick` pour choisir un mot parmi ceux qui commencent au même endroit
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum KeywordMatchKind {
+    /// L'occurrence la plus à gauche, puis le mot le plus long
+    LeftmostLongest,
+    /// L'occurrence la plus à gauche, puis le mot qui apparaît le premier dans le dictionnaire
+    LeftmostFirst,
+    /// L'occurrence la plus à gauche, puis le mot le plus court
+    LeftmostShortest,
+}
+
+impl KeywordMatchKind {
+    /// Indique si `candidate`, trouvée après `current` au même départ, la remplace selon la sémantique
+    fn prefers(self, candidate : &Match, current : &Match) -> bool {
+        match self {
+            KeywordMatchKind::LeftmostLongest => candidate.get_end() > current.get_end(),
+            KeywordMatchKind::LeftmostFirst => candidate.get_pattern() < current.get_pattern(),
+            KeywordMatchKind::LeftmostShortest => false,
+        }
+    }
+}
+
+/// Automate d'Aho–Corasick: un automate déterministe reconnaissant les textes qui se terminent par l'un des mots d'un dictionnaire
+///
+/// Les liens d'échec sont compilés dans les transitions: l'automate est complet sur l'alphabet des mots et
+/// lit un texte en une seule passe, sans retour arrière. Chaque état final est annoté par les mots qui s'y terminent.
+#[derive(Debug, Clone)]
+pub struct AhoCorasick {
+    dfa: DeterministicFiniteAutomaton,
+    keywords: Vec<String>,
+    outputs: HashMap<State, Vec<usize>>,
+    longest: usize,
+}
+
+impl AhoCorasick {
+    /// Créer l'automate d'Aho–Corasick d'une liste de mots
+    ///
+    /// Les états sont nommés d'après le préfixe qu'ils représentent, l'état initial étant le préfixe vide.
+    ///
+    /// # Arguments
+    ///
+    /// * `keywords` - Les mots du dictionnaire, leur ordre sert de priorité pour `KeywordMatchKind::LeftmostFirst`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use automate::*;
+    /// fn main() {
+    ///     let dictionary : AhoCorasick = AhoCorasick::new(&["he", "she", "his", "hers"]);
+    ///     let found : Vec<(usize, usize, &str)> = dictionary.find_all_keywords("ushers").iter()
+    ///         .map(|found| (found.get_start(), found.get_end(), dictionary.get_keyword(found.get_pattern().unwrap())))
+    ///         .collect();
+    ///     assert_eq!(found, vec![(1, 4, "she"), (2, 4, "he"), (2, 6, "hers")]);
+    ///     assert!(dictionary.get_dfa().accept("hishe"));
+    /// }
+    /// ```
+    ///
+    /// # Return
+    ///
+    /// * `AhoCorasick` - L'automate du dictionnaire
+    ///
+    pub fn new(keywords : &[&str]) -> Self {
+        //construction du trie: chaque prefixe d'un mot est un etat
+        let root : State = State::from_str("");
+        let mut children : HashMap<State, BTreeMap<Symbol, State>> = HashMap::new();
+        let mut own_keywords : HashMap<State, Vec<usize>> = HashMap::new();
+        let mut alphabet : HashSet<Symbol> = HashSet::new();
+        for (index, keyword) in keywords.iter().enumerate() {
+            let mut node : State = root.clone();
+            let mut prefix : String = String::new();
+            for letter in keyword.chars() {
+                prefix.push(letter);
+                let symbol : Symbol = Symbol::new(String::from(letter));
+                alphabet.insert(symbol.clone());
+                let child : State = State::new(prefix.clone());
+                children.entry(node).or_default().insert(symbol, child.clone());
+                node = child;
+            }
+            own_keywords.entry(node).or_default().push(index);
+        }
+        let mut sorted_alphabet : Vec<Symbol> = alphabet.iter().cloned().collect();
+        sorted_alphabet.sort();
+
+        //parcours en largeur: le lien d'echec d'un noeud est calculé avant ceux de ses enfants
+        let mut delta : HashMap<Transition<State>, State> = HashMap::new();
+        let mut outputs : HashMap<State, Vec<usize>> = HashMap::new();
+        let mut failures : HashMap<State, State> = HashMap::new();
+        let mut states : HashSet<State> = HashSet::from([root.clone()]);
+        let mut queue : VecDeque<State> = VecDeque::from([root.clone()]);
+        while let Some(node) = queue.pop_front() {
+            let failure : Option<State> = failures.get(&node).cloned();
+            //mots reconnus: ceux du noeud puis ceux de son lien d'echec (suffixes)
+            let mut node_outputs : Vec<usize> = own_keywords.get(&node).cloned().unwrap_or_default();
+            if let Some(failure) = &failure {
+                node_outputs.extend(outputs.get(failure).cloned().unwrap_or_default());
+            }
+            if !node_outputs.is_empty() {
+                outputs.insert(node.clone(), node_outputs);
+            }
+            for symbol in &sorted_alphabet {
+                let child : Option<State> = children.get(&node).and_then(|children| children.get(symbol)).cloned();
+                let image : State = match (&child, &failure) {
+                    (Some(child), _) => child.clone(),
+                    //la transition manquante est celle du lien d'echec, déjà calculée
+                    (None, Some(failure)) => delta[&Transition::new(symbol.clone(), failure.clone())].clone(),
+                    (None, None) => root.clone(),
+                };
+                if let Some(child) = child {
+                    //le lien d'echec de l'enfant est l'image du symbole depuis le lien d'echec du noeud
+                    let child_failure : State = match &failure {
+                        Some(failure) => delta[&Transition::new(symbol.clone(), failure.clone())].clone(),
+                        None => root.clone(),
+                    };
+                    failures.insert(child.clone(), child_failure);
+                    states.insert(child.clone());
+                    queue.push_back(child);
+                }
+                delta.insert(Transition::new(symbol.clone(), node.clone()), image);
+            }
+        }
+        let ends : HashSet<State> = outputs.keys().cloned().collect();
+        let fsm : FiniteStateMachine = FiniteStateMachine::new(states, alphabet, ends);
+        AhoCorasick {
+            dfa: DeterministicFiniteAutomaton::new(root, delta, fsm),
+            keywords: keywords.iter().map(|keyword| String::from(*keyword)).collect(),
+            outputs,
+            longest: keywords.iter().map(|keyword| keyword.len()).max().unwrap_or(0),
+        }
+    }
+
+    /// Retourne l'automate déterministe du dictionnaire, avec les liens d'échec compilés
+    ///
+    /// Il reconnaît les textes qui se terminent par un mot (Σ*·K): ses méthodes de recherche rapportent donc des
+    /// occurrences qui commencent au plus tôt, avant le mot reconnu. Les occurrences des mots eux-mêmes sont données par `find_all`.
+    pub fn get_dfa(&self) -> &DeterministicFiniteAutomaton {
+        &self.dfa
+    }
+
+    /// Retourne les mots du dictionnaire
+    pub fn get_keywords(&self) -> &Vec<String> {
+        &self.keywords
+    }
+
+    /// Retourne le mot d'indice `pattern`
+    pub fn get_keyword(&self, pattern : usize) -> &str {
+        &self.keywords[pattern]
+    }
+
+    /// Retourne les indices des mots qui se terminent dans `state` de `get_dfa`, du plus long au plus court
+    pub fn get_outputs(&self, state : &State) -> &[usize] {
+        self.outputs.get(state).map_or(&[], |outputs| outputs.as_slice())
+    }
+
+    /// Lit `text` à partir de la position `from` et passe à `visit` chaque occurrence d'un mot, par position de fin
+    ///
+    /// Un caractère absent de l'alphabet ramène à l'état initial. La lecture s'arrête dès que `visit` retourne `false`.
+    fn scan(&self, text : &str, from : usize, mut visit : impl FnMut(Match) -> bool) {
+        let root : &State = self.dfa.get_start();
+        let mut state : &State = root;
+        let mut report = |state : &State, end : usize| {
+            self.get_outputs(state).iter()
+                .all(|pattern| visit(Match::with_pattern(end - self.keywords[*pattern].len(), end, state.clone(), *pattern)))
+        };
+        if !report(state, from) {
+            return;
+        }
+        for (offset, letter) in text[from..].char_indices() {
+            state = self.dfa.apply_delta(Transition::new(Symbol::new(String::from(letter)), state.clone())).unwrap_or(root);
+            if !report(state, from + offset + letter.len_utf8()) {
+                return;
+            }
+        }
+    }
+
+    /// Cherche l'occurrence choisie par `kind` parmi celles qui commencent à partir de la position `from`
+    fn find_at(&self, text : &str, from : usize, kind : KeywordMatchKind) -> Option<Match> {
+        let mut best : Option<Match> = None;
+        self.scan(text, from, |found| {
+            if let Some(current) = &best {
+                //une occurrence qui se termine ici commence trop tard pour l'emporter, de même que les suivantes
+                if found.get_end() > current.get_start() + self.longest {
+                    return false;
+                }
+                if found.get_start() > current.get_start() || (found.get_start() == current.get_start() && !kind.prefers(&found, current)) {
+                    return true;
+                }
+            }
+            best = Some(found);
+            true
+        });
+        best
+    }
+
+    /// Retourne la première occurrence d'un mot du dictionnaire dans `text`, voir `find_all`
+    pub fn find(&self, text : &str, kind : KeywordMatchKind) -> Option<Match> {
+        self.find_at(text, 0, kind)
+    }
+
+    /// Retourne les occurrences sans chevauchement des mots du dictionnaire dans `text`
+    ///
+    /// Parmi les occurrences commençant le plus à gauche, `KeywordMatchKind::LeftmostLongest` choisit le mot le plus long,
+    /// `KeywordMatchKind::LeftmostFirst` le mot qui apparaît le premier dans le dictionnaire et `KeywordMatchKind::LeftmostShortest` le plus court.
+    /// Après une occurrence, la lecture reprend à sa fin depuis l'état initial.
+    ///
+    /// # Arguments
+    ///
+    /// * `text` - Le texte à parcourir
+    /// * `kind` - La sémantique de choix des occurrences
+    ///
+    /// # Return
+    ///
+    /// * `Vec<Match>` - Les occurrences triées par position, `get_pattern` donnant le mot reconnu
+    ///
+    pub fn find_all(&self, text : &str, kind : KeywordMatchKind) -> Vec<Match> {
+        find_non_overlapping(text, |from| self.find_at(text, from, kind))
+    }
+
+    /// Retourne toutes les occurrences des mots du dictionnaire dans `text`, y compris celles qui se chevauchent
+    ///
+    /// Le texte est lu une seule fois; un caractère absent de l'alphabet ramène à l'état initial. Contrairement à
+    /// `DeterministicFiniteAutomaton::find_overlapping`, qui garde une occurrence par position de départ, chaque mot
+    /// trouvé est rapporté, même quand un autre commence au même endroit.
+    ///
+    /// # Return
+    ///
+    /// * `Vec<Match>` - Les occurrences triées par position de fin puis de début, `get_pattern` donnant le mot reconnu
+    ///
+    pub fn find_all_keywords(&self, text : &str) -> Vec<Match> {
+        let mut matches : Vec<Match> = Vec::new();
+        self.scan(text, 0, |found| {
+            matches.push(found);
+            true
+        });
+        matches
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use super::super::MatchKind;
+    use super::super::test_words::enumerate_words;
+
+    fn spans(dictionary : &AhoCorasick, matches : &[Match]) -> Vec<(usize, usize, String)> {
+        matches.iter().map(|found| (found.get_start(), found.get_end(), String::from(dictionary.get_keyword(found.get_pattern().unwrap())))).collect()
+    }
+
+    #[test]
+    fn dictionary_matches() {
+        let dictionary : AhoCorasick = AhoCorasick::new(&["he", "she", "his", "hers"]);
+        let dfa : &DeterministicFiniteAutomaton = dictionary.get_dfa();
+        assert!(dfa.is_complete());
+        assert_eq!(dictionary.get_outputs(&State::from_str("she")), &[1, 0]);
+        assert_eq!(spans(&dictionary, &dictionary.find_all("ushers", KeywordMatchKind::LeftmostLongest)), vec![(1, 4, String::from("she"))]);
+
+        //la priorité et la longueur sont en désaccord
+        let priorities : AhoCorasick = AhoCorasick::new(&["ab", "abcd", "bc"]);
+        assert_eq!(spans(&priorities, &priorities.find_all("xabcd", KeywordMatchKind::LeftmostLongest)), vec![(1, 5, String::from("abcd"))]);
+        assert_eq!(spans(&priorities, &priorities.find_all("xabcd", KeywordMatchKind::LeftmostFirst)), vec![(1, 3, String::from("ab"))]);
+        assert_eq!(spans(&priorities, &priorities.find_all("abcabc", KeywordMatchKind::LeftmostFirst)).len(), 2);
+        let reversed : AhoCorasick = AhoCorasick::new(&["abcd", "ab", "bc"]);
+        assert_eq!(spans(&reversed, &reversed.find_all("xabcd", KeywordMatchKind::LeftmostFirst)), vec![(1, 5, String::from("abcd"))]);
+        assert_eq!(spans(&reversed, &reversed.find_all("xabcd", KeywordMatchKind::LeftmostShortest)), vec![(1, 3, String::from("ab"))]);
+        //la priorité ne départage que les occurrences qui commencent au même endroit
+        assert_eq!(spans(&reversed, &reversed.find_all("xbcd", KeywordMatchKind::LeftmostFirst)), vec![(1, 3, String::from("bc"))]);
+        //l'automate à liens d'échec reconnaît Σ*·K, la recherche du dictionnaire rapporte les mots de K
+        assert_eq!(dictionary.get_dfa().find("hshe", MatchKind::LeftmostShortest).unwrap().get_start(), 0);
+        assert_eq!(dictionary.find("hshe", KeywordMatchKind::LeftmostShortest).unwrap().get_start(), 1);
+    }
+
+    #[test]
+    fn dictionary_matches_naive() {
+        let keywords : [&str; 5] = ["a", "ab", "bab", "bc", "cab"];
+        let dictionary : AhoCorasick = AhoCorasick::new(&keywords);
+        for text in enumerate_words("abc", 6) {
+            //l'automate accepte exactement les textes qui se terminent par un mot du dictionnaire
+            assert_eq!(dictionary.get_dfa().accept(&text), keywords.iter().any(|keyword| text.ends_with(keyword)), "{}", text);
+            let mut expected : Vec<(usize, usize, String)> = Vec::new();
+            for end in 0..=text.len() {
+                for start in 0..=end {
+                    if keywords.contains(&&text[start..end]) {
+                        expected.push((start, end, String::from(&text[start..end])));
+                    }
+                }
+            }
+            assert_eq!(spans(&dictionary, &dictionary.find_all_keywords(&text)), expected, "{}", text);
+            for kind in [KeywordMatchKind::LeftmostLongest, KeywordMatchKind::LeftmostFirst, KeywordMatchKind::LeftmostShortest] {
+                //a chaque départ, le mot choisi par la sémantique parmi ceux qui y commencent
+                let mut expected : Vec<(usize, usize, String)> = Vec::new();
+                let mut start : usize = 0;
+                while start < text.len() {
+                    let candidates = keywords.iter().enumerate().filter(|(_, keyword)| text[start..].starts_with(*keyword));
+                    let chosen : Option<(usize, &&str)> = match kind {
+                        KeywordMatchKind::LeftmostLongest => candidates.max_by_key(|(_, keyword)| keyword.len()),
+                        KeywordMatchKind::LeftmostFirst => candidates.min_by_key(|(index, _)| *index),
+                        KeywordMatchKind::LeftmostShortest => candidates.min_by_key(|(_, keyword)| keyword.len()),
+                    };
+                    match chosen {
+                        Some((_, keyword)) => {
+                            expected.push((start, start + keyword.len(), String::from(*keyword)));
+                            start += keyword.len();
+                        }
+                        None => start += 1,
+                    }
+                }
+                assert_eq!(spans(&dictionary, &dictionary.find_all(&text, kind)), expected, "{} {:?}", text, kind);
+            }
+        }
+    }
+}
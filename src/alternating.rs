@@ -0,0 +1,306 @@
+use super::{State, Symbol, Transition, FiniteStateMachine, BooleanFormula, DeterministicFiniteAutomaton, NonDeterministicFiniteAutomaton, SetNames, to_symbols};
+use super::formula::minimal_sets;
+use std::collections::{BTreeSet, HashMap, HashSet, VecDeque};
+use std::fs;
+use serde_json::{Value, from_str, map::Map};
+
+/// Automate alternant: l'image d'une transition est une formule booléenne positive sur les états
+///
+/// Une disjonction est un choix non déterministe, une conjonction demande que chacun de ses états accepte la
+/// suite du mot. Une transition absente vaut `false`; un mot est accepté si la formule initiale est vraie.
+#[derive(Debug, Clone)]
+pub struct AlternatingAutomaton {
+    start: BooleanFormula,
+    delta: HashMap<Transition<State>, BooleanFormula>,
+    fsm: FiniteStateMachine,
+}
+
+impl AlternatingAutomaton {
+    /// Créer un automate alternant
+    ///
+    /// # Arguments
+    ///
+    /// * `_start` - La formule initiale de l'automate
+    /// * `_delta` - La formule image de chaque couple (symbole, état)
+    /// * `_fsm` - Les états, l'alphabet et les états finaux de l'automate
+    ///
+    pub fn new(_start : BooleanFormula, _delta : HashMap<Transition<State>, BooleanFormula>, _fsm : FiniteStateMachine) -> Self {
+        AlternatingAutomaton {
+            start: _start,
+            delta: _delta,
+            fsm: _fsm,
+        }
+    }
+
+    /// Créer un automate alternant depuis un objet json
+    ///
+    /// L'objet contient `alphabet` et `ends`, `start` une formule et `delta` une liste de transitions
+    /// `{"state": "p", "symbol": "a", "formula": {"and": ["p", "q"]}}`; les formules suivent le schéma de
+    /// `BooleanFormula::from_json`. Les états sont ceux apparaissant dans les formules, les transitions et `ends`.
+    ///
+    /// # Return
+    ///
+    /// * `Result<AlternatingAutomaton, String>` - L'automate, une erreur si une clé manque ou si une formule est invalide
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use automate::*;
+    /// fn main() {
+    ///     //les mots contenant au moins un a et au moins un b
+    ///     let automaton : AlternatingAutomaton = AlternatingAutomaton::from_json_file("src/automates/AFA1.json").unwrap();
+    ///     assert_eq!(automaton.get_start().to_text(), "p & q");
+    ///     assert!(automaton.accept("bba"));
+    ///     assert!(!automaton.accept("aaa"));
+    /// }
+    /// ```
+    ///
+    pub fn from_json(content_json: &Map<String, Value>) -> Result<Self, String> {
+        let text = |value : &Value, key : &str| value.as_str().map(String::from).ok_or_else(|| format!("{} must be a string", key));
+        let array = |key : &str| content_json.get(key).and_then(|value| value.as_array()).ok_or_else(|| format!("missing array {}", key));
+        let alphabet : HashSet<Symbol> = array("alphabet")?.iter().map(|symbol| text(symbol, "symbol").map(Symbol::new)).collect::<Result<_, _>>()?;
+        let ends : HashSet<State> = array("ends")?.iter().map(|state| text(state, "state").map(State::new)).collect::<Result<_, _>>()?;
+        let start : BooleanFormula = BooleanFormula::from_json(content_json.get("start").ok_or_else(|| String::from("missing formula start"))?)?;
+        let mut states : HashSet<State> = ends.iter().cloned().chain(start.get_states()).collect();
+        let mut delta : HashMap<Transition<State>, BooleanFormula> = HashMap::new();
+        for (index, element_delta) in array("delta")?.iter().enumerate() {
+            let transition_json : &Map<String, Value> = element_delta.as_object().ok_or_else(|| format!("transition {} is not an object", index))?;
+            let field = |key : &str| transition_json.get(key).ok_or_else(|| format!("transition {} has no {}", index, key));
+            let state : State = State::new(text(field("state")?, "state")?);
+            let symbol : Symbol = Symbol::new(text(field("symbol")?, "symbol")?);
+            let formula : BooleanFormula = BooleanFormula::from_json(field("formula")?)?;
+            states.insert(state.clone());
+            states.extend(formula.get_states());
+            delta.insert(Transition::new(symbol, state), formula);
+        }
+        Ok(AlternatingAutomaton::new(start, delta, FiniteStateMachine::new(states, alphabet, ends)))
+    }
+
+    /// Créer un automate alternant depuis un chemin vers un fichier json, voir `from_json`
+    pub fn from_json_file(path: &str) -> Result<Self, String> {
+        let content : String = fs::read_to_string(path).map_err(|error| format!("{}: {}", path, error))?;
+        let content_json : Value = from_str::<Value>(&content).map_err(|error| format!("{}: {}", path, error))?;
+        AlternatingAutomaton::from_json(content_json.as_object().ok_or_else(|| format!("{}: expected a json object", path))?)
+    }
+
+    /// Retourne l'automate sous forme json, voir `from_json`
+    pub fn to_json(&self) -> Value {
+        let names = |mut values : Vec<&String>| -> Value {
+            values.sort();
+            Value::Array(values.into_iter().map(|name| Value::String(name.clone())).collect())
+        };
+        let alphabet : Value = names(self.get_alphabet().iter().map(|symbol| symbol.get_value()).collect());
+        let ends : Value = names(self.get_ends().iter().map(|state| state.get_name()).collect());
+        let mut transitions : Vec<(&String, &String, &BooleanFormula)> = self.delta.iter()
+            .map(|(transition, formula)| (transition.get_content().get_name(), transition.get_symbol().get_value(), formula))
+            .collect();
+        transitions.sort_by_key(|(state, symbol, _)| (*state, *symbol));
+        let delta : Vec<Value> = transitions.into_iter().map(|(state, symbol, formula)| {
+            let mut transition : Map<String, Value> = Map::new();
+            transition.insert(String::from("state"), Value::String(state.clone()));
+            transition.insert(String::from("symbol"), Value::String(symbol.clone()));
+            transition.insert(String::from("formula"), formula.to_json());
+            Value::Object(transition)
+        }).collect();
+        let mut content : Map<String, Value> = Map::new();
+        content.insert(String::from("alphabet"), alphabet);
+        content.insert(String::from("start"), self.start.to_json());
+        content.insert(String::from("ends"), ends);
+        content.insert(String::from("delta"), Value::Array(delta));
+        Value::Object(content)
+    }
+
+    /// Créer un automate alternant équivalent à un automate non déterministe: les images deviennent des disjonctions
+    pub fn from_nfa(nfa : &NonDeterministicFiniteAutomaton) -> Self {
+        let disjunction = |states : &HashSet<State>| -> BooleanFormula {
+            let mut atoms : Vec<State> = states.iter().cloned().collect();
+            atoms.sort();
+            BooleanFormula::Or(atoms.into_iter().map(BooleanFormula::Atom).collect())
+        };
+        let delta : HashMap<Transition<State>, BooleanFormula> = nfa.get_delta().iter()
+            .map(|(transition, images)| (transition.clone(), disjunction(images)))
+            .collect();
+        let fsm : FiniteStateMachine = FiniteStateMachine::new(nfa.get_states().clone(), nfa.get_alphabet().clone(), nfa.get_ends().clone());
+        AlternatingAutomaton::new(disjunction(nfa.get_starts()), delta, fsm)
+    }
+
+    /// Retourne la formule initiale de l'automate
+    pub fn get_start(&self) -> &BooleanFormula {
+        &self.start
+    }
+
+    /// Retourne la fonction de transition de l'automate
+    pub fn get_delta(&self) -> &HashMap<Transition<State>, BooleanFormula> {
+        &self.delta
+    }
+
+    /// Retourne les états de l'automate
+    pub fn get_states(&self) -> &HashSet<State> {
+        self.fsm.get_states()
+    }
+
+    /// Retourne l'alphabet de l'automate
+    pub fn get_alphabet(&self) -> &HashSet<Symbol> {
+        self.fsm.get_alphabet()
+    }
+
+    /// Retourne les états finaux de l'automate
+    pub fn get_ends(&self) -> &HashSet<State> {
+        self.fsm.get_ends()
+    }
+
+    /// Retourne la formule image d'une transition, `false` si la transition est absente
+    pub fn apply_delta(&self, transition : &Transition<State>) -> BooleanFormula {
+        self.delta.get(transition).cloned().unwrap_or(BooleanFormula::False)
+    }
+
+    /// Indique si l'état `state` accepte le suffixe de `word` commençant à `position`, en mémorisant les résultats
+    fn accepts_from(&self, state : &State, word : &[Symbol], position : usize, memo : &mut HashMap<(State, usize), bool>) -> bool {
+        if position == word.len() {
+            return self.get_ends().contains(state);
+        }
+        if let Some(accepted) = memo.get(&(state.clone(), position)) {
+            return *accepted;
+        }
+        let formula : BooleanFormula = self.apply_delta(&Transition::new(word[position].clone(), state.clone()));
+        let accepted : bool = formula.evaluate(&mut |image : &State| self.accepts_from(image, word, position + 1, memo));
+        memo.insert((state.clone(), position), accepted);
+        accepted
+    }
+
+    /// indique si un mot, donné comme une suite de symboles, est accepté dans la langue de l'automate
+    ///
+    /// La formule initiale est évaluée récursivement: un état vaut vrai s'il accepte la suite du mot, c'est-à-dire
+    /// s'il est final en fin de mot ou si la formule de sa transition est vraie à la position suivante.
+    pub fn accept_symbols(&self, word : &[Symbol]) -> bool {
+        let mut memo : HashMap<(State, usize), bool> = HashMap::new();
+        self.start.evaluate(&mut |state : &State| self.accepts_from(state, word, 0, &mut memo))
+    }
+
+    /// indique si un mot est accepté dans la langue de l'automate, chaque caractère étant un symbole
+    pub fn accept(&self, _word : &str) -> bool {
+        let word : Vec<Symbol> = to_symbols(_word);
+        self.accept_symbols(&word)
+    }
+
+    /// Convertit l'automate en automate non déterministe, dont les états sont des ensembles d'états
+    ///
+    /// Un état `{p,q}` de l'automate obtenu demande que `p` et `q` acceptent tous deux la suite du mot; ses images
+    /// sont les unions des modèles minimaux des formules de `p` et de `q`. Le nombre d'états est au plus
+    /// exponentiel en celui de l'automate alternant, seuls les ensembles accessibles sont construits.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use automate::*;
+    /// fn main() {
+    ///     let automaton : AlternatingAutomaton = AlternatingAutomaton::from_json_file("src/automates/AFA1.json").unwrap();
+    ///     let nfa : NonDeterministicFiniteAutomaton = automaton.to_nfa();
+    ///     assert!(nfa.accept("ab"));
+    ///     assert!(!nfa.accept("bb"));
+    /// }
+    /// ```
+    ///
+    pub fn to_nfa(&self) -> NonDeterministicFiniteAutomaton {
+        let starts : Vec<BTreeSet<State>> = self.start.minimal_models();
+        let mut seen : HashSet<BTreeSet<State>> = starts.iter().cloned().collect();
+        let mut queue : VecDeque<BTreeSet<State>> = starts.iter().cloned().collect();
+        let mut delta : HashMap<Transition<State>, HashSet<State>> = HashMap::new();
+        let mut states : HashSet<State> = HashSet::new();
+        let mut ends : HashSet<State> = HashSet::new();
+        let mut names : SetNames = SetNames::default();
+        let mut alphabet : Vec<Symbol> = self.get_alphabet().iter().cloned().collect();
+        alphabet.sort();
+        while let Some(conjunction) = queue.pop_front() {
+            let name : State = names.name(&conjunction);
+            states.insert(name.clone());
+            if conjunction.iter().all(|state| self.get_ends().contains(state)) {
+                ends.insert(name.clone());
+            }
+            for symbol in &alphabet {
+                //chaque état de la conjonction choisit un modèle de sa formule
+                let images : Vec<BTreeSet<State>> = conjunction.iter().fold(vec![BTreeSet::new()], |images, state| {
+                    let models : Vec<BTreeSet<State>> = self.apply_delta(&Transition::new(symbol.clone(), state.clone())).minimal_models();
+                    minimal_sets(images.iter()
+                        .flat_map(|image| models.iter().map(move |model| image.union(model).cloned().collect()))
+                        .collect())
+                });
+                if images.is_empty() {
+                    continue;
+                }
+                let targets : &mut HashSet<State> = delta.entry(Transition::new(symbol.clone(), name.clone())).or_default();
+                for image in images {
+                    targets.insert(names.name(&image));
+                    if seen.insert(image.clone()) {
+                        queue.push_back(image);
+                    }
+                }
+            }
+        }
+        let starts : HashSet<State> = starts.iter().map(|start| names.name(start)).collect();
+        NonDeterministicFiniteAutomaton::new(starts, delta, FiniteStateMachine::new(states, self.get_alphabet().clone(), ends))
+    }
+
+    /// Convertit l'automate en automate déterministe, par déterminisation de `to_nfa`
+    ///
+    /// Les états sont des ensembles d'ensembles d'états: leur nombre est au plus doublement exponentiel en celui de
+    /// l'automate alternant.
+    pub fn to_dfa(&self) -> DeterministicFiniteAutomaton {
+        self.to_nfa().determinize()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use super::super::test_words::enumerate_words;
+    use serde_json::json;
+
+    #[test]
+    fn conversions_preserve_language() {
+        let automaton : AlternatingAutomaton = AlternatingAutomaton::from_json_file("src/automates/AFA1.json").unwrap();
+        let nfa : NonDeterministicFiniteAutomaton = automaton.to_nfa();
+        let dfa : DeterministicFiniteAutomaton = automaton.to_dfa();
+        let round_trip : AlternatingAutomaton = AlternatingAutomaton::from_json(automaton.to_json().as_object().unwrap()).unwrap();
+        let from_nfa : AlternatingAutomaton = AlternatingAutomaton::from_nfa(&nfa);
+        for word in enumerate_words("ab", 6) {
+            let expected : bool = word.contains('a') && word.contains('b');
+            assert_eq!(automaton.accept(&word), expected, "{}", word);
+            assert_eq!(nfa.accept(&word), expected, "{}", word);
+            assert_eq!(dfa.accept(&word), expected, "{}", word);
+            assert_eq!(round_trip.accept(&word), expected, "{}", word);
+            assert_eq!(from_nfa.accept(&word), expected, "{}", word);
+        }
+    }
+
+    #[test]
+    fn universal_branching() {
+        //mots sur {a,b} de longueur au moins 2 dont la deuxième lettre est un a et la longueur est paire
+        let automaton : AlternatingAutomaton = AlternatingAutomaton::from_json(json!({
+            "alphabet": ["a", "b"],
+            "start": {"and": ["second", "even"]},
+            "ends": ["even", "ok"],
+            "delta": [
+                {"state": "second", "symbol": "a", "formula": "first"},
+                {"state": "second", "symbol": "b", "formula": "first"},
+                {"state": "first", "symbol": "a", "formula": "ok"},
+                {"state": "ok", "symbol": "a", "formula": "ok"},
+                {"state": "ok", "symbol": "b", "formula": "ok"},
+                {"state": "even", "symbol": "a", "formula": "odd"},
+                {"state": "even", "symbol": "b", "formula": "odd"},
+                {"state": "odd", "symbol": "a", "formula": "even"},
+                {"state": "odd", "symbol": "b", "formula": "even"}
+            ]
+        }).as_object().unwrap()).unwrap();
+        let dfa : DeterministicFiniteAutomaton = automaton.to_dfa();
+        for word in enumerate_words("ab", 6) {
+            let expected : bool = word.len() % 2 == 0 && word.chars().nth(1) == Some('a');
+            assert_eq!(automaton.accept(&word), expected, "{}", word);
+            assert_eq!(dfa.accept(&word), expected, "{}", word);
+        }
+        let error : String = AlternatingAutomaton::from_json(json!({
+            "alphabet": ["a"], "start": {"xor": ["p"]}, "ends": [], "delta": []
+        }).as_object().unwrap()).unwrap_err();
+        assert_eq!(error, "unknown operator 'xor', expected 'and' or 'or'");
+    }
+}
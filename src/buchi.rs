@@ -0,0 +1,312 @@
+use super::{Transition, State, Symbol, FiniteStateMachine, NonDeterministicFiniteAutomaton, to_symbols};
+use super::paths::{self, Adjacency};
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::fs;
+use serde_json::{Value, from_str, map::Map};
+
+/// Automate de Büchi: un automate non déterministe lisant des mots infinis
+///
+/// Un mot infini est accepté s'il existe une exécution qui passe infiniment souvent par un état acceptant.
+/// Les états acceptants sont les états finaux de la machine à état fini sous-jacente.
+#[derive(Debug, Clone)]
+pub struct BuchiAutomaton {
+    starts: HashSet<State>,
+    delta: HashMap<Transition<State>, HashSet<State>>,
+    fsm: FiniteStateMachine,
+}
+
+impl BuchiAutomaton {
+    /// Créer un automate de Büchi
+    ///
+    /// # Arguments
+    ///
+    /// * `_starts` - Les états initiaux de l'automate
+    /// * `_delta` - Une HashMap decrivant les transitions de l'automate, comme pour un automate non déterministe
+    /// * `_fsm` - La machine à état fini décrivant l'automate, ses états finaux étant les états acceptants
+    ///
+    /// # Return
+    ///
+    /// * `BuchiAutomaton` - L'automate de Büchi correspondant
+    ///
+    pub fn new(_starts : HashSet<State>, _delta : HashMap<Transition<State>, HashSet<State>>, _fsm : FiniteStateMachine) -> Self {
+        BuchiAutomaton {
+            starts: _starts,
+            delta: _delta,
+            fsm: _fsm,
+        }
+    }
+
+    /// Créer un automate de Büchi depuis un contenu json, au format d'un automate non déterministe
+    ///
+    /// Les états de `ends` sont les états acceptants.
+    pub fn from_json(content_json: &Map<String, Value>) -> Self {
+        BuchiAutomaton::from_nfa(&NonDeterministicFiniteAutomaton::from_json(content_json))
+    }
+
+    /// Créer un automate de Büchi depuis un chemin vers un fichier json, voir `from_json`
+    pub fn from_json_file(path: &str) -> Self {
+        let content_json: Value = {
+            // Charge le contenu du fichier en tant que String
+            let content : String = fs::read_to_string(path).unwrap();
+            // Parse le texte en structure Json
+            from_str::<Value>(&content).unwrap()
+        };
+        BuchiAutomaton::from_json(content_json.as_object().unwrap())
+    }
+
+    /// Créer un automate de Büchi ayant les mêmes transitions qu'un automate non déterministe, ses états finaux devenant acceptants
+    pub fn from_nfa(nfa : &NonDeterministicFiniteAutomaton) -> Self {
+        let fsm : FiniteStateMachine = FiniteStateMachine::new(nfa.get_states().clone(), nfa.get_alphabet().clone(), nfa.get_ends().clone());
+        BuchiAutomaton::new(nfa.get_starts().clone(), nfa.get_delta().clone(), fsm)
+    }
+
+    /// Retourne l'automate non déterministe ayant les mêmes transitions, ses états finaux étant les états acceptants
+    pub fn to_nfa(&self) -> NonDeterministicFiniteAutomaton {
+        NonDeterministicFiniteAutomaton::new(self.starts.clone(), self.delta.clone(), self.fsm.clone())
+    }
+
+    /// Retourne les états initiaux de l'automate
+    pub fn get_starts(&self) -> &HashSet<State> {
+        &self.starts
+    }
+
+    /// Retourne les transitions de l'automate
+    pub fn get_delta(&self) -> &HashMap<Transition<State>, HashSet<State>> {
+        &self.delta
+    }
+
+    /// Retourne les états de l'automate
+    pub fn get_states(&self) -> &HashSet<State> {
+        self.fsm.get_states()
+    }
+
+    /// Retourne l'alphabet de l'automate
+    pub fn get_alphabet(&self) -> &HashSet<Symbol> {
+        self.fsm.get_alphabet()
+    }
+
+    /// Retourne les états acceptants de l'automate
+    pub fn get_ends(&self) -> &HashSet<State> {
+        self.fsm.get_ends()
+    }
+
+    pub fn apply_delta(&self, transition : Transition<State>) -> Option<&HashSet<State>> {
+        self.delta.get(&transition)
+    }
+
+    /// Retourne la liste d'adjacence de l'automate (etat -> couples (symbole, image) triés)
+    fn adjacency(&self) -> Adjacency {
+        paths::build_adjacency(self.delta.iter().flat_map(|(transition, images)| {
+            images.iter().map(move |image| (transition.get_content().clone(), transition.get_symbol().clone(), image.clone()))
+        }))
+    }
+
+    /// Retourne le produit de deux automates de Büchi, qui accepte l'intersection de leurs langages
+    ///
+    /// Les états `(p,q,i)` mémorisent l'automate dont on attend le prochain passage par un état acceptant: la
+    /// copie 1 passe dans la copie 2 en quittant un état acceptant de `self`, la copie 2 revient dans la copie 1
+    /// en quittant un état acceptant de `other`. Les états acceptants sont les états `(p,q,1)` où `p` est
+    /// acceptant. Seuls les états accessibles sont construits, sur l'alphabet commun.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use automate::*;
+    /// fn main() {
+    ///     //une infinité de a, et un nombre fini de b
+    ///     let infinitely_a : BuchiAutomaton = BuchiAutomaton::from_json_file("src/automates/BUCHI1.json");
+    ///     let finitely_b : BuchiAutomaton = BuchiAutomaton::from_json_file("src/automates/BUCHI2.json");
+    ///     let product : BuchiAutomaton = infinitely_a.product(&finitely_b);
+    ///     assert!(product.accept_lasso("bab", "a"));
+    ///     assert!(!product.accept_lasso("a", "ab"));
+    /// }
+    /// ```
+    ///
+    /// # Return
+    ///
+    /// * `BuchiAutomaton` - L'automate produit
+    ///
+    pub fn product(&self, other : &BuchiAutomaton) -> BuchiAutomaton {
+        let name = |state : &State, other_state : &State, copy : usize| State::new(format!("({},{},{})", state.get_name(), other_state.get_name(), copy));
+        let mut alphabet : Vec<Symbol> = self.get_alphabet().intersection(other.get_alphabet()).cloned().collect();
+        alphabet.sort();
+        let mut starts : HashSet<State> = HashSet::new();
+        let mut queue : VecDeque<(State, State, usize)> = VecDeque::new();
+        let mut states : HashSet<State> = HashSet::new();
+        for start in &self.starts {
+            for other_start in &other.starts {
+                let state : State = name(start, other_start, 1);
+                starts.insert(state.clone());
+                if states.insert(state) {
+                    queue.push_back((start.clone(), other_start.clone(), 1));
+                }
+            }
+        }
+        let mut delta : HashMap<Transition<State>, HashSet<State>> = HashMap::new();
+        let mut ends : HashSet<State> = HashSet::new();
+        while let Some((state, other_state, copy)) = queue.pop_front() {
+            let current : State = name(&state, &other_state, copy);
+            if copy == 1 && self.get_ends().contains(&state) {
+                ends.insert(current.clone());
+            }
+            let next_copy : usize = match copy {
+                1 if self.get_ends().contains(&state) => 2,
+                2 if other.get_ends().contains(&other_state) => 1,
+                _ => copy,
+            };
+            for symbol in &alphabet {
+                let images : Option<&HashSet<State>> = self.apply_delta(Transition::new(symbol.clone(), state.clone()));
+                let other_images : Option<&HashSet<State>> = other.apply_delta(Transition::new(symbol.clone(), other_state.clone()));
+                for image in images.into_iter().flatten() {
+                    for other_image in other_images.into_iter().flatten() {
+                        let next : State = name(image, other_image, next_copy);
+                        delta.entry(Transition::new(symbol.clone(), current.clone())).or_default().insert(next.clone());
+                        if states.insert(next) {
+                            queue.push_back((image.clone(), other_image.clone(), next_copy));
+                        }
+                    }
+                }
+            }
+        }
+        BuchiAutomaton::new(starts, delta, FiniteStateMachine::new(states, alphabet.into_iter().collect(), ends))
+    }
+
+    /// Retourne l'automate de Büchi déterministe acceptant le seul mot `prefix · period^ω`
+    fn lasso(prefix : &[Symbol], period : &[Symbol]) -> BuchiAutomaton {
+        let word : Vec<&Symbol> = prefix.iter().chain(period.iter()).collect();
+        let name = |position : usize| State::new(position.to_string());
+        let mut delta : HashMap<Transition<State>, HashSet<State>> = HashMap::new();
+        for (position, symbol) in word.iter().enumerate() {
+            let image : usize = if position + 1 == word.len() { prefix.len() } else { position + 1 };
+            delta.insert(Transition::new((*symbol).clone(), name(position)), HashSet::from([name(image)]));
+        }
+        let states : HashSet<State> = (0..word.len()).map(name).collect();
+        let alphabet : HashSet<Symbol> = word.into_iter().cloned().collect();
+        BuchiAutomaton::new(HashSet::from([name(0)]), delta, FiniteStateMachine::new(states, alphabet, HashSet::from([name(prefix.len())])))
+    }
+
+    /// Indique si le mot ultimement périodique `prefix · period^ω` est accepté, voir `accept_lasso`
+    pub fn accept_lasso_symbols(&self, prefix : &[Symbol], period : &[Symbol]) -> bool {
+        !period.is_empty() && self.product(&BuchiAutomaton::lasso(prefix, period)).accepted_lasso().is_some()
+    }
+
+    /// Indique si le mot infini `prefix · period · period · ...` est accepté
+    ///
+    /// Le mot est reconnu par un automate déterministe en forme de lasso, dont le produit avec l'automate est
+    /// non vide si et seulement si le mot est accepté.
+    ///
+    /// # Arguments
+    ///
+    /// * `prefix` - Le préfixe du mot, lu une fois
+    /// * `period` - La période du mot, répétée indéfiniment; une période vide ne décrit pas un mot infini et est refusée
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use automate::*;
+    /// fn main() {
+    ///     let infinitely_a : BuchiAutomaton = BuchiAutomaton::from_json_file("src/automates/BUCHI1.json");
+    ///     assert!(infinitely_a.accept_lasso("bbb", "ba"));
+    ///     assert!(!infinitely_a.accept_lasso("aaa", "b"));
+    /// }
+    /// ```
+    ///
+    pub fn accept_lasso(&self, prefix : &str, period : &str) -> bool {
+        self.accept_lasso_symbols(&to_symbols(prefix), &to_symbols(period))
+    }
+
+    /// Cherche un mot accepté de la forme `prefix · period^ω`
+    ///
+    /// Le langage est non vide si et seulement si un état acceptant accessible appartient à une composante
+    /// fortement connexe non triviale. Le préfixe est alors le plus court mot menant à cet état et la période
+    /// le plus court cycle qui y revient.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use automate::*;
+    /// fn main() {
+    ///     let finitely_b : BuchiAutomaton = BuchiAutomaton::from_json_file("src/automates/BUCHI2.json");
+    ///     let (prefix, period) : (Vec<Symbol>, Vec<Symbol>) = finitely_b.accepted_lasso().unwrap();
+    ///     assert_eq!(prefix, vec![Symbol::from_str("a")]);
+    ///     assert_eq!(period, vec![Symbol::from_str("a")]);
+    /// }
+    /// ```
+    ///
+    /// # Return
+    ///
+    /// * `Option<(Vec<Symbol>, Vec<Symbol>)>` - Le préfixe et la période d'un mot accepté, `None` si le langage est vide
+    ///
+    pub fn accepted_lasso(&self) -> Option<(Vec<Symbol>, Vec<Symbol>)> {
+        let adjacency : Adjacency = self.adjacency();
+        let mut candidates : Vec<State> = Vec::new();
+        for component in paths::strongly_connected_components(&adjacency, &self.starts) {
+            let mut accepting : Vec<&State> = component.iter().filter(|state| self.get_ends().contains(*state)).collect();
+            accepting.sort();
+            for state in accepting {
+                let looping : bool = component.len() > 1 || adjacency.get(state).into_iter().flatten().any(|(_, image)| image == state);
+                if looping {
+                    candidates.push(state.clone());
+                }
+            }
+        }
+        let accepting : &State = candidates.first()?;
+        let prefix : Vec<Symbol> = paths::shortest_word(&adjacency, &self.starts, &HashSet::from([accepting.clone()]))?;
+        let period : Vec<Symbol> = adjacency.get(accepting).into_iter().flatten()
+            .filter_map(|(symbol, image)| {
+                let mut cycle : Vec<Symbol> = vec![symbol.clone()];
+                cycle.extend(paths::shortest_word(&adjacency, &HashSet::from([image.clone()]), &HashSet::from([accepting.clone()]))?);
+                Some(cycle)
+            })
+            .min_by_key(|cycle| cycle.len())?;
+        Some((prefix, period))
+    }
+
+    /// Indique si l'automate n'accepte aucun mot infini, voir `accepted_lasso`
+    pub fn is_empty(&self) -> bool {
+        self.accepted_lasso().is_none()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use super::super::test_words::enumerate_words;
+
+    #[test]
+    fn lassos() {
+        let infinitely_a : BuchiAutomaton = BuchiAutomaton::from_json_file("src/automates/BUCHI1.json");
+        let finitely_b : BuchiAutomaton = BuchiAutomaton::from_json_file("src/automates/BUCHI2.json");
+        let product : BuchiAutomaton = infinitely_a.product(&finitely_b);
+        let words : Vec<String> = enumerate_words("ab", 3);
+        for prefix in &words {
+            for period in words.iter().filter(|period| !period.is_empty()) {
+                assert_eq!(infinitely_a.accept_lasso(prefix, period), period.contains('a'), "{} {}", prefix, period);
+                assert_eq!(finitely_b.accept_lasso(prefix, period), !period.contains('b'), "{} {}", prefix, period);
+                assert_eq!(product.accept_lasso(prefix, period), !period.contains('b'), "{} {}", prefix, period);
+            }
+        }
+        assert!(!infinitely_a.accept_lasso("a", ""));
+    }
+
+    #[test]
+    fn emptiness() {
+        let infinitely_a : BuchiAutomaton = BuchiAutomaton::from_json_file("src/automates/BUCHI1.json");
+        let (prefix, period) : (Vec<Symbol>, Vec<Symbol>) = infinitely_a.accepted_lasso().unwrap();
+        assert!(infinitely_a.accept_lasso_symbols(&prefix, &period));
+        //l'automate du seul mot a·b^ω
+        let once_a : BuchiAutomaton = BuchiAutomaton::lasso(&[Symbol::from_str("a")], &[Symbol::from_str("b")]);
+        assert!(once_a.accept_lasso("ab", "bb"));
+        assert!(!once_a.accept_lasso("", "ab"));
+        let mut delta : HashMap<Transition<State>, HashSet<State>> = once_a.get_delta().clone();
+        delta.insert(Transition::new(Symbol::from_str("a"), State::from_str("1")), HashSet::from([State::from_str("0")]));
+        //l'état acceptant 0 n'est sur aucun cycle, sauf en ajoutant la transition de 1 vers 0
+        let ends : HashSet<State> = HashSet::from([State::from_str("0")]);
+        let once : BuchiAutomaton = BuchiAutomaton::new(once_a.get_starts().clone(), once_a.get_delta().clone(), FiniteStateMachine::new(once_a.get_states().clone(), once_a.get_alphabet().clone(), ends.clone()));
+        assert!(once.is_empty());
+        let cycling : BuchiAutomaton = BuchiAutomaton::new(once_a.get_starts().clone(), delta, FiniteStateMachine::new(once_a.get_states().clone(), once_a.get_alphabet().clone(), ends));
+        assert_eq!(cycling.accepted_lasso(), Some((Vec::new(), vec![Symbol::from_str("a"), Symbol::from_str("a")])));
+        assert!(cycling.accept_lasso("", "aba"));
+        assert!(!cycling.accept_lasso("", "ab"));
+    }
+}
@@ -0,0 +1,177 @@
+/// Caractère suivant `letter`, en sautant les codes réservés aux surrogates
+fn next_char(letter : char) -> Option<char> {
+    match letter {
+        '\u{D7FF}' => Some('\u{E000}'),
+        _ => char::from_u32(letter as u32 + 1),
+    }
+}
+
+/// Caractère précédant `letter`, en sautant les codes réservés aux surrogates
+fn previous_char(letter : char) -> Option<char> {
+    match letter {
+        '\u{E000}' => Some('\u{D7FF}'),
+        _ => (letter as u32).checked_sub(1).and_then(char::from_u32),
+    }
+}
+
+/// Ensemble de caractères, représenté par des intervalles
+///
+/// Les intervalles sont bornes comprises, triés, disjoints et non contigus: deux ensembles égaux ont donc la
+/// même représentation.
+#[derive(Debug, Clone, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub struct CharSet {
+    ranges: Vec<(char, char)>,
+}
+
+impl CharSet {
+    /// Créer un ensemble de caractères depuis des intervalles quelconques, bornes comprises
+    ///
+    /// Les intervalles vides (`first > last`) sont ignorés, les autres sont triés et fusionnés.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use automate::*;
+    /// fn main() {
+    ///     let set : CharSet = CharSet::new(&[('d', 'f'), ('a', 'c'), ('0', '9')]);
+    ///     assert_eq!(set.get_ranges(), &vec![('0', '9'), ('a', 'f')]);
+    ///     assert!(set.contains('e'));
+    ///     assert!(!set.contains('g'));
+    /// }
+    /// ```
+    ///
+    pub fn new(_ranges : &[(char, char)]) -> Self {
+        let mut sorted : Vec<(char, char)> = _ranges.iter().filter(|(first, last)| first <= last).cloned().collect();
+        sorted.sort();
+        let mut ranges : Vec<(char, char)> = Vec::new();
+        for (first, last) in sorted {
+            match ranges.last_mut() {
+                Some((_, previous)) if next_char(*previous).is_none_or(|next| first <= next) => {
+                    *previous = (*previous).max(last);
+                },
+                _ => ranges.push((first, last)),
+            }
+        }
+        CharSet {
+            ranges,
+        }
+    }
+
+    /// Retourne l'ensemble vide
+    pub fn empty() -> Self {
+        CharSet::new(&[])
+    }
+
+    /// Retourne l'ensemble de tous les caractères
+    pub fn full() -> Self {
+        CharSet::new(&[('\0', char::MAX)])
+    }
+
+    /// Retourne l'ensemble réduit à `letter`
+    pub fn from_char(letter : char) -> Self {
+        CharSet::new(&[(letter, letter)])
+    }
+
+    /// Retourne les intervalles de l'ensemble, triés, disjoints et non contigus
+    pub fn get_ranges(&self) -> &Vec<(char, char)> {
+        &self.ranges
+    }
+
+    /// Indique si l'ensemble est vide
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+
+    /// Indique si `letter` appartient à l'ensemble
+    pub fn contains(&self, letter : char) -> bool {
+        self.ranges.iter().any(|(first, last)| *first <= letter && letter <= *last)
+    }
+
+    /// Retourne le nombre de caractères de l'ensemble
+    pub fn len(&self) -> usize {
+        self.ranges.iter().map(|(first, last)| {
+            let surrogates : u32 = if (*first as u32) < 0xD800 && (*last as u32) > 0xDFFF { 0x800 } else { 0 };
+            (*last as u32 - *first as u32 + 1 - surrogates) as usize
+        }).sum()
+    }
+
+    /// Retourne les caractères de l'ensemble, dans l'ordre
+    pub fn chars(&self) -> impl Iterator<Item = char> + '_ {
+        self.ranges.iter().flat_map(|(first, last)| *first..=*last)
+    }
+
+    /// Retourne l'union de deux ensembles
+    pub fn union(&self, other : &CharSet) -> CharSet {
+        let ranges : Vec<(char, char)> = self.ranges.iter().chain(other.ranges.iter()).cloned().collect();
+        CharSet::new(&ranges)
+    }
+
+    /// Retourne le complémentaire de l'ensemble parmi tous les caractères
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use automate::*;
+    /// fn main() {
+    ///     let digits : CharSet = CharSet::new(&[('0', '9')]);
+    ///     assert_eq!(digits.complement().get_ranges(), &vec![('\0', '/'), (':', char::MAX)]);
+    ///     assert_eq!(digits.complement().complement(), digits);
+    /// }
+    /// ```
+    ///
+    pub fn complement(&self) -> CharSet {
+        let mut ranges : Vec<(char, char)> = Vec::new();
+        let mut start : Option<char> = Some('\0');
+        for (first, last) in &self.ranges {
+            if let (Some(begin), Some(end)) = (start, previous_char(*first)) {
+                ranges.push((begin, end));
+            }
+            start = next_char(*last);
+        }
+        if let Some(begin) = start {
+            ranges.push((begin, char::MAX));
+        }
+        CharSet::new(&ranges)
+    }
+
+    /// Retourne l'intersection de deux ensembles
+    pub fn intersection(&self, other : &CharSet) -> CharSet {
+        let mut ranges : Vec<(char, char)> = Vec::new();
+        for (first, last) in &self.ranges {
+            for (other_first, other_last) in &other.ranges {
+                ranges.push((*first.max(other_first), *last.min(other_last)));
+            }
+        }
+        CharSet::new(&ranges)
+    }
+
+    /// Retourne les caractères de l'ensemble qui n'appartiennent pas à `other`
+    pub fn difference(&self, other : &CharSet) -> CharSet {
+        self.intersection(&other.complement())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn operations() {
+        let letters : CharSet = CharSet::new(&[('a', 'z'), ('A', 'Z')]);
+        let hexadecimal : CharSet = CharSet::new(&[('0', '9'), ('a', 'f'), ('A', 'F')]);
+        assert_eq!(letters.intersection(&hexadecimal), CharSet::new(&[('A', 'F'), ('a', 'f')]));
+        assert_eq!(letters.union(&hexadecimal).len(), 62);
+        assert_eq!(hexadecimal.difference(&letters).get_ranges(), &vec![('0', '9')]);
+        assert_eq!(CharSet::new(&[('a', 'b'), ('c', 'd')]).get_ranges(), &vec![('a', 'd')]);
+        //De Morgan et complémentaire autour des surrogates
+        assert_eq!(letters.union(&hexadecimal).complement(), letters.complement().intersection(&hexadecimal.complement()));
+        assert_eq!(CharSet::full().complement(), CharSet::empty());
+        assert_eq!(CharSet::empty().complement(), CharSet::full());
+        let around : CharSet = CharSet::new(&[('\u{D7FF}', '\u{D7FF}'), ('\u{E000}', '\u{E000}')]);
+        assert_eq!(around.get_ranges().len(), 1);
+        assert_eq!(around.len(), 2);
+        assert_eq!(CharSet::full().len(), 0x110000 - 0x800);
+        assert_eq!(CharSet::from_char(char::MAX).complement().complement(), CharSet::from_char(char::MAX));
+        assert_eq!(hexadecimal.chars().count(), 22);
+    }
+}
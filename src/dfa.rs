@@ -1,7 +1,8 @@
-use super::{Transition, State,Symbol, FiniteStateMachine};
-use std::collections::{HashMap, HashSet};
+use super::{Transition, State,Symbol, FiniteStateMachine, NonDeterministicFiniteAutomaton, fresh_state};
+use super::paths::{self, Adjacency};
+use std::collections::{HashMap, HashSet, VecDeque};
 use std::fs;
-use serde_json::{Value, from_str, map::Map};
+use serde_json::{Value, from_str, map::Map, json, to_string_pretty};
 
 /// Automate a état fini déterministe
 #[derive(Debug, Clone)]
@@ -182,8 +183,8 @@ impl DeterministicFiniteAutomaton {
         let fsm : FiniteStateMachine = FiniteStateMachine::new(states, alphabet, ends);
         DeterministicFiniteAutomaton { 
             start: state_init, 
-            delta: delta, 
-            fsm: fsm
+            delta, 
+            fsm
         }
     }
 
@@ -257,6 +258,58 @@ impl DeterministicFiniteAutomaton {
         DeterministicFiniteAutomaton::from_json(content_json.as_object().unwrap())
     }
 
+    /// Retourne le contenu json de l'automate, au format lu par `from_json`
+    ///
+    /// Les états, l'alphabet et les transitions sont triés afin qu'un même automate donne toujours le même json.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use automate::*;
+    /// fn main() {
+    ///     let dfa : DeterministicFiniteAutomaton = DeterministicFiniteAutomaton::from_json_file("src/automates/DFA1.json");
+    ///     let copy : DeterministicFiniteAutomaton = DeterministicFiniteAutomaton::from_json(dfa.to_json().as_object().unwrap());
+    ///     assert_eq!(copy.get_delta(), dfa.get_delta());
+    /// }
+    /// ```
+    ///
+    /// # Return
+    ///
+    /// * `Value` - Le contenu json de l'automate
+    ///
+    pub fn to_json(&self) -> Value {
+        let mut states : Vec<&String> = self.get_states().iter().map(|state| state.get_name()).collect();
+        states.sort();
+        let mut alphabet : Vec<&String> = self.get_alphabet().iter().map(|symbol| symbol.get_value()).collect();
+        alphabet.sort();
+        let mut ends : Vec<&String> = self.get_ends().iter().map(|state| state.get_name()).collect();
+        ends.sort();
+        let mut transitions : Vec<(&Transition<State>, &State)> = self.get_delta().iter().collect();
+        transitions.sort_by_key(|(transition, _)| (transition.get_content(), transition.get_symbol()));
+        let delta : Vec<Value> = transitions.iter().map(|(transition, image)| json!({
+            "state" : transition.get_content().get_name(),
+            "symbol" : transition.get_symbol().get_value(),
+            "image" : image.get_name()
+        })).collect();
+        json!({
+            "states" : states,
+            "alphabet" : alphabet,
+            "ends" : ends,
+            "start" : self.get_start().get_name(),
+            "delta" : delta
+        })
+    }
+
+    /// Enregistre l'automate dans un fichier json, au format lu par `from_json_file`
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - Le chemin vers le fichier json
+    ///
+    pub fn to_json_file(&self, path: &str) -> std::io::Result<()> {
+        fs::write(path, to_string_pretty(&self.to_json())?)
+    }
+
     /// Retourne l'état de départ de l'automate
     pub fn get_start(&self) -> &State {
         &self.start
@@ -269,21 +322,21 @@ impl DeterministicFiniteAutomaton {
 
     /// Retournes les differents états de l'automate
     pub fn get_states(&self) -> &HashSet<State> {
-        &self.fsm.get_states()
+        self.fsm.get_states()
     }
 
     /// Retourne l'alphabet de l'automate
     pub fn get_alphabet(&self) -> &HashSet<Symbol> {
-        &self.fsm.get_alphabet()
+        self.fsm.get_alphabet()
     }
 
     /// Retourne les états finaux de l'automate
     pub fn get_ends(&self) -> &HashSet<State> {
-        &self.fsm.get_ends()
+        self.fsm.get_ends()
     }
     
     pub fn apply_delta(&self,transition : Transition<State>) -> Option<&State>{
-        self.get_delta().get(&transition).clone()
+        self.get_delta().get(&transition)
     }
 
     /// indique si un mot est accepté dans la langue de l'automate
@@ -304,12 +357,383 @@ impl DeterministicFiniteAutomaton {
         //si l'etat est dans la liste des etats finaux 
         self.get_ends().contains(&state)
     }
+
+    /// indique si un mot, donné comme une suite de symboles, est accepté dans la langue de l'automate
+    ///
+    /// Contrairement à `accept`, les symboles peuvent contenir plusieurs caractères.
+    pub fn accept_symbols(&self, word : &[Symbol]) -> bool {
+        let mut state : State = self.get_start().clone();
+        for symbol in word {
+            state = match self.apply_delta(Transition::new(symbol.clone(), state)) {
+                Some(image) => image.clone(),
+                None => return false,
+            };
+        }
+        self.get_ends().contains(&state)
+    }
+
+    /// Retourne la liste d'adjacence de l'automate (etat -> couples (symbole, image) triés)
+    pub(crate) fn adjacency(&self) -> Adjacency {
+        paths::build_adjacency(self.get_delta().iter().map(|(transition, image)| {
+            (transition.get_content().clone(), transition.get_symbol().clone(), image.clone())
+        }))
+    }
+
+    /// Retourne le plus court mot accepté par l'automate
+    ///
+    /// Le parcours se fait en largeur en suivant l'ordre des symboles, le mot retourné est donc le plus petit
+    /// des mots les plus courts dans l'ordre lexicographique.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use automate::*;
+    /// fn main() {
+    ///     let dfa : DeterministicFiniteAutomaton = DeterministicFiniteAutomaton::from_json_file("src/automates/DFA2.json");
+    ///     let word : Vec<Symbol> = dfa.shortest_accepted_word().unwrap();
+    ///     assert_eq!(word, vec![Symbol::from_str("0"), Symbol::from_str("1")]);
+    /// }
+    /// ```
+    ///
+    /// # Return
+    ///
+    /// * `Option<Vec<Symbol>>` - Le mot le plus court, `None` si le langage est vide
+    ///
+    pub fn shortest_accepted_word(&self) -> Option<Vec<Symbol>> {
+        let starts : HashSet<State> = HashSet::from([self.get_start().clone()]);
+        paths::shortest_word(&self.adjacency(), &starts, self.get_ends())
+    }
+
+    /// Indique si le langage de l'automate est fini
+    pub fn is_finite(&self) -> bool {
+        let starts : HashSet<State> = HashSet::from([self.get_start().clone()]);
+        paths::is_finite(&self.adjacency(), &starts, self.get_ends())
+    }
+
+    /// Retourne le plus long mot accepté par l'automate
+    ///
+    /// # Return
+    ///
+    /// * `Option<Vec<Symbol>>` - Le mot le plus long, `None` si le langage est vide ou infini
+    ///
+    pub fn longest_accepted_word(&self) -> Option<Vec<Symbol>> {
+        let starts : HashSet<State> = HashSet::from([self.get_start().clone()]);
+        paths::longest_word(&self.adjacency(), &starts, self.get_ends())
+    }
+
+    /// Retourne tous les mots acceptés par l'automate, dans l'ordre lexicographique
+    ///
+    /// # Return
+    ///
+    /// * `Option<Vec<Vec<Symbol>>>` - Les mots acceptés, `None` si le langage est infini
+    ///
+    pub fn accepted_words(&self) -> Option<Vec<Vec<Symbol>>> {
+        let starts : HashSet<State> = HashSet::from([self.get_start().clone()]);
+        paths::all_words(&self.adjacency(), &starts, self.get_ends())
+    }
+
+    /// Retourne, pour chaque état, le nombre minimal de symboles à lire pour atteindre un état final
+    ///
+    /// Les états depuis lesquels aucun état final n'est accessible n'apparaissent pas dans la HashMap.
+    /// La table est calculée en un parcours en largeur de tout l'automate: pour suivre la lecture d'un texte,
+    /// elle est calculée une seule fois puis consultée à chaque état atteint.
+    ///
+    /// # Return
+    ///
+    /// * `HashMap<State, usize>` - La distance de chaque état à l'acceptation
+    ///
+    pub fn distances_to_acceptance(&self) -> HashMap<State, usize> {
+        paths::distances_to(&self.adjacency(), self.get_ends())
+    }
+
+    /// Indique si le langage de l'automate est inclus dans celui de `other`
+    ///
+    /// L'automate produit est parcouru en largeur, une transition absente menant dans un puits implicite.
+    /// Le premier couple d'états acceptant pour `self` et refusant pour `other` fournit un contre-exemple,
+    /// qui est donc l'un des plus courts.
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - L'automate dont le langage doit contenir celui de self
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use automate::*;
+    /// fn main() {
+    ///     let dfa1 : DeterministicFiniteAutomaton = DeterministicFiniteAutomaton::from_json_file("src/automates/DFA1.json");
+    ///     let dfa3 : DeterministicFiniteAutomaton = DeterministicFiniteAutomaton::from_json_file("src/automates/DFA3.json");
+    ///     assert_eq!(dfa1.is_subset_of(&dfa1), Ok(()));
+    ///     //"b" est accepté par dfa3 mais pas par dfa1
+    ///     assert_eq!(dfa3.is_subset_of(&dfa1), Err(vec![Symbol::from_str("b")]));
+    /// }
+    /// ```
+    ///
+    /// # Return
+    ///
+    /// * `Result<(), Vec<Symbol>>` - `Ok(())` si l'inclusion est vérifiée, sinon un mot accepté par self et refusé par `other`
+    ///
+    pub fn is_subset_of(&self, other : &DeterministicFiniteAutomaton) -> Result<(), Vec<Symbol>> {
+        let mut alphabet : Vec<Symbol> = self.get_alphabet().union(other.get_alphabet()).cloned().collect();
+        alphabet.sort();
+        //un etat de other à None represente le puits
+        type Pair = (State, Option<State>);
+        let start : Pair = (self.get_start().clone(), Some(other.get_start().clone()));
+        let mut visited : HashSet<Pair> = HashSet::from([start.clone()]);
+        let mut queue : VecDeque<(Pair, Vec<Symbol>)> = VecDeque::from([(start, Vec::new())]);
+        while let Some(((state, other_state), word)) = queue.pop_front() {
+            let other_accepts : bool = other_state.as_ref().is_some_and(|other_state| other.get_ends().contains(other_state));
+            if self.get_ends().contains(&state) && !other_accepts {
+                return Err(word);
+            }
+            for symbol in &alphabet {
+                //si self n'a pas de transition, aucun mot de ce prefixe n'est accepté par self
+                let image : State = match self.apply_delta(Transition::new(symbol.clone(), state.clone())) {
+                    Some(image) => image.clone(),
+                    None => continue,
+                };
+                let other_image : Option<State> = other_state.as_ref().and_then(|other_state| {
+                    other.apply_delta(Transition::new(symbol.clone(), other_state.clone())).cloned()
+                });
+                let pair : Pair = (image, other_image);
+                if visited.insert(pair.clone()) {
+                    let mut next_word : Vec<Symbol> = word.clone();
+                    next_word.push(symbol.clone());
+                    queue.push_back((pair, next_word));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Retourne l'automate produit reconnaissant l'intersection des langages de self et de `other`
+    ///
+    /// Seuls les couples d'états accessibles depuis le couple initial sont construits, chacun étant nommé `(p,q)`.
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - L'automate à intersecter avec self
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use automate::*;
+    /// fn main() {
+    ///     let dfa1 : DeterministicFiniteAutomaton = DeterministicFiniteAutomaton::from_json_file("src/automates/DFA1.json");
+    ///     let dfa3 : DeterministicFiniteAutomaton = DeterministicFiniteAutomaton::from_json_file("src/automates/DFA3.json");
+    ///     let both : DeterministicFiniteAutomaton = dfa1.intersection(&dfa3);
+    ///     assert!(both.accept("ab"));
+    ///     assert!(!both.accept("abc"));
+    ///     assert!(!both.accept("abab"));
+    /// }
+    /// ```
+    ///
+    /// # Return
+    ///
+    /// * `DeterministicFiniteAutomaton` - L'automate de l'intersection, sur les symboles communs aux deux alphabets
+    ///
+    pub fn intersection(&self, other : &DeterministicFiniteAutomaton) -> DeterministicFiniteAutomaton {
+        let alphabet : HashSet<Symbol> = self.get_alphabet().intersection(other.get_alphabet()).cloned().collect();
+        let name = |state : &State, other_state : &State| State::new(format!("({},{})", state.get_name(), other_state.get_name()));
+        let start : State = name(self.get_start(), other.get_start());
+        let mut states : HashSet<State> = HashSet::from([start.clone()]);
+        let mut ends : HashSet<State> = HashSet::new();
+        let mut delta : HashMap<Transition<State>, State> = HashMap::new();
+        let mut queue : VecDeque<(State, State)> = VecDeque::from([(self.get_start().clone(), other.get_start().clone())]);
+        while let Some((state, other_state)) = queue.pop_front() {
+            let pair : State = name(&state, &other_state);
+            if self.get_ends().contains(&state) && other.get_ends().contains(&other_state) {
+                ends.insert(pair.clone());
+            }
+            for symbol in &alphabet {
+                let image : Option<&State> = self.apply_delta(Transition::new(symbol.clone(), state.clone()));
+                let other_image : Option<&State> = other.apply_delta(Transition::new(symbol.clone(), other_state.clone()));
+                if let (Some(image), Some(other_image)) = (image, other_image) {
+                    let image_pair : State = name(image, other_image);
+                    if states.insert(image_pair.clone()) {
+                        queue.push_back((image.clone(), other_image.clone()));
+                    }
+                    delta.insert(Transition::new(symbol.clone(), pair.clone()), image_pair);
+                }
+            }
+        }
+        let fsm : FiniteStateMachine = FiniteStateMachine::new(states, alphabet, ends);
+        DeterministicFiniteAutomaton::new(start, delta, fsm)
+    }
+
+    /// Indique si l'automate est complet: chaque état possède une transition pour chaque symbole de l'alphabet
+    pub fn is_complete(&self) -> bool {
+        self.get_states().iter().all(|state| {
+            self.get_alphabet().iter().all(|symbol| self.get_delta().contains_key(&Transition::new(symbol.clone(), state.clone())))
+        })
+    }
+
+    /// Retourne une copie complète de l'automate
+    ///
+    /// Les transitions manquantes sont redirigées vers un nouvel état puits non final. Si l'automate est
+    /// déjà complet, il est retourné sans modification.
+    pub fn complete(&self) -> DeterministicFiniteAutomaton {
+        if self.is_complete() {
+            return self.clone();
+        }
+        //nom du puits, choisi pour ne pas entrer en collision avec un etat existant
+        let sink : State = fresh_state(self.get_states(), "sink");
+        let mut states : HashSet<State> = self.get_states().clone();
+        states.insert(sink.clone());
+        let mut delta : HashMap<Transition<State>, State> = self.get_delta().clone();
+        for state in &states {
+            for symbol in self.get_alphabet() {
+                delta.entry(Transition::new(symbol.clone(), state.clone())).or_insert_with(|| sink.clone());
+            }
+        }
+        let fsm : FiniteStateMachine = FiniteStateMachine::new(states, self.get_alphabet().clone(), self.get_ends().clone());
+        DeterministicFiniteAutomaton::new(self.get_start().clone(), delta, fsm)
+    }
+
+    /// Parcours en largeur des états accessibles, en suivant l'alphabet trié
+    ///
+    /// Retourne les états dans l'ordre de leur découverte.
+    fn bfs_order(&self) -> Vec<State> {
+        let mut alphabet : Vec<Symbol> = self.get_alphabet().iter().cloned().collect();
+        alphabet.sort();
+        let mut order : Vec<State> = vec![self.get_start().clone()];
+        let mut visited : HashSet<State> = HashSet::from([self.get_start().clone()]);
+        let mut index : usize = 0;
+        while index < order.len() {
+            let state : State = order[index].clone();
+            for symbol in &alphabet {
+                if let Some(image) = self.apply_delta(Transition::new(symbol.clone(), state.clone())) {
+                    if visited.insert(image.clone()) {
+                        order.push(image.clone());
+                    }
+                }
+            }
+            index += 1;
+        }
+        order
+    }
+
+    /// Renomme les états de l'automate en `0..n` dans l'ordre d'un parcours en largeur sur l'alphabet trié
+    ///
+    /// Deux automates déterministes complets et minimaux reconnaissant le même langage ont la même forme
+    /// canonique, quels que soient les noms de leurs états. L'automate n'est pas minimisé ici: pour comparer deux
+    /// langages, canonicaliser le résultat de `minimize`, qui est complet et minimal. Les états inaccessibles sont supprimés.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use automate::*;
+    /// fn main() {
+    ///     let dfa : DeterministicFiniteAutomaton = DeterministicFiniteAutomaton::from_json_file("src/automates/DFA2.json");
+    ///     let canonical : DeterministicFiniteAutomaton = dfa.canonicalize();
+    ///     assert_eq!(canonical.get_start(), &State::from_str("0"));
+    ///     assert!(canonical.accept("0001"));
+    ///     assert_eq!(dfa.minimize().canonicalize().get_delta(), dfa.minimize_brzozowski().canonicalize().get_delta());
+    /// }
+    /// ```
+    ///
+    /// # Return
+    ///
+    /// * `DeterministicFiniteAutomaton` - L'automate dont les états ont été renommés
+    ///
+    pub fn canonicalize(&self) -> DeterministicFiniteAutomaton {
+        let names : HashMap<State, State> = self.bfs_order().into_iter().enumerate()
+            .map(|(index, state)| (state, State::new(index.to_string())))
+            .collect();
+        let mut delta : HashMap<Transition<State>, State> = HashMap::new();
+        for (transition, image) in self.get_delta() {
+            if let Some(state) = names.get(transition.get_content()) {
+                delta.insert(Transition::new(transition.get_symbol().clone(), state.clone()), names[image].clone());
+            }
+        }
+        let ends : HashSet<State> = self.get_ends().iter().filter_map(|end| names.get(end).cloned()).collect();
+        let states : HashSet<State> = names.values().cloned().collect();
+        let fsm : FiniteStateMachine = FiniteStateMachine::new(states, self.get_alphabet().clone(), ends);
+        DeterministicFiniteAutomaton::new(names[self.get_start()].clone(), delta, fsm)
+    }
+
+    /// Cherche un isomorphisme entre les états de self et ceux de `other`
+    ///
+    /// Les deux automates sont parcourus simultanément depuis leurs états initiaux: la bijection est forcée
+    /// par le déterminisme, il suffit donc de vérifier qu'elle est cohérente avec les transitions et les états finaux.
+    /// Un état inaccessible n'est atteint par aucune bijection: les automates qui en ont ne sont isomorphes à aucun autre.
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - L'automate à comparer
+    ///
+    /// # Return
+    ///
+    /// * `Option<HashMap<State, State>>` - La bijection entre les états de self et ceux de `other`, `None` si les automates ne sont pas isomorphes
+    ///
+    pub fn is_isomorphic(&self, other : &DeterministicFiniteAutomaton) -> Option<HashMap<State, State>> {
+        if self.get_alphabet() != other.get_alphabet() {
+            return None;
+        }
+        let mut bijection : HashMap<State, State> = HashMap::from([(self.get_start().clone(), other.get_start().clone())]);
+        let mut inverse : HashMap<State, State> = HashMap::from([(other.get_start().clone(), self.get_start().clone())]);
+        let mut stack : Vec<(State, State)> = vec![(self.get_start().clone(), other.get_start().clone())];
+        while let Some((state, other_state)) = stack.pop() {
+            if self.get_ends().contains(&state) != other.get_ends().contains(&other_state) {
+                return None;
+            }
+            for symbol in self.get_alphabet() {
+                let image : Option<&State> = self.apply_delta(Transition::new(symbol.clone(), state.clone()));
+                let other_image : Option<&State> = other.apply_delta(Transition::new(symbol.clone(), other_state.clone()));
+                match (image, other_image) {
+                    (None, None) => {}
+                    (Some(image), Some(other_image)) => {
+                        match (bijection.get(image), inverse.get(other_image)) {
+                            (None, None) => {
+                                bijection.insert(image.clone(), other_image.clone());
+                                inverse.insert(other_image.clone(), image.clone());
+                                stack.push((image.clone(), other_image.clone()));
+                            }
+                            (Some(mapped), Some(_)) if mapped == other_image => {}
+                            _ => return None,
+                        }
+                    }
+                    _ => return None,
+                }
+            }
+        }
+        if bijection.len() != self.get_states().len() || inverse.len() != other.get_states().len() {
+            return None;
+        }
+        Some(bijection)
+    }
+
+    /// Convertit l'automate en automate non déterministe reconnaissant le même langage
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use automate::*;
+    /// fn main() {
+    ///     let dfa : DeterministicFiniteAutomaton = DeterministicFiniteAutomaton::from_json_file("src/automates/DFA1.json");
+    ///     let nfa : NonDeterministicFiniteAutomaton = dfa.to_nfa();
+    ///     assert!(nfa.accept("abab"));
+    /// }
+    /// ```
+    ///
+    /// # Return
+    ///
+    /// * `NonDeterministicFiniteAutomaton` - L'automate non déterministe correspondant
+    ///
+    pub fn to_nfa(&self) -> NonDeterministicFiniteAutomaton {
+        let delta : HashMap<Transition<State>, HashSet<State>> = self.get_delta().iter()
+            .map(|(transition, image)| (transition.clone(), HashSet::from([image.clone()])))
+            .collect();
+        NonDeterministicFiniteAutomaton::new(HashSet::from([self.get_start().clone()]), delta, self.fsm.clone())
+    }
 }
 
 
 #[cfg(test)]
 mod test {
     use super::*;
+    use serde_json::json;
+    use super::super::test_words::word_to_string;
 
     #[test]
     fn creation_partial_eq_copy() {      
@@ -338,4 +762,113 @@ mod test {
         assert_eq!(dfa.accept("aaab"), false);
         assert_eq!(dfa.accept("abab"), true);
     }
+
+    #[test]
+    fn shortest_longest_distance() {
+        //(ab)*: le mot vide est accepté et le langage est infini
+        let dfa1 : DeterministicFiniteAutomaton = DeterministicFiniteAutomaton::from_json_file("src/automates/DFA1.json");
+        assert_eq!(dfa1.shortest_accepted_word(), Some(vec![]));
+        assert!(!dfa1.is_finite());
+        assert_eq!(dfa1.longest_accepted_word(), None);
+
+        let dfa2 : DeterministicFiniteAutomaton = DeterministicFiniteAutomaton::from_json_file("src/automates/DFA2.json");
+        assert_eq!(word_to_string(&dfa2.shortest_accepted_word().unwrap()), "01");
+        let distances : HashMap<State, usize> = dfa2.distances_to_acceptance();
+        assert_eq!(distances.get(&State::from_str("A")), Some(&2));
+        assert_eq!(distances.get(&State::from_str("B")), Some(&1));
+        assert_eq!(distances.get(&State::from_str("D")), Some(&0));
+
+        //langage fini {b, ab, abc}
+        let dfa3 : DeterministicFiniteAutomaton = DeterministicFiniteAutomaton::from_json_file("src/automates/DFA3.json");
+        assert!(dfa3.is_finite());
+        assert_eq!(word_to_string(&dfa3.shortest_accepted_word().unwrap()), "b");
+        assert_eq!(word_to_string(&dfa3.longest_accepted_word().unwrap()), "abc");
+        let distances : HashMap<State, usize> = dfa3.distances_to_acceptance();
+        assert_eq!(distances.get(&State::from_str("q_1")), Some(&1));
+        assert_eq!(distances.get(&State::from_str("q_3")), Some(&0));
+        assert_eq!(distances.len(), 4);
+    }
+
+    #[test]
+    fn inclusion() {
+        let dfa1 : DeterministicFiniteAutomaton = DeterministicFiniteAutomaton::from_json_file("src/automates/DFA1.json");
+        let dfa2 : DeterministicFiniteAutomaton = DeterministicFiniteAutomaton::from_json_file("src/automates/DFA2.json");
+        let dfa3 : DeterministicFiniteAutomaton = DeterministicFiniteAutomaton::from_json_file("src/automates/DFA3.json");
+        //{ab}
+        let ab : DeterministicFiniteAutomaton = DeterministicFiniteAutomaton::from_json(json!({
+            "states" : ["0", "1", "2"],
+            "alphabet" : ["a", "b"],
+            "ends" : ["2"],
+            "start" : "0",
+            "delta" : [
+                {"state" : "0", "symbol" : "a", "image" : "1"},
+                {"state" : "1", "symbol" : "b", "image" : "2"}
+            ]
+        }).as_object().unwrap());
+
+        assert_eq!(dfa2.is_subset_of(&dfa2), Ok(()));
+        assert_eq!(ab.is_subset_of(&dfa1), Ok(()));
+        assert_eq!(ab.is_subset_of(&dfa3), Ok(()));
+        //le mot vide est dans dfa1 mais pas dans dfa3
+        assert_eq!(dfa1.is_subset_of(&dfa3), Err(vec![]));
+        assert_eq!(word_to_string(&dfa3.is_subset_of(&ab).unwrap_err()), "b");
+        //le contre exemple est bien accepté par l'un et refusé par l'autre
+        let counterexample : String = word_to_string(&dfa2.is_subset_of(&dfa1).unwrap_err());
+        assert!(dfa2.accept(&counterexample));
+        assert!(!dfa1.accept(&counterexample));
+    }
+
+    #[test]
+    fn canonicalize_isomorphic() {
+        let dfa1 : DeterministicFiniteAutomaton = DeterministicFiniteAutomaton::from_json_file("src/automates/DFA1.json");
+        let dfa2 : DeterministicFiniteAutomaton = DeterministicFiniteAutomaton::from_json_file("src/automates/DFA2.json");
+        //DFA2 avec d'autres noms d'etats
+        let renamed : DeterministicFiniteAutomaton = DeterministicFiniteAutomaton::from_json(json!({
+            "states" : ["q_0", "q_1", "q_2", "q_3"],
+            "alphabet" : ["0", "1"],
+            "ends" : ["q_3"],
+            "start" : "q_0",
+            "delta" : [
+                {"state" : "q_0", "symbol" : "0", "image" : "q_2"},
+                {"state" : "q_0", "symbol" : "1", "image" : "q_1"},
+                {"state" : "q_2", "symbol" : "0", "image" : "q_2"},
+                {"state" : "q_2", "symbol" : "1", "image" : "q_3"},
+                {"state" : "q_1", "symbol" : "1", "image" : "q_1"},
+                {"state" : "q_1", "symbol" : "0", "image" : "q_3"},
+                {"state" : "q_3", "symbol" : "0", "image" : "q_3"},
+                {"state" : "q_3", "symbol" : "1", "image" : "q_3"}
+            ]
+        }).as_object().unwrap());
+
+        let bijection : HashMap<State, State> = dfa2.is_isomorphic(&renamed).unwrap();
+        assert_eq!(bijection.len(), 4);
+        assert_eq!(bijection[&State::from_str("A")], State::from_str("q_0"));
+        assert_eq!(bijection[&State::from_str("B")], State::from_str("q_2"));
+        assert_eq!(bijection[&State::from_str("D")], State::from_str("q_3"));
+        assert_eq!(dfa1.is_isomorphic(&dfa2), None);
+
+        let canonical : DeterministicFiniteAutomaton = dfa2.canonicalize();
+        let canonical_renamed : DeterministicFiniteAutomaton = renamed.canonicalize();
+        assert_eq!(canonical.get_start(), canonical_renamed.get_start());
+        assert_eq!(canonical.get_delta(), canonical_renamed.get_delta());
+        assert_eq!(canonical.get_ends(), canonical_renamed.get_ends());
+        assert_eq!(canonical.get_ends(), &HashSet::from([State::from_str("3")]));
+        assert!(canonical.is_isomorphic(&dfa2).is_some());
+        //un état inaccessible en plus
+        let mut states : HashSet<State> = renamed.get_states().clone();
+        states.insert(State::from_str("q_4"));
+        let unreachable : DeterministicFiniteAutomaton = DeterministicFiniteAutomaton::new(renamed.get_start().clone(), renamed.get_delta().clone(),
+            FiniteStateMachine::new(states, renamed.get_alphabet().clone(), renamed.get_ends().clone()));
+        assert_eq!(dfa2.is_isomorphic(&unreachable), None);
+        assert_eq!(unreachable.is_isomorphic(&dfa2), None);
+
+        //completion de DFA1: un puits est ajouté
+        assert!(!dfa1.is_complete());
+        let complete : DeterministicFiniteAutomaton = dfa1.complete();
+        assert!(complete.is_complete());
+        assert_eq!(complete.get_states().len(), 3);
+        assert!(complete.accept("abab"));
+        assert!(!complete.accept("abb"));
+        assert!(dfa2.is_complete());
+    }
 }
\ No newline at end of file
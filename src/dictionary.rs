@@ -0,0 +1,208 @@
+use super::{Transition, State, Symbol, FiniteStateMachine, DeterministicFiniteAutomaton, to_symbols};
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+/// Signature d'un noeud déjà minimisé: deux noeuds de même signature reconnaissent les mêmes suffixes
+type Signature = (bool, Vec<(Symbol, usize)>);
+
+/// Noeud de l'automate en construction
+#[derive(Debug, Default)]
+struct Node {
+    end: bool,
+    edges: BTreeMap<Symbol, usize>,
+}
+
+/// Construction incrémentale de Daciuk: seul le chemin du dernier mot ajouté n'est pas encore minimisé
+struct Builder {
+    nodes: Vec<Node>,
+    register: HashMap<Signature, usize>,
+}
+
+impl Builder {
+    fn new() -> Self {
+        Builder { nodes: vec![Node::default()], register: HashMap::new() }
+    }
+
+    fn signature(&self, node : usize) -> Signature {
+        let node : &Node = &self.nodes[node];
+        (node.end, node.edges.iter().map(|(symbol, child)| (symbol.clone(), *child)).collect())
+    }
+
+    /// Minimise le dernier enfant de `node` et ses descendants, en les remplaçant par un noeud équivalent déjà enregistré
+    fn replace_or_register(&mut self, node : usize) {
+        let (symbol, child) : (Symbol, usize) = match self.nodes[node].edges.iter().next_back() {
+            Some((symbol, child)) => (symbol.clone(), *child),
+            None => return,
+        };
+        self.replace_or_register(child);
+        let signature : Signature = self.signature(child);
+        match self.register.get(&signature) {
+            Some(&equivalent) => {
+                self.nodes[node].edges.insert(symbol, equivalent);
+            },
+            None => {
+                self.register.insert(signature, child);
+            },
+        }
+    }
+
+    /// Ajoute un mot supérieur ou égal à tous les mots déjà ajoutés
+    fn add(&mut self, word : &[Symbol]) {
+        //le prefixe commun avec le mot précédent suit le chemin non minimisé
+        let mut node : usize = 0;
+        let mut length : usize = 0;
+        while let Some(&child) = word.get(length).and_then(|symbol| self.nodes[node].edges.get(symbol)) {
+            node = child;
+            length += 1;
+        }
+        self.replace_or_register(node);
+        for symbol in &word[length..] {
+            self.nodes.push(Node::default());
+            let child : usize = self.nodes.len() - 1;
+            self.nodes[node].edges.insert(symbol.clone(), child);
+            node = child;
+        }
+        self.nodes[node].end = true;
+    }
+
+    fn finish(mut self) -> DeterministicFiniteAutomaton {
+        self.replace_or_register(0);
+        //seuls les noeuds accessibles depuis la racine font partie de l'automate
+        let name = |node : usize| State::new(node.to_string());
+        let mut states : HashSet<State> = HashSet::from([name(0)]);
+        let mut alphabet : HashSet<Symbol> = HashSet::new();
+        let mut ends : HashSet<State> = HashSet::new();
+        let mut delta : HashMap<Transition<State>, State> = HashMap::new();
+        let mut stack : Vec<usize> = vec![0];
+        while let Some(node) = stack.pop() {
+            if self.nodes[node].end {
+                ends.insert(name(node));
+            }
+            for (symbol, child) in &self.nodes[node].edges {
+                alphabet.insert(symbol.clone());
+                delta.insert(Transition::new(symbol.clone(), name(node)), name(*child));
+                if states.insert(name(*child)) {
+                    stack.push(*child);
+                }
+            }
+        }
+        let fsm : FiniteStateMachine = FiniteStateMachine::new(states, alphabet, ends);
+        DeterministicFiniteAutomaton::new(name(0), delta, fsm).canonicalize()
+    }
+}
+
+impl DeterministicFiniteAutomaton {
+    /// Créer l'automate minimal d'un ensemble fini de mots triés, par la construction incrémentale de Daciuk
+    ///
+    /// L'automate est minimisé au fur et à mesure de l'ajout des mots, sans construire l'arbre des préfixes:
+    /// le temps de construction est linéaire en la taille totale des mots. L'automate retourné est acyclique,
+    /// partiel (sans état puits) et ses états sont nommés comme par `canonicalize`.
+    ///
+    /// # Arguments
+    ///
+    /// * `words` - Les mots, triés dans l'ordre lexicographique; les doublons sont ignorés
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use automate::*;
+    /// fn main() {
+    ///     let dfa : DeterministicFiniteAutomaton = DeterministicFiniteAutomaton::from_sorted_words(&["tap", "taps", "top", "tops"]).unwrap();
+    ///     assert_eq!(dfa.get_states().len(), 5);
+    ///     assert!(dfa.accept("tops"));
+    ///     assert!(!dfa.accept("to"));
+    ///     assert!(DeterministicFiniteAutomaton::from_sorted_words(&["top", "tap"]).is_err());
+    /// }
+    /// ```
+    ///
+    /// # Return
+    ///
+    /// * `Result<DeterministicFiniteAutomaton, String>` - L'automate minimal des mots, une erreur si les mots ne sont pas triés
+    ///
+    pub fn from_sorted_words(words : &[&str]) -> Result<DeterministicFiniteAutomaton, String> {
+        let mut builder : Builder = Builder::new();
+        let mut previous : Option<&str> = None;
+        for word in words {
+            match previous {
+                Some(previous) if previous > *word => return Err(format!("\"{}\" is not sorted after \"{}\"", word, previous)),
+                Some(previous) if previous == *word => continue,
+                _ => {},
+            }
+            let symbols : Vec<Symbol> = to_symbols(word);
+            builder.add(&symbols);
+            previous = Some(word);
+        }
+        Ok(builder.finish())
+    }
+
+    /// Créer l'automate minimal d'un ensemble fini de mots quelconques
+    ///
+    /// Les mots sont triés et dédoublonnés avant la construction s'ils ne le sont pas déjà, voir `from_sorted_words`.
+    ///
+    /// # Arguments
+    ///
+    /// * `words` - Les mots, dans un ordre quelconque
+    ///
+    /// # Return
+    ///
+    /// * `DeterministicFiniteAutomaton` - L'automate minimal des mots
+    ///
+    pub fn from_words(words : &[&str]) -> DeterministicFiniteAutomaton {
+        DeterministicFiniteAutomaton::from_sorted_words(words).unwrap_or_else(|_| {
+            let mut sorted : Vec<&str> = words.to_vec();
+            sorted.sort_unstable();
+            sorted.dedup();
+            DeterministicFiniteAutomaton::from_sorted_words(&sorted).unwrap()
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use super::super::test_words::enumerate_words;
+
+    #[test]
+    fn minimal_dictionary() {
+        let words : Vec<String> = enumerate_words("abc", 4).into_iter()
+            .enumerate()
+            .filter(|(index, _)| index % 3 != 1 && index % 7 != 2)
+            .map(|(_, word)| word)
+            .collect();
+        let words : Vec<&str> = words.iter().map(|word| word.as_str()).collect();
+        let dictionary : DeterministicFiniteAutomaton = DeterministicFiniteAutomaton::from_words(&words);
+        assert!(dictionary.is_finite());
+        for word in enumerate_words("abc", 5) {
+            assert_eq!(dictionary.accept(&word), words.contains(&word.as_str()), "{}", word);
+        }
+        //l'automate minimal complet n'a en plus que l'état puits
+        assert_eq!(dictionary.minimize().get_states().len(), dictionary.get_states().len() + 1);
+        assert!(dictionary.complete().minimize().is_isomorphic(&dictionary.minimize()).is_some());
+
+        //l'ordre et les doublons ne changent pas le résultat
+        let mut shuffled : Vec<&str> = words.iter().rev().cloned().collect();
+        shuffled.extend(words.iter().step_by(5));
+        assert!(DeterministicFiniteAutomaton::from_sorted_words(&shuffled).is_err());
+        let unsorted : DeterministicFiniteAutomaton = DeterministicFiniteAutomaton::from_words(&shuffled);
+        assert_eq!(unsorted.get_delta(), dictionary.get_delta());
+        assert_eq!(unsorted.get_ends(), dictionary.get_ends());
+    }
+
+    #[test]
+    fn empty_dictionaries() {
+        let empty : DeterministicFiniteAutomaton = DeterministicFiniteAutomaton::from_sorted_words(&[]).unwrap();
+        assert_eq!(empty.get_states().len(), 1);
+        assert!(!empty.accept(""));
+        let epsilon : DeterministicFiniteAutomaton = DeterministicFiniteAutomaton::from_sorted_words(&["", "", "a"]).unwrap();
+        assert!(epsilon.accept("") && epsilon.accept("a") && !epsilon.accept("aa"));
+    }
+
+    #[test]
+    fn json_round_trip() {
+        let dictionary : DeterministicFiniteAutomaton = DeterministicFiniteAutomaton::from_words(&["mode", "modes", "code", "codes", "cod"]);
+        let copy : DeterministicFiniteAutomaton = DeterministicFiniteAutomaton::from_json(dictionary.to_json().as_object().unwrap());
+        assert_eq!(copy.get_start(), dictionary.get_start());
+        assert_eq!(copy.get_delta(), dictionary.get_delta());
+        assert_eq!(copy.get_ends(), dictionary.get_ends());
+        assert_eq!(copy.get_states(), dictionary.get_states());
+    }
+}
@@ -0,0 +1,185 @@
+use super::State;
+use std::collections::BTreeSet;
+use serde_json::{Value, Map};
+
+/// Formule booléenne positive sur des états, image d'une transition d'automate alternant
+///
+/// Une conjonction impose que tous ses états acceptent la suite du mot, une disjonction qu'au moins un l'accepte.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum BooleanFormula {
+    True,
+    False,
+    Atom(State),
+    And(Vec<BooleanFormula>),
+    Or(Vec<BooleanFormula>),
+}
+
+impl BooleanFormula {
+    /// Créer une formule depuis une valeur json
+    ///
+    /// Une formule est `true`, `false`, le nom d'un état, `{"and": [...]}` ou `{"or": [...]}`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use automate::*;
+    /// use serde_json::json;
+    /// fn main() {
+    ///     let formula : BooleanFormula = BooleanFormula::from_json(&json!({"and": ["p", {"or": ["q", true]}]})).unwrap();
+    ///     assert_eq!(formula.to_text(), "p & (q | true)");
+    ///     assert!(BooleanFormula::from_json(&json!({"not": "p"})).is_err());
+    /// }
+    /// ```
+    ///
+    pub fn from_json(content_json : &Value) -> Result<Self, String> {
+        match content_json {
+            Value::Bool(true) => Ok(BooleanFormula::True),
+            Value::Bool(false) => Ok(BooleanFormula::False),
+            Value::String(name) => Ok(BooleanFormula::Atom(State::new(name.clone()))),
+            Value::Object(object) if object.len() == 1 => {
+                let (operator, operands) : (&String, &Value) = object.iter().next().unwrap();
+                let operands : Vec<BooleanFormula> = operands.as_array()
+                    .ok_or_else(|| format!("operands of '{}' must be an array", operator))?
+                    .iter().map(BooleanFormula::from_json).collect::<Result<_, _>>()?;
+                match operator.as_str() {
+                    "and" => Ok(BooleanFormula::And(operands)),
+                    "or" => Ok(BooleanFormula::Or(operands)),
+                    _ => Err(format!("unknown operator '{}', expected 'and' or 'or'", operator)),
+                }
+            },
+            _ => Err(format!("invalid formula {}", content_json)),
+        }
+    }
+
+    /// Retourne la formule sous forme json, voir `from_json`
+    pub fn to_json(&self) -> Value {
+        match self {
+            BooleanFormula::True => Value::Bool(true),
+            BooleanFormula::False => Value::Bool(false),
+            BooleanFormula::Atom(state) => Value::String(state.get_name().clone()),
+            BooleanFormula::And(operands) | BooleanFormula::Or(operands) => {
+                let operator : &str = if matches!(self, BooleanFormula::And(_)) { "and" } else { "or" };
+                let mut object : Map<String, Value> = Map::new();
+                object.insert(String::from(operator), Value::Array(operands.iter().map(BooleanFormula::to_json).collect()));
+                Value::Object(object)
+            },
+        }
+    }
+
+    /// Retourne la formule sous forme de texte, avec `&`, `|` et des parenthèses autour des sous-formules composées
+    pub fn to_text(&self) -> String {
+        let operand = |formula : &BooleanFormula| -> String {
+            match formula {
+                BooleanFormula::And(operands) | BooleanFormula::Or(operands) if operands.len() > 1 => format!("({})", formula.to_text()),
+                _ => formula.to_text(),
+            }
+        };
+        match self {
+            BooleanFormula::True => String::from("true"),
+            BooleanFormula::False => String::from("false"),
+            BooleanFormula::Atom(state) => state.get_name().clone(),
+            BooleanFormula::And(operands) if operands.is_empty() => String::from("true"),
+            BooleanFormula::Or(operands) if operands.is_empty() => String::from("false"),
+            BooleanFormula::And(operands) => operands.iter().map(operand).collect::<Vec<String>>().join(" & "),
+            BooleanFormula::Or(operands) => operands.iter().map(operand).collect::<Vec<String>>().join(" | "),
+        }
+    }
+
+    /// Retourne les états apparaissant dans la formule
+    pub fn get_states(&self) -> BTreeSet<State> {
+        match self {
+            BooleanFormula::True | BooleanFormula::False => BTreeSet::new(),
+            BooleanFormula::Atom(state) => BTreeSet::from([state.clone()]),
+            BooleanFormula::And(operands) | BooleanFormula::Or(operands) => operands.iter().flat_map(BooleanFormula::get_states).collect(),
+        }
+    }
+
+    /// Evalue la formule, la valeur de chaque état étant donnée par `valuation`
+    ///
+    /// Les opérandes sont évalués de gauche à droite et l'évaluation s'arrête dès que le résultat est connu.
+    pub fn evaluate<F>(&self, valuation : &mut F) -> bool
+    where
+        F: FnMut(&State) -> bool,
+    {
+        match self {
+            BooleanFormula::True => true,
+            BooleanFormula::False => false,
+            BooleanFormula::Atom(state) => valuation(state),
+            BooleanFormula::And(operands) => operands.iter().all(|operand| operand.evaluate(valuation)),
+            BooleanFormula::Or(operands) => operands.iter().any(|operand| operand.evaluate(valuation)),
+        }
+    }
+
+    /// Retourne les modèles minimaux de la formule: les ensembles minimaux d'états qui, tous vrais, la rendent vraie
+    ///
+    /// Comme la formule est positive, elle est vraie pour un ensemble d'états si et seulement s'il contient un
+    /// de ses modèles minimaux. La formule `false` n'a aucun modèle, `true` a pour seul modèle l'ensemble vide.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use automate::*;
+    /// use std::collections::BTreeSet;
+    /// use serde_json::json;
+    /// fn main() {
+    ///     let formula : BooleanFormula = BooleanFormula::from_json(&json!({"and": [{"or": ["p", "q"]}, {"or": ["p", "r"]}]})).unwrap();
+    ///     let models : Vec<BTreeSet<State>> = formula.minimal_models();
+    ///     //{p} rend inutiles {p,r} et {p,q}
+    ///     assert_eq!(models, vec![
+    ///         BTreeSet::from([State::from_str("p")]),
+    ///         BTreeSet::from([State::from_str("q"), State::from_str("r")]),
+    ///     ]);
+    /// }
+    /// ```
+    ///
+    pub fn minimal_models(&self) -> Vec<BTreeSet<State>> {
+        let models : Vec<BTreeSet<State>> = match self {
+            BooleanFormula::True => vec![BTreeSet::new()],
+            BooleanFormula::False => Vec::new(),
+            BooleanFormula::Atom(state) => vec![BTreeSet::from([state.clone()])],
+            BooleanFormula::Or(operands) => operands.iter().flat_map(BooleanFormula::minimal_models).collect(),
+            BooleanFormula::And(operands) => operands.iter().fold(vec![BTreeSet::new()], |models, operand| {
+                let operand_models : Vec<BTreeSet<State>> = operand.minimal_models();
+                models.iter()
+                    .flat_map(|model| operand_models.iter().map(move |other| model.union(other).cloned().collect()))
+                    .collect()
+            }),
+        };
+        minimal_sets(models)
+    }
+}
+
+/// Retire les doublons et les ensembles contenant un autre ensemble de la liste, puis trie le résultat
+pub(crate) fn minimal_sets(mut sets : Vec<BTreeSet<State>>) -> Vec<BTreeSet<State>> {
+    sets.sort_by_key(|set| set.len());
+    let mut minimal : Vec<BTreeSet<State>> = Vec::new();
+    for set in sets {
+        if !minimal.iter().any(|smaller| smaller.is_subset(&set)) {
+            minimal.push(set);
+        }
+    }
+    minimal.sort();
+    minimal
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn json_round_trip_and_evaluation() {
+        let content : Value = json!({"or": [{"and": ["p", "q"]}, false, "r"]});
+        let formula : BooleanFormula = BooleanFormula::from_json(&content).unwrap();
+        assert_eq!(formula.to_json(), content);
+        assert_eq!(formula.to_text(), "(p & q) | false | r");
+        assert_eq!(formula.get_states().len(), 3);
+        let mut calls : usize = 0;
+        assert!(!formula.evaluate(&mut |state : &State| { calls += 1; state.get_name() == "p" }));
+        assert_eq!(calls, 3);
+        assert!(formula.evaluate(&mut |state : &State| state.get_name() == "r"));
+        assert_eq!(formula.minimal_models().len(), 2);
+        assert!(BooleanFormula::False.minimal_models().is_empty());
+        assert_eq!(BooleanFormula::And(Vec::new()).minimal_models(), vec![BTreeSet::new()]);
+    }
+}
@@ -134,9 +134,9 @@ impl FiniteStateMachine {
         }
 
         FiniteStateMachine {
-            alphabet : alphabet,
-            states : states,
-            ends: ends,
+            alphabet,
+            states,
+            ends,
         }
     }
 
@@ -0,0 +1,675 @@
+use super::{State, Symbol, NonDeterministicFiniteAutomaton, fresh_state, to_symbols};
+use std::collections::{BTreeSet, HashMap, HashSet, VecDeque};
+use std::fs;
+use serde_json::{Value, from_str, map::Map};
+
+/// Transition d'un transducteur: (état, symbole lu ou `None` pour epsilon, symboles produits, image)
+pub type FstArc = (State, Option<Symbol>, Vec<Symbol>, State);
+
+/// Configuration de l'application d'un transducteur: un état et une position dans le mot lu
+type Configuration = (State, usize);
+
+/// Transitions sans epsilon en entrée, et ensemble des sorties finales de chaque état final
+type EpsilonFree = (Vec<FstArc>, HashMap<State, BTreeSet<Vec<Symbol>>>);
+
+/// Transducteur à état fini: chaque transition lit au plus un symbole et produit une suite de symboles
+///
+/// Chaque état final produit en plus une sortie finale, le plus souvent vide.
+#[derive(Debug, Clone)]
+pub struct FST {
+    start: State,
+    arcs: Vec<FstArc>,
+    ends: HashMap<State, Vec<Symbol>>,
+    states: HashSet<State>,
+    inputs: HashSet<Symbol>,
+    outputs: HashSet<Symbol>,
+}
+
+/// Nomme un état de la déterminisation d'après les couples (état, sortie en attente) qu'il représente
+fn residual_state(subset : &BTreeSet<(State, Vec<Symbol>)>) -> State {
+    let names : Vec<String> = subset.iter()
+        .map(|(state, residual)| format!("{}:{}", state.get_name(), residual.iter().map(|symbol| symbol.get_value().as_str()).collect::<String>()))
+        .collect();
+    State::new(format!("{{{}}}", names.join(",")))
+}
+
+impl FST {
+    /// Créer un transducteur à état fini
+    ///
+    /// Les états et les alphabets d'entrée et de sortie sont déduits des transitions.
+    ///
+    /// # Arguments
+    ///
+    /// * `_start` - L'état initial du transducteur
+    /// * `_arcs` - Les transitions du transducteur
+    /// * `_ends` - Les états finaux, chacun associé à sa sortie finale
+    ///
+    /// # Return
+    ///
+    /// * `FST` - Le transducteur correspondant
+    ///
+    pub fn new(_start : State, _arcs : Vec<FstArc>, _ends : HashMap<State, Vec<Symbol>>) -> Self {
+        let mut states : HashSet<State> = HashSet::from([_start.clone()]);
+        let mut inputs : HashSet<Symbol> = HashSet::new();
+        let mut outputs : HashSet<Symbol> = HashSet::new();
+        for (state, input, output, image) in &_arcs {
+            states.insert(state.clone());
+            states.insert(image.clone());
+            inputs.extend(input.iter().cloned());
+            outputs.extend(output.iter().cloned());
+        }
+        for (end, output) in &_ends {
+            states.insert(end.clone());
+            outputs.extend(output.iter().cloned());
+        }
+        FST {
+            start: _start,
+            arcs: _arcs,
+            ends: _ends,
+            states,
+            inputs,
+            outputs,
+        }
+    }
+
+    /// Créer un transducteur depuis un contenu json
+    ///
+    /// # Examples
+    ///
+    /// Le contenu du json: un symbole lu `null` est une epsilon-transition, les sorties sont des listes de symboles
+    /// et `end_outputs`, facultatif, donne les sorties finales non vides
+    ///
+    /// ```json
+    /// {
+    ///     "start" : "q_0",
+    ///     "ends" : ["q_1"],
+    ///     "end_outputs" : [
+    ///         {
+    ///             "state" : "q_1",
+    ///             "output" : ["!"]
+    ///         }
+    ///     ],
+    ///     "delta" : [
+    ///         {
+    ///             "state" : "q_0",
+    ///             "input" : "a",
+    ///             "output" : ["x", "y"],
+    ///             "image" : "q_1"
+    ///         },
+    ///         {
+    ///             "state" : "q_1",
+    ///             "input" : null,
+    ///             "output" : [],
+    ///             "image" : "q_0"
+    ///         }
+    ///     ]
+    /// }
+    /// ```
+    ///
+    /// # Return
+    ///
+    /// * `FST` - Le transducteur correspondant
+    ///
+    pub fn from_json(content_json: &Map<String, Value>) -> Self {
+        let name = |value : &Value| String::from(value.as_str().unwrap());
+        let word = |value : &Value| -> Vec<Symbol> { value.as_array().unwrap().iter().map(|symbol| Symbol::new(name(symbol))).collect() };
+        let start : State = State::new(name(content_json.get("start").unwrap()));
+        let mut arcs : Vec<FstArc> = Vec::new();
+        for element_delta in content_json.get("delta").unwrap().as_array().unwrap() {
+            let arc_json : &Map<String, Value> = element_delta.as_object().unwrap();
+            let input : Option<Symbol> = arc_json.get("input").unwrap().as_str().map(|symbol| Symbol::new(String::from(symbol)));
+            arcs.push((
+                State::new(name(arc_json.get("state").unwrap())),
+                input,
+                word(arc_json.get("output").unwrap()),
+                State::new(name(arc_json.get("image").unwrap())),
+            ));
+        }
+        let mut ends : HashMap<State, Vec<Symbol>> = HashMap::new();
+        for end in content_json.get("ends").unwrap().as_array().unwrap() {
+            ends.insert(State::new(name(end)), Vec::new());
+        }
+        for element_output in content_json.get("end_outputs").and_then(|outputs| outputs.as_array()).into_iter().flatten() {
+            let output_json : &Map<String, Value> = element_output.as_object().unwrap();
+            ends.insert(State::new(name(output_json.get("state").unwrap())), word(output_json.get("output").unwrap()));
+        }
+        FST::new(start, arcs, ends)
+    }
+
+    /// Créer un transducteur depuis un chemin vers un fichier json, voir `from_json`
+    pub fn from_json_file(path: &str) -> Self {
+        let content_json: Value = {
+            // Charge le contenu du fichier en tant que String
+            let content : String = fs::read_to_string(path).unwrap();
+            // Parse le texte en structure Json
+            from_str::<Value>(&content).unwrap()
+        };
+        FST::from_json(content_json.as_object().unwrap())
+    }
+
+    /// Retourne l'état initial du transducteur
+    pub fn get_start(&self) -> &State {
+        &self.start
+    }
+
+    /// Retourne les transitions du transducteur
+    pub fn get_arcs(&self) -> &Vec<FstArc> {
+        &self.arcs
+    }
+
+    /// Retourne les états finaux et leurs sorties finales
+    pub fn get_ends(&self) -> &HashMap<State, Vec<Symbol>> {
+        &self.ends
+    }
+
+    /// Retourne les états du transducteur
+    pub fn get_states(&self) -> &HashSet<State> {
+        &self.states
+    }
+
+    /// Retourne l'alphabet d'entrée
+    pub fn get_inputs(&self) -> &HashSet<Symbol> {
+        &self.inputs
+    }
+
+    /// Retourne l'alphabet de sortie
+    pub fn get_outputs(&self) -> &HashSet<Symbol> {
+        &self.outputs
+    }
+
+    /// Regroupe les transitions par état de départ
+    fn arcs_from(&self) -> HashMap<&State, Vec<&FstArc>> {
+        let mut arcs_from : HashMap<&State, Vec<&FstArc>> = HashMap::new();
+        for arc in &self.arcs {
+            arcs_from.entry(&arc.0).or_default().push(arc);
+        }
+        arcs_from
+    }
+
+    /// Retourne toutes les traductions d'une suite de symboles, voir `apply`
+    pub fn apply_symbols(&self, input : &[Symbol]) -> Result<BTreeSet<Vec<Symbol>>, String> {
+        let arcs_from : HashMap<&State, Vec<&FstArc>> = self.arcs_from();
+        //graphe des configurations: chaque arc indique s'il consomme un symbole
+        let successors = |(state, position) : &Configuration| -> Vec<(&FstArc, Configuration)> {
+            arcs_from.get(state).into_iter().flatten()
+                .filter_map(|arc| match &arc.1 {
+                    None => Some((*arc, (arc.3.clone(), *position))),
+                    Some(symbol) if input.get(*position) == Some(symbol) => Some((*arc, (arc.3.clone(), position + 1))),
+                    Some(_) => None,
+                })
+                .collect()
+        };
+        let start : Configuration = (self.get_start().clone(), 0);
+        let mut reached : HashSet<Configuration> = HashSet::from([start.clone()]);
+        let mut predecessors : HashMap<Configuration, Vec<Configuration>> = HashMap::new();
+        let mut stack : Vec<Configuration> = vec![start.clone()];
+        while let Some(configuration) = stack.pop() {
+            for (_, next) in successors(&configuration) {
+                predecessors.entry(next.clone()).or_default().push(configuration.clone());
+                if reached.insert(next.clone()) {
+                    stack.push(next);
+                }
+            }
+        }
+        let mut useful : HashSet<Configuration> = reached.iter()
+            .filter(|(state, position)| *position == input.len() && self.ends.contains_key(state))
+            .cloned()
+            .collect();
+        let mut stack : Vec<Configuration> = useful.iter().cloned().collect();
+        while let Some(configuration) = stack.pop() {
+            for previous in predecessors.get(&configuration).into_iter().flatten() {
+                if useful.insert(previous.clone()) {
+                    stack.push(previous.clone());
+                }
+            }
+        }
+        //une epsilon-transition productive sur un cycle utile donne une infinité de traductions
+        for configuration in &useful {
+            for (arc, next) in successors(configuration) {
+                if arc.1.is_none() && !arc.2.is_empty() && useful.contains(&next) && self.epsilon_reaches(&arcs_from, &next, configuration, &useful) {
+                    return Err(format!("infinitely many outputs: epsilon cycle through {} producing output", configuration.0.get_name()));
+                }
+            }
+        }
+        //les cycles restants ne produisent rien, les couples (configuration, sortie) visités sont donc en nombre fini
+        let mut translations : BTreeSet<Vec<Symbol>> = BTreeSet::new();
+        let mut visited : HashSet<(Configuration, Vec<Symbol>)> = HashSet::new();
+        let mut stack : Vec<(Configuration, Vec<Symbol>)> = if useful.contains(&start) { vec![(start, Vec::new())] } else { Vec::new() };
+        while let Some((configuration, output)) = stack.pop() {
+            if !visited.insert((configuration.clone(), output.clone())) {
+                continue;
+            }
+            if configuration.1 == input.len() {
+                if let Some(end_output) = self.ends.get(&configuration.0) {
+                    let mut translation : Vec<Symbol> = output.clone();
+                    translation.extend(end_output.iter().cloned());
+                    translations.insert(translation);
+                }
+            }
+            for (arc, next) in successors(&configuration) {
+                if useful.contains(&next) {
+                    let mut next_output : Vec<Symbol> = output.clone();
+                    next_output.extend(arc.2.iter().cloned());
+                    stack.push((next, next_output));
+                }
+            }
+        }
+        Ok(translations)
+    }
+
+    /// Indique si `to` est accessible depuis `from` par des epsilon-transitions entre configurations utiles
+    fn epsilon_reaches(&self, arcs_from : &HashMap<&State, Vec<&FstArc>>, from : &Configuration, to : &Configuration, useful : &HashSet<Configuration>) -> bool {
+        let mut reached : HashSet<State> = HashSet::from([from.0.clone()]);
+        let mut stack : Vec<State> = vec![from.0.clone()];
+        while let Some(state) = stack.pop() {
+            if state == to.0 {
+                return true;
+            }
+            for arc in arcs_from.get(&state).into_iter().flatten() {
+                if arc.1.is_none() && useful.contains(&(arc.3.clone(), from.1)) && reached.insert(arc.3.clone()) {
+                    stack.push(arc.3.clone());
+                }
+            }
+        }
+        false
+    }
+
+    /// Retourne toutes les traductions d'un mot, lu caractère par caractère
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use automate::*;
+    /// fn main() {
+    ///     //chaque a devient xy, chaque b est effacé et un z peut être ajouté à la fin
+    ///     let fst : FST = FST::from_json_file("src/automates/FST1.json");
+    ///     let outputs : Vec<Vec<Symbol>> = fst.apply("ab").unwrap().into_iter().collect();
+    ///     assert_eq!(outputs, vec![["x", "y"].map(Symbol::from_str).to_vec(), ["x", "y", "z"].map(Symbol::from_str).to_vec()]);
+    ///     assert!(fst.apply("c").unwrap().is_empty());
+    /// }
+    /// ```
+    ///
+    /// # Return
+    ///
+    /// * `Result<BTreeSet<Vec<Symbol>>, String>` - Les traductions, une erreur si elles sont en nombre infini
+    ///
+    pub fn apply(&self, input : &str) -> Result<BTreeSet<Vec<Symbol>>, String> {
+        self.apply_symbols(&to_symbols(input))
+    }
+
+    /// Retourne un transducteur équivalent dont chaque transition produit au plus un symbole et dont les sorties finales sont vides
+    ///
+    /// Les sorties de plusieurs symboles sont découpées en chaînes d'epsilon-transitions, et les sorties finales
+    /// non vides deviennent des epsilon-transitions vers un nouvel état final.
+    fn normalize(&self) -> FST {
+        let mut states : HashSet<State> = self.states.clone();
+        let mut arcs : Vec<FstArc> = Vec::new();
+        let chain = |states : &mut HashSet<State>, arcs : &mut Vec<FstArc>, state : &State, input : &Option<Symbol>, output : &[Symbol], image : &State| {
+            if output.len() <= 1 {
+                arcs.push((state.clone(), input.clone(), output.to_vec(), image.clone()));
+                return;
+            }
+            let mut current : State = state.clone();
+            for (index, symbol) in output.iter().enumerate() {
+                let input : Option<Symbol> = if index == 0 { input.clone() } else { None };
+                let next : State = if index + 1 == output.len() {
+                    image.clone()
+                } else {
+                    let next : State = fresh_state(states, &format!("{}#{}", state.get_name(), index + 1));
+                    states.insert(next.clone());
+                    next
+                };
+                arcs.push((current, input, vec![symbol.clone()], next.clone()));
+                current = next;
+            }
+        };
+        for (state, input, output, image) in &self.arcs {
+            chain(&mut states, &mut arcs, state, input, output, image);
+        }
+        let mut ends : HashMap<State, Vec<Symbol>> = HashMap::new();
+        let end : State = fresh_state(&states, "end");
+        for (state, output) in &self.ends {
+            if output.is_empty() {
+                ends.insert(state.clone(), Vec::new());
+            } else {
+                states.insert(end.clone());
+                ends.insert(end.clone(), Vec::new());
+                chain(&mut states, &mut arcs, state, &None, output, &end);
+            }
+        }
+        FST::new(self.start.clone(), arcs, ends)
+    }
+
+    /// Compose self avec `other`: les sorties de self sont lues par `other`
+    ///
+    /// Les deux transducteurs sont d'abord normalisés pour que chaque transition produise au plus un symbole,
+    /// puis le produit est construit depuis le couple initial, chaque état étant nommé `(p,q)`.
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - Le transducteur appliqué aux sorties de self
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use automate::*;
+    /// use serde_json::json;
+    /// fn main() {
+    ///     let fst : FST = FST::from_json_file("src/automates/FST1.json");
+    ///     //x devient 1, y est effacé et z devient 2
+    ///     let digits : FST = FST::from_json(json!({
+    ///         "start" : "q",
+    ///         "ends" : ["q"],
+    ///         "delta" : [
+    ///             { "state" : "q", "input" : "x", "output" : ["1"], "image" : "q" },
+    ///             { "state" : "q", "input" : "y", "output" : [], "image" : "q" },
+    ///             { "state" : "q", "input" : "z", "output" : ["2"], "image" : "q" }
+    ///         ]
+    ///     }).as_object().unwrap());
+    ///     let outputs : Vec<Vec<Symbol>> = fst.compose(&digits).apply("aba").unwrap().into_iter().collect();
+    ///     assert_eq!(outputs, vec![["1", "1"].map(Symbol::from_str).to_vec(), ["1", "1", "2"].map(Symbol::from_str).to_vec()]);
+    /// }
+    /// ```
+    ///
+    /// # Return
+    ///
+    /// * `FST` - Le transducteur de la relation composée: `u` est traduit en `w` s'il existe `v` tel que self traduit `u` en `v` et `other` traduit `v` en `w`
+    ///
+    pub fn compose(&self, other : &FST) -> FST {
+        let left : FST = self.normalize();
+        let right : FST = other.normalize();
+        let left_arcs : HashMap<&State, Vec<&FstArc>> = left.arcs_from();
+        let right_arcs : HashMap<&State, Vec<&FstArc>> = right.arcs_from();
+        let name = |state : &State, other_state : &State| State::new(format!("({},{})", state.get_name(), other_state.get_name()));
+        let mut arcs : Vec<FstArc> = Vec::new();
+        let mut ends : HashMap<State, Vec<Symbol>> = HashMap::new();
+        let mut reached : HashSet<(State, State)> = HashSet::from([(left.start.clone(), right.start.clone())]);
+        let mut queue : VecDeque<(State, State)> = VecDeque::from([(left.start.clone(), right.start.clone())]);
+        while let Some((state, other_state)) = queue.pop_front() {
+            let pair : State = name(&state, &other_state);
+            if left.ends.contains_key(&state) && right.ends.contains_key(&other_state) {
+                ends.insert(pair.clone(), Vec::new());
+            }
+            let mut moves : Vec<(Option<Symbol>, Vec<Symbol>, State, State)> = Vec::new();
+            for (_, input, output, image) in left_arcs.get(&state).into_iter().flatten() {
+                match output.first() {
+                    //self ne produit rien: other ne bouge pas
+                    None => moves.push((input.clone(), Vec::new(), image.clone(), other_state.clone())),
+                    //other lit le symbole produit par self
+                    Some(produced) => {
+                        for (_, other_input, other_output, other_image) in right_arcs.get(&other_state).into_iter().flatten() {
+                            if other_input.as_ref() == Some(produced) {
+                                moves.push((input.clone(), other_output.clone(), image.clone(), other_image.clone()));
+                            }
+                        }
+                    },
+                }
+            }
+            //other avance seul sur ses epsilon-transitions
+            for (_, other_input, other_output, other_image) in right_arcs.get(&other_state).into_iter().flatten() {
+                if other_input.is_none() {
+                    moves.push((None, other_output.clone(), state.clone(), other_image.clone()));
+                }
+            }
+            for (input, output, image, other_image) in moves {
+                arcs.push((pair.clone(), input, output, name(&image, &other_image)));
+                if reached.insert((image.clone(), other_image.clone())) {
+                    queue.push_back((image, other_image));
+                }
+            }
+        }
+        FST::new(name(&left.start, &right.start), arcs, ends)
+    }
+
+    /// Retourne le transducteur inverse, qui traduit `v` en `u` quand self traduit `u` en `v`
+    pub fn invert(&self) -> FST {
+        let normalized : FST = self.normalize();
+        let arcs : Vec<FstArc> = normalized.arcs.iter()
+            .map(|(state, input, output, image)| (state.clone(), output.first().cloned(), input.iter().cloned().collect(), image.clone()))
+            .collect();
+        FST::new(normalized.start.clone(), arcs, normalized.ends.clone())
+    }
+
+    /// Retourne l'automate des mots lus par le transducteur (le domaine de la relation)
+    pub fn input_projection(&self) -> NonDeterministicFiniteAutomaton {
+        let transitions : Vec<(State, Option<Symbol>, State)> = self.arcs.iter()
+            .map(|(state, input, _, image)| (state.clone(), input.clone(), image.clone()))
+            .collect();
+        let ends : HashSet<State> = self.ends.keys().cloned().collect();
+        NonDeterministicFiniteAutomaton::from_epsilon_transitions(HashSet::from([self.start.clone()]), &transitions, &ends, self.inputs.clone())
+    }
+
+    /// Retourne l'automate des mots produits par le transducteur (l'image de la relation)
+    pub fn output_projection(&self) -> NonDeterministicFiniteAutomaton {
+        self.invert().input_projection()
+    }
+
+    /// Retourne un transducteur équivalent sans epsilon-transition en entrée
+    ///
+    /// Les sorties des chemins d'epsilon-transitions sont reportées sur la transition suivante ou sur la sortie finale.
+    /// Un état peut alors avoir plusieurs sorties finales.
+    fn remove_input_epsilons(&self) -> Result<EpsilonFree, String> {
+        let arcs_from : HashMap<&State, Vec<&FstArc>> = self.arcs_from();
+        let mut arcs : Vec<FstArc> = Vec::new();
+        let mut ends : HashMap<State, BTreeSet<Vec<Symbol>>> = HashMap::new();
+        let limit : usize = self.states.len();
+        for state in &self.states {
+            //chemins d'epsilon-transitions depuis state, avec leur sortie
+            let mut visited : HashSet<(State, Vec<Symbol>)> = HashSet::new();
+            let mut stack : Vec<(State, Vec<Symbol>, usize)> = vec![(state.clone(), Vec::new(), 0)];
+            while let Some((current, output, productive)) = stack.pop() {
+                if !visited.insert((current.clone(), output.clone())) {
+                    continue;
+                }
+                //plus de transitions productives que d'états: un cycle produit une sortie
+                if productive > limit {
+                    return Err(format!("epsilon cycle producing output through {}", current.get_name()));
+                }
+                if let Some(end_output) = self.ends.get(&current) {
+                    let mut translation : Vec<Symbol> = output.clone();
+                    translation.extend(end_output.iter().cloned());
+                    ends.entry(state.clone()).or_default().insert(translation);
+                }
+                for (_, input, arc_output, image) in arcs_from.get(&current).into_iter().flatten() {
+                    let mut next_output : Vec<Symbol> = output.clone();
+                    next_output.extend(arc_output.iter().cloned());
+                    match input {
+                        None => stack.push((image.clone(), next_output, productive + usize::from(!arc_output.is_empty()))),
+                        Some(_) => arcs.push((state.clone(), input.clone(), next_output, image.clone())),
+                    }
+                }
+            }
+        }
+        Ok((arcs, ends))
+    }
+
+    /// Déterminise le transducteur en un transducteur séquentiel, par l'algorithme de Mohri
+    ///
+    /// Chaque état du résultat représente un ensemble de couples (état, sortie en attente): la transition produit
+    /// le plus long préfixe commun des sorties possibles et garde le reste en attente. Ce n'est possible que si
+    /// le transducteur est fonctionnel et si les sorties en attente restent bornées (propriété des jumeaux).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use automate::*;
+    /// use serde_json::json;
+    /// fn main() {
+    ///     //ab -> xy et ac -> xz, le choix entre y et z est retardé jusqu'au second symbole
+    ///     let fst : FST = FST::from_json(json!({
+    ///         "start" : "q_0",
+    ///         "ends" : ["f"],
+    ///         "delta" : [
+    ///             { "state" : "q_0", "input" : "a", "output" : ["x"], "image" : "q_1" },
+    ///             { "state" : "q_1", "input" : "b", "output" : ["y"], "image" : "f" },
+    ///             { "state" : "q_0", "input" : "a", "output" : ["x", "z"], "image" : "q_2" },
+    ///             { "state" : "q_2", "input" : "c", "output" : [], "image" : "f" }
+    ///         ]
+    ///     }).as_object().unwrap());
+    ///     let sequential : FST = fst.determinize().unwrap();
+    ///     assert_eq!(sequential.get_states().len(), 3);
+    ///     assert_eq!(sequential.apply("ac").unwrap(), fst.apply("ac").unwrap());
+    /// }
+    /// ```
+    ///
+    /// # Return
+    ///
+    /// * `Result<FST, String>` - Le transducteur séquentiel, une erreur si le transducteur n'est pas déterminisable
+    ///
+    pub fn determinize(&self) -> Result<FST, String> {
+        let (arcs, ends) : EpsilonFree = self.remove_input_epsilons()?;
+        let mut arcs_from : HashMap<&State, Vec<&FstArc>> = HashMap::new();
+        for arc in &arcs {
+            arcs_from.entry(&arc.0).or_default().push(arc);
+        }
+        //borne sur les sorties en attente d'un transducteur vérifiant la propriété des jumeaux
+        let longest : usize = arcs.iter().map(|arc| arc.2.len()).chain(ends.values().flatten().map(|output| output.len())).max().unwrap_or(0);
+        let bound : usize = (self.states.len() * self.states.len() + 1) * longest;
+        let mut inputs : Vec<&Symbol> = self.inputs.iter().collect();
+        inputs.sort();
+
+        let start : BTreeSet<(State, Vec<Symbol>)> = BTreeSet::from([(self.start.clone(), Vec::new())]);
+        let mut result_arcs : Vec<FstArc> = Vec::new();
+        let mut result_ends : HashMap<State, Vec<Symbol>> = HashMap::new();
+        let mut reached : HashSet<BTreeSet<(State, Vec<Symbol>)>> = HashSet::from([start.clone()]);
+        let mut stack : Vec<BTreeSet<(State, Vec<Symbol>)>> = vec![start.clone()];
+        while let Some(subset) = stack.pop() {
+            let name : State = residual_state(&subset);
+            //sortie finale: toutes les sorties possibles doivent coincider
+            let finals : BTreeSet<Vec<Symbol>> = subset.iter()
+                .flat_map(|(state, residual)| ends.get(state).into_iter().flatten().map(move |end_output| {
+                    let mut translation : Vec<Symbol> = residual.clone();
+                    translation.extend(end_output.iter().cloned());
+                    translation
+                }))
+                .collect();
+            if finals.len() > 1 {
+                return Err(format!("not functional: several outputs end in {}", name.get_name()));
+            }
+            if let Some(end_output) = finals.into_iter().next() {
+                result_ends.insert(name.clone(), end_output);
+            }
+            for symbol in &inputs {
+                let mut candidates : BTreeSet<(State, Vec<Symbol>)> = BTreeSet::new();
+                for (state, residual) in &subset {
+                    for (_, input, output, image) in arcs_from.get(state).into_iter().flatten() {
+                        if input.as_ref() == Some(*symbol) {
+                            let mut pending : Vec<Symbol> = residual.clone();
+                            pending.extend(output.iter().cloned());
+                            candidates.insert((image.clone(), pending));
+                        }
+                    }
+                }
+                if candidates.is_empty() {
+                    continue;
+                }
+                //la transition produit le plus long préfixe commun des sorties en attente
+                let first : &Vec<Symbol> = &candidates.iter().next().unwrap().1;
+                let common : usize = (0..first.len())
+                    .take_while(|index| candidates.iter().all(|(_, pending)| pending.get(*index) == Some(&first[*index])))
+                    .count();
+                let produced : Vec<Symbol> = first[..common].to_vec();
+                let next : BTreeSet<(State, Vec<Symbol>)> = candidates.into_iter().map(|(image, pending)| (image, pending[common..].to_vec())).collect();
+                if next.iter().any(|(_, residual)| residual.len() > bound) {
+                    return Err(String::from("not sequentializable: pending outputs grow without bound"));
+                }
+                result_arcs.push((name.clone(), Some((*symbol).clone()), produced, residual_state(&next)));
+                if reached.insert(next.clone()) {
+                    stack.push(next);
+                }
+            }
+        }
+        Ok(FST::new(residual_state(&start), result_arcs, result_ends))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use super::super::test_words::{enumerate_words, word_to_string};
+    use serde_json::json;
+
+    fn strings(outputs : &BTreeSet<Vec<Symbol>>) -> Vec<String> {
+        outputs.iter().map(|output| word_to_string(output)).collect()
+    }
+
+    /// Transducteur d'un seul état final bouclant sur les couples (lettre lue, sortie) donnés
+    fn substitution(pairs : &[(&str, &str)]) -> FST {
+        let state : State = State::from_str("q");
+        let arcs : Vec<FstArc> = pairs.iter().map(|(input, output)| (state.clone(), to_symbols(input).first().cloned(), to_symbols(output), state.clone())).collect();
+        FST::new(state.clone(), arcs, HashMap::from([(state, Vec::new())]))
+    }
+
+    #[test]
+    fn apply_and_projections() {
+        let fst : FST = FST::from_json_file("src/automates/FST1.json");
+        assert_eq!(strings(&fst.apply("aba").unwrap()), vec!["xyxy", "xyxyz"]);
+        assert_eq!(strings(&fst.apply("").unwrap()), vec!["", "z"]);
+        let domain : NonDeterministicFiniteAutomaton = fst.input_projection();
+        let image : NonDeterministicFiniteAutomaton = fst.output_projection();
+        for word in enumerate_words("abxyz", 4) {
+            assert_eq!(domain.accept(&word), word.chars().all(|letter| "ab".contains(letter)), "{}", word);
+            let expected : bool = word.trim_end_matches('z').len() + 1 >= word.len() && word.trim_end_matches('z').as_bytes().chunks(2).all(|pair| pair == b"xy");
+            assert_eq!(image.accept(&word), expected, "{}", word);
+        }
+        //une epsilon-boucle productive donne une infinité de sorties
+        let looping : FST = FST::new(State::from_str("q"), vec![(State::from_str("q"), None, to_symbols("x"), State::from_str("q"))], HashMap::from([(State::from_str("q"), Vec::new())]));
+        assert!(looping.apply("").is_err());
+        assert!(looping.determinize().is_err());
+    }
+
+    #[test]
+    fn composition_and_inversion() {
+        let double : FST = substitution(&[("a", "bb"), ("c", "")]);
+        let rename : FST = substitution(&[("b", "c"), ("b", "d")]);
+        let composed : FST = double.compose(&rename);
+        for word in enumerate_words("ac", 4) {
+            let mut expected : BTreeSet<Vec<Symbol>> = BTreeSet::new();
+            for middle in double.apply(&word).unwrap() {
+                expected.extend(rename.apply_symbols(&middle).unwrap());
+            }
+            assert_eq!(composed.apply(&word).unwrap(), expected, "{}", word);
+        }
+        //c est effacé: l'inverse peut en insérer autant qu'il veut
+        assert!(double.invert().apply("bb").is_err());
+        let bijection : FST = substitution(&[("a", "bb"), ("c", "d")]);
+        assert_eq!(strings(&bijection.invert().apply("bbdbb").unwrap()), vec!["aca"]);
+        assert!(bijection.invert().apply("bdb").unwrap().is_empty());
+    }
+
+    #[test]
+    fn determinization() {
+        let fst : FST = FST::from_json(json!({
+            "start" : "q_0",
+            "ends" : ["q_4", "q_2"],
+            "end_outputs" : [{ "state" : "q_2", "output" : ["!"] }],
+            "delta" : [
+                { "state" : "q_0", "input" : "a", "output" : ["x"], "image" : "q_1" },
+                { "state" : "q_0", "input" : "a", "output" : ["x", "y"], "image" : "q_2" },
+                { "state" : "q_1", "input" : "b", "output" : ["y", "y"], "image" : "q_4" },
+                { "state" : "q_4", "input" : "b", "output" : ["y", "y"], "image" : "q_4" },
+                { "state" : "q_2", "input" : "c", "output" : [], "image" : "q_3" },
+                { "state" : "q_3", "input" : null, "output" : ["z"], "image" : "q_2" }
+            ]
+        }).as_object().unwrap());
+        let sequential : FST = fst.determinize().unwrap();
+        for word in enumerate_words("abc", 5) {
+            assert_eq!(sequential.apply(&word).unwrap(), fst.apply(&word).unwrap(), "{}", word);
+        }
+        //a+b -> a+b et a+c -> c+c: la sortie dépend de la dernière lettre, les sorties en attente ne sont pas bornées
+        let unbounded : FST = FST::from_json(json!({
+            "start" : "q_0",
+            "ends" : ["f"],
+            "delta" : [
+                { "state" : "q_0", "input" : "a", "output" : ["a"], "image" : "q_1" },
+                { "state" : "q_1", "input" : "a", "output" : ["a"], "image" : "q_1" },
+                { "state" : "q_1", "input" : "b", "output" : ["b"], "image" : "f" },
+                { "state" : "q_0", "input" : "a", "output" : ["c"], "image" : "q_2" },
+                { "state" : "q_2", "input" : "a", "output" : ["c"], "image" : "q_2" },
+                { "state" : "q_2", "input" : "c", "output" : ["c"], "image" : "f" }
+            ]
+        }).as_object().unwrap());
+        assert!(unbounded.determinize().is_err());
+        assert!(FST::from_json_file("src/automates/FST1.json").determinize().is_err());
+    }
+}
@@ -0,0 +1,646 @@
+use super::{State, Symbol, PushdownAutomaton, PdaArc, AcceptanceMode, fresh_state, to_symbols};
+use std::collections::{BTreeSet, HashMap, HashSet};
+
+/// Symbole d'une grammaire: un terminal, lu dans les mots, ou un non-terminal, représenté par un état
+#[derive(Debug, Clone, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub enum GrammarSymbol {
+    Terminal(Symbol),
+    Nonterminal(State),
+}
+
+/// Règle d'une grammaire: un non-terminal et le mot qui peut le remplacer, vide pour ε
+pub type Production = (State, Vec<GrammarSymbol>);
+
+/// Grammaire algébrique (hors contexte)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Grammar {
+    start: State,
+    productions: Vec<Production>,
+}
+
+impl Grammar {
+    /// Créer une grammaire
+    ///
+    /// Les règles en double sont ignorées.
+    ///
+    /// # Arguments
+    ///
+    /// * `_start` - L'axiome de la grammaire
+    /// * `_productions` - Les règles de la grammaire
+    ///
+    /// # Return
+    ///
+    /// * `Grammar` - La grammaire correspondante
+    ///
+    pub fn new(_start : State, _productions : Vec<Production>) -> Self {
+        let mut seen : HashSet<Production> = HashSet::new();
+        let productions : Vec<Production> = _productions.into_iter().filter(|production| seen.insert(production.clone())).collect();
+        Grammar {
+            start: _start,
+            productions,
+        }
+    }
+
+    /// Créer une grammaire depuis sa description textuelle
+    ///
+    /// Chaque ligne `A -> α | β | ...` donne les règles du non-terminal `A`, l'axiome étant celui de la première
+    /// ligne. Les non-terminaux sont les noms situés à gauche d'une flèche. Un terminal entre guillemets, où `\"`
+    /// et `\\` désignent un guillemet et une barre oblique, est lu tel quel et peut contenir plusieurs caractères.
+    /// Dans une alternative, un mot séparé par des espaces qui n'est pas un non-terminal est lu lettre par lettre,
+    /// chaque lettre étant un non-terminal si elle en nomme un et un terminal sinon: `a S b`, `aSb` et `"a" S "b"`
+    /// sont équivalents. Une alternative vide ou `ε` désigne le mot vide. Les lignes vides et celles commençant
+    /// par `#` sont ignorées.
+    ///
+    /// # Arguments
+    ///
+    /// * `text` - La description de la grammaire
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use automate::*;
+    /// fn main() {
+    ///     let grammar : Grammar = Grammar::from_text("S -> a S b | ε").unwrap();
+    ///     assert_eq!(grammar.get_productions().len(), 2);
+    ///     assert!(grammar.accept("aabb"));
+    ///     assert!(!grammar.accept("aab"));
+    ///     assert!(Grammar::from_text("S -> a\nb").is_err());
+    /// }
+    /// ```
+    ///
+    /// # Return
+    ///
+    /// * `Result<Grammar, String>` - La grammaire, une erreur indiquant la ligne fautive
+    ///
+    pub fn from_text(text : &str) -> Result<Self, String> {
+        let mut rules : Vec<(usize, State, &str)> = Vec::new();
+        for (index, line) in text.lines().enumerate() {
+            let line : &str = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let (left, right) : (&str, &str) = line.split_once("->").ok_or_else(|| format!("line {}: expected '->'", index + 1))?;
+            let left : &str = left.trim();
+            if left.is_empty() || left.contains(char::is_whitespace) {
+                return Err(format!("line {}: expected a single nonterminal before '->'", index + 1));
+            }
+            rules.push((index + 1, State::from_str(left), right));
+        }
+        let start : State = rules.first().ok_or_else(|| String::from("empty grammar"))?.1.clone();
+        let nonterminals : HashSet<&str> = rules.iter().map(|(_, left, _)| left.get_name().as_str()).collect();
+        let mut productions : Vec<Production> = Vec::new();
+        for (line, left, right) in &rules {
+            let mut bodies : Vec<Vec<GrammarSymbol>> = vec![Vec::new()];
+            let mut letters = right.chars().peekable();
+            while let Some(letter) = letters.next() {
+                if letter == '|' {
+                    bodies.push(Vec::new());
+                } else if letter == '"' {
+                    let mut value : String = String::new();
+                    loop {
+                        match letters.next() {
+                            None => return Err(format!("line {}: unterminated terminal", line)),
+                            Some('"') => break,
+                            Some('\\') => value.push(letters.next().ok_or_else(|| format!("line {}: unterminated terminal", line))?),
+                            Some(other) => value.push(other),
+                        }
+                    }
+                    bodies.last_mut().unwrap().push(GrammarSymbol::Terminal(Symbol::new(value)));
+                } else if !letter.is_whitespace() {
+                    let mut token : String = String::from(letter);
+                    while let Some(next) = letters.next_if(|next| !next.is_whitespace() && *next != '|' && *next != '"') {
+                        token.push(next);
+                    }
+                    let body : &mut Vec<GrammarSymbol> = bodies.last_mut().unwrap();
+                    if token == "ε" {
+                        continue;
+                    }
+                    if nonterminals.contains(token.as_str()) {
+                        body.push(GrammarSymbol::Nonterminal(State::new(token)));
+                        continue;
+                    }
+                    for letter in token.chars() {
+                        let name : String = String::from(letter);
+                        body.push(if nonterminals.contains(name.as_str()) {
+                            GrammarSymbol::Nonterminal(State::new(name))
+                        } else {
+                            GrammarSymbol::Terminal(Symbol::new(name))
+                        });
+                    }
+                }
+            }
+            productions.extend(bodies.into_iter().map(|body| (left.clone(), body)));
+        }
+        Ok(Grammar::new(start, productions))
+    }
+
+    /// Retourne la description textuelle de la grammaire, au format lu par `from_text`
+    ///
+    /// Les règles d'un même non-terminal sont regroupées sur une ligne, dans l'ordre de leur première apparition,
+    /// et les terminaux sont écrits entre guillemets: `from_text` relit les mêmes règles, regroupées.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use automate::*;
+    /// fn main() {
+    ///     let grammar : Grammar = Grammar::from_text("S -> a S b | ε").unwrap();
+    ///     assert_eq!(grammar.to_text().unwrap(), "S -> \"a\" S \"b\" | ε\n");
+    /// }
+    /// ```
+    ///
+    /// # Return
+    ///
+    /// * `Result<String, String>` - La description, une erreur si un non-terminal n'a aucune règle ou si son nom
+    ///   ne peut pas être écrit sans guillemets (vide, `ε`, avec un espace, `|`, `"`, `->` ou commençant par `#`)
+    ///
+    pub fn to_text(&self) -> Result<String, String> {
+        let quote = |value : &str| format!("\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\""));
+        let mut order : Vec<&State> = vec![&self.start];
+        let mut alternatives : HashMap<&State, Vec<String>> = HashMap::new();
+        for (left, body) in &self.productions {
+            if !order.contains(&left) {
+                order.push(left);
+            }
+            let names : Vec<String> = body.iter().map(|symbol| match symbol {
+                GrammarSymbol::Terminal(terminal) => quote(terminal.get_value()),
+                GrammarSymbol::Nonterminal(nonterminal) => nonterminal.get_name().clone(),
+            }).collect();
+            alternatives.entry(left).or_default().push(if names.is_empty() { String::from("ε") } else { names.join(" ") });
+        }
+        let mut nonterminals : Vec<State> = self.get_nonterminals().into_iter().collect();
+        nonterminals.sort();
+        for nonterminal in &nonterminals {
+            let name : &str = nonterminal.get_name();
+            if name.is_empty() || name == "ε" || name.starts_with('#') || name.contains(|letter : char| letter.is_whitespace() || letter == '|' || letter == '"') || name.contains("->") {
+                return Err(format!("nonterminal \"{}\" cannot be written in the text format", name));
+            }
+            if !alternatives.contains_key(nonterminal) {
+                return Err(format!("nonterminal \"{}\" has no rule", name));
+            }
+        }
+        Ok(order.iter()
+            .map(|left| format!("{} -> {}\n", left.get_name(), alternatives[left].join(" | ")))
+            .collect())
+    }
+
+    /// Retourne l'axiome de la grammaire
+    pub fn get_start(&self) -> &State {
+        &self.start
+    }
+
+    /// Retourne les règles de la grammaire
+    pub fn get_productions(&self) -> &Vec<Production> {
+        &self.productions
+    }
+
+    /// Retourne les non-terminaux de la grammaire, axiome compris
+    pub fn get_nonterminals(&self) -> HashSet<State> {
+        let mut nonterminals : HashSet<State> = HashSet::from([self.start.clone()]);
+        for (left, body) in &self.productions {
+            nonterminals.insert(left.clone());
+            for symbol in body {
+                if let GrammarSymbol::Nonterminal(nonterminal) = symbol {
+                    nonterminals.insert(nonterminal.clone());
+                }
+            }
+        }
+        nonterminals
+    }
+
+    /// Retourne les terminaux de la grammaire
+    pub fn get_terminals(&self) -> HashSet<Symbol> {
+        self.productions.iter()
+            .flat_map(|(_, body)| body.iter())
+            .filter_map(|symbol| match symbol {
+                GrammarSymbol::Terminal(terminal) => Some(terminal.clone()),
+                GrammarSymbol::Nonterminal(_) => None,
+            })
+            .collect()
+    }
+
+    /// Convertit la grammaire en un automate à pile à un seul état, qui accepte par pile vide
+    ///
+    /// La pile contient le reste de la dérivation la plus à gauche: un non-terminal au sommet est remplacé par
+    /// l'une de ses règles, un terminal au sommet est dépilé en le lisant. Les symboles de pile sont les noms
+    /// des terminaux et des non-terminaux; un non-terminal qui porte le nom d'un terminal est renommé par `fresh_state`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use automate::*;
+    /// fn main() {
+    ///     let pda : PushdownAutomaton = Grammar::from_text("S -> ( S ) S | ε").unwrap().to_pda();
+    ///     assert!(pda.accept("(()())", AcceptanceMode::EmptyStack));
+    ///     assert!(!pda.accept("())(", AcceptanceMode::EmptyStack));
+    /// }
+    /// ```
+    ///
+    /// # Return
+    ///
+    /// * `PushdownAutomaton` - L'automate à pile reconnaissant par pile vide le langage de la grammaire
+    ///
+    pub fn to_pda(&self) -> PushdownAutomaton {
+        let state : State = State::from_str("q");
+        let mut terminals : Vec<Symbol> = self.get_terminals().into_iter().collect();
+        terminals.sort();
+        let mut nonterminals : Vec<State> = self.get_nonterminals().into_iter().collect();
+        nonterminals.sort();
+        //symbole de pile de chaque non-terminal, distinct de ceux des terminaux
+        let terminal_names : HashSet<State> = terminals.iter().map(|terminal| State::new(terminal.get_value().clone())).collect();
+        let mut taken : HashSet<State> = terminal_names.clone();
+        taken.extend(nonterminals.iter().cloned());
+        let mut renaming : HashMap<State, Symbol> = HashMap::new();
+        for nonterminal in nonterminals {
+            let name : State = if terminal_names.contains(&nonterminal) {
+                let name : State = fresh_state(&taken, nonterminal.get_name());
+                taken.insert(name.clone());
+                name
+            } else {
+                nonterminal.clone()
+            };
+            renaming.insert(nonterminal, Symbol::new(name.get_name().clone()));
+        }
+        let stack_symbol = |symbol : &GrammarSymbol| match symbol {
+            GrammarSymbol::Terminal(terminal) => terminal.clone(),
+            GrammarSymbol::Nonterminal(nonterminal) => renaming[nonterminal].clone(),
+        };
+        let mut arcs : Vec<PdaArc> = self.productions.iter()
+            .map(|(left, body)| (state.clone(), None, renaming[left].clone(), body.iter().map(stack_symbol).collect(), state.clone()))
+            .collect();
+        for terminal in terminals {
+            arcs.push((state.clone(), Some(terminal.clone()), terminal, Vec::new(), state.clone()));
+        }
+        PushdownAutomaton::new(state, renaming[&self.start].clone(), arcs, HashSet::new())
+    }
+
+    /// Convertit un automate à pile en grammaire, par la construction des triplets
+    ///
+    /// Le non-terminal `[p,X,q]` engendre les mots qui mènent de l'état `p` à l'état `q` en dépilant `X`. Un
+    /// automate acceptant par état final est d'abord converti par `to_empty_stack`. Les non-terminaux inutiles
+    /// sont supprimés.
+    ///
+    /// # Arguments
+    ///
+    /// * `pda` - L'automate à pile
+    /// * `mode` - Le mode d'acceptation de l'automate
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use automate::*;
+    /// fn main() {
+    ///     let pda : PushdownAutomaton = PushdownAutomaton::from_json_file("src/automates/PDA1.json");
+    ///     let grammar : Grammar = Grammar::from_pda(&pda, AcceptanceMode::EmptyStack);
+    ///     assert!(grammar.accept("([]())"));
+    ///     assert!(!grammar.accept("(]"));
+    /// }
+    /// ```
+    ///
+    /// # Return
+    ///
+    /// * `Grammar` - La grammaire engendrant le langage de l'automate
+    ///
+    pub fn from_pda(pda : &PushdownAutomaton, mode : AcceptanceMode) -> Grammar {
+        let pda : PushdownAutomaton = match mode {
+            AcceptanceMode::FinalState => pda.to_empty_stack(),
+            AcceptanceMode::EmptyStack => pda.clone(),
+        };
+        let mut states : Vec<&State> = pda.get_states().iter().collect();
+        states.sort();
+        let triple = |state : &State, symbol : &Symbol, image : &State| GrammarSymbol::Nonterminal(State::new(format!("[{},{},{}]", state.get_name(), symbol.get_value(), image.get_name())));
+        let name = |symbol : GrammarSymbol| match symbol {
+            GrammarSymbol::Nonterminal(nonterminal) => nonterminal,
+            GrammarSymbol::Terminal(_) => unreachable!(),
+        };
+        let mut productions : Vec<Production> = Vec::new();
+        for (state, input, pop, push, image) in pda.get_arcs() {
+            let prefix : Vec<GrammarSymbol> = input.iter().map(|symbol| GrammarSymbol::Terminal(symbol.clone())).collect();
+            //toutes les suites d'états intermédiaires r_1, ..., r_k pour dépiler les k symboles empilés
+            let mut paths : Vec<(Vec<GrammarSymbol>, &State)> = vec![(prefix, image)];
+            for symbol in push {
+                paths = paths.iter()
+                    .flat_map(|(body, from)| states.iter().map(move |to| {
+                        let mut body : Vec<GrammarSymbol> = body.clone();
+                        body.push(triple(from, symbol, to));
+                        (body, *to)
+                    }))
+                    .collect();
+            }
+            for (body, last) in paths {
+                productions.push((name(triple(state, pop, last)), body));
+            }
+        }
+        //le nouvel axiome ne doit porter le nom ni d'un non-terminal ni d'un terminal
+        let mut names : HashSet<State> = productions.iter().map(|(left, _)| left.clone()).collect();
+        names.extend(pda.get_alphabet().iter().map(|symbol| State::new(symbol.get_value().clone())));
+        let start : State = fresh_state(&names, "S");
+        for state in &states {
+            productions.insert(0, (start.clone(), vec![triple(pda.get_start(), pda.get_stack_start(), state)]));
+        }
+        Grammar::new(start, productions).trim()
+    }
+
+    /// Retourne la grammaire privée des non-terminaux qui n'engendrent aucun mot ou ne sont pas accessibles depuis l'axiome
+    pub fn trim(&self) -> Grammar {
+        //non-terminaux productifs, par point fixe
+        let mut productive : HashSet<&State> = HashSet::new();
+        let mut changed : bool = true;
+        while changed {
+            changed = false;
+            for (left, body) in &self.productions {
+                if !productive.contains(left) && body.iter().all(|symbol| match symbol {
+                    GrammarSymbol::Terminal(_) => true,
+                    GrammarSymbol::Nonterminal(nonterminal) => productive.contains(nonterminal),
+                }) {
+                    productive.insert(left);
+                    changed = true;
+                }
+            }
+        }
+        let useful : Vec<&Production> = self.productions.iter()
+            .filter(|(left, body)| productive.contains(left) && body.iter().all(|symbol| match symbol {
+                GrammarSymbol::Terminal(_) => true,
+                GrammarSymbol::Nonterminal(nonterminal) => productive.contains(nonterminal),
+            }))
+            .collect();
+        //non-terminaux accessibles depuis l'axiome
+        let mut reachable : HashSet<&State> = HashSet::from([&self.start]);
+        let mut stack : Vec<&State> = vec![&self.start];
+        while let Some(nonterminal) = stack.pop() {
+            for (_, body) in useful.iter().filter(|(left, _)| left == nonterminal) {
+                for symbol in body {
+                    if let GrammarSymbol::Nonterminal(next) = symbol {
+                        if reachable.insert(next) {
+                            stack.push(next);
+                        }
+                    }
+                }
+            }
+        }
+        let productions : Vec<Production> = useful.into_iter().filter(|(left, _)| reachable.contains(left)).cloned().collect();
+        Grammar::new(self.start.clone(), productions)
+    }
+
+    /// Retourne une grammaire équivalente sous forme normale de Chomsky
+    ///
+    /// Toutes les règles sont de la forme `A -> B C` ou `A -> a`, avec en plus `S0 -> ε` si le mot vide est
+    /// engendré, `S0` étant un nouvel axiome qui n'apparait dans aucune règle. Les étapes sont celles du cours:
+    /// nouvel axiome, remplacement des terminaux des règles longues par `T_a`, découpage des règles longues,
+    /// suppression des règles vides puis des règles unitaires, et enfin suppression des non-terminaux inutiles.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use automate::*;
+    /// fn main() {
+    ///     let cnf : Grammar = Grammar::from_text("S -> a S b | ε").unwrap().to_cnf();
+    ///     assert!(cnf.is_cnf());
+    ///     assert!(cnf.cyk("ab"));
+    ///     assert!(cnf.cyk(""));
+    /// }
+    /// ```
+    ///
+    /// # Return
+    ///
+    /// * `Grammar` - La grammaire sous forme normale de Chomsky
+    ///
+    pub fn to_cnf(&self) -> Grammar {
+        let mut nonterminals : HashSet<State> = self.get_nonterminals();
+        let fresh = |nonterminals : &mut HashSet<State>, base : &str| -> State {
+            let state : State = fresh_state(nonterminals, base);
+            nonterminals.insert(state.clone());
+            state
+        };
+        //nouvel axiome
+        let start : State = fresh(&mut nonterminals, &format!("{}0", self.start.get_name()));
+        let mut productions : Vec<Production> = vec![(start.clone(), vec![GrammarSymbol::Nonterminal(self.start.clone())])];
+        productions.extend(self.productions.iter().cloned());
+
+        //terminaux des règles longues
+        let mut terminal_names : HashMap<Symbol, State> = HashMap::new();
+        let mut terminal_rules : Vec<Production> = Vec::new();
+        for (_, body) in productions.iter_mut().filter(|(_, body)| body.len() >= 2) {
+            for symbol in body.iter_mut() {
+                if let GrammarSymbol::Terminal(terminal) = symbol {
+                    let name : State = terminal_names.entry(terminal.clone()).or_insert_with(|| {
+                        let name : State = fresh(&mut nonterminals, &format!("T_{}", terminal.get_value()));
+                        terminal_rules.push((name.clone(), vec![GrammarSymbol::Terminal(terminal.clone())]));
+                        name
+                    }).clone();
+                    *symbol = GrammarSymbol::Nonterminal(name);
+                }
+            }
+        }
+        productions.extend(terminal_rules);
+
+        //découpage des règles de plus de deux symboles
+        let mut binary : Vec<Production> = Vec::new();
+        for (left, body) in productions {
+            let mut left : State = left;
+            let mut rest : &[GrammarSymbol] = &body;
+            let mut index : usize = 0;
+            while rest.len() > 2 {
+                index += 1;
+                let next : State = fresh(&mut nonterminals, &format!("{}_{}", left.get_name(), index));
+                binary.push((left, vec![rest[0].clone(), GrammarSymbol::Nonterminal(next.clone())]));
+                left = next;
+                rest = &rest[1..];
+            }
+            binary.push((left, rest.to_vec()));
+        }
+
+        //suppression des règles vides: chaque règle est déclinée en omettant ses symboles effaçables
+        let mut nullable : HashSet<State> = HashSet::new();
+        let mut changed : bool = true;
+        while changed {
+            changed = false;
+            for (left, body) in &binary {
+                if !nullable.contains(left) && body.iter().all(|symbol| matches!(symbol, GrammarSymbol::Nonterminal(nonterminal) if nullable.contains(nonterminal))) {
+                    nullable.insert(left.clone());
+                    changed = true;
+                }
+            }
+        }
+        let mut without_empty : Vec<Production> = Vec::new();
+        for (left, body) in &binary {
+            let mut variants : Vec<Vec<GrammarSymbol>> = vec![Vec::new()];
+            for symbol in body {
+                let erasable : bool = matches!(symbol, GrammarSymbol::Nonterminal(nonterminal) if nullable.contains(nonterminal));
+                variants = variants.into_iter()
+                    .flat_map(|variant| {
+                        let mut kept : Vec<GrammarSymbol> = variant.clone();
+                        kept.push(symbol.clone());
+                        if erasable { vec![kept, variant] } else { vec![kept] }
+                    })
+                    .collect();
+            }
+            for variant in variants {
+                if !variant.is_empty() {
+                    without_empty.push((left.clone(), variant));
+                }
+            }
+        }
+        if nullable.contains(&start) {
+            without_empty.push((start.clone(), Vec::new()));
+        }
+
+        //suppression des règles unitaires A -> B: A reçoit les autres règles de tout B accessible par règles unitaires
+        let unit = |body : &Vec<GrammarSymbol>| match body.as_slice() {
+            [GrammarSymbol::Nonterminal(nonterminal)] => Some(nonterminal.clone()),
+            _ => None,
+        };
+        let mut lefts : Vec<State> = Vec::new();
+        for (left, _) in &without_empty {
+            if !lefts.contains(left) {
+                lefts.push(left.clone());
+            }
+        }
+        let mut cnf : Vec<Production> = Vec::new();
+        for left in lefts {
+            let mut closure : Vec<State> = vec![left.clone()];
+            let mut index : usize = 0;
+            while index < closure.len() {
+                let current : State = closure[index].clone();
+                for (_, body) in without_empty.iter().filter(|(other, _)| *other == current) {
+                    if let Some(next) = unit(body) {
+                        if !closure.contains(&next) {
+                            closure.push(next);
+                        }
+                    }
+                }
+                index += 1;
+            }
+            for reached in &closure {
+                for (_, body) in without_empty.iter().filter(|(other, body)| other == reached && unit(body).is_none()) {
+                    //seul le nouvel axiome garde sa règle vide
+                    if !body.is_empty() || left == start {
+                        cnf.push((left.clone(), body.clone()));
+                    }
+                }
+            }
+        }
+        Grammar::new(start, cnf).trim()
+    }
+
+    /// Indique si la grammaire est sous forme normale de Chomsky, voir `to_cnf`
+    pub fn is_cnf(&self) -> bool {
+        let appears : bool = self.productions.iter().any(|(_, body)| body.contains(&GrammarSymbol::Nonterminal(self.start.clone())));
+        self.productions.iter().all(|(left, body)| match body.as_slice() {
+            [] => *left == self.start && !appears,
+            [GrammarSymbol::Terminal(_)] => true,
+            [GrammarSymbol::Nonterminal(_), GrammarSymbol::Nonterminal(_)] => true,
+            _ => false,
+        })
+    }
+
+    /// Indique si la grammaire, supposée sous forme normale de Chomsky, engendre `word`, par l'algorithme CYK
+    ///
+    /// Les règles qui ne sont pas sous forme normale sont ignorées. La table `table[i][l]` contient les
+    /// non-terminaux qui engendrent le facteur de longueur `l + 1` commençant à la position `i`.
+    pub fn cyk(&self, word : &str) -> bool {
+        let letters : Vec<Symbol> = to_symbols(word);
+        if letters.is_empty() {
+            return self.productions.iter().any(|(left, body)| *left == self.start && body.is_empty());
+        }
+        let length : usize = letters.len();
+        let mut table : Vec<Vec<BTreeSet<&State>>> = vec![vec![BTreeSet::new(); length]; length];
+        for (position, letter) in letters.iter().enumerate() {
+            for (left, body) in &self.productions {
+                if body.as_slice() == [GrammarSymbol::Terminal(letter.clone())] {
+                    table[position][0].insert(left);
+                }
+            }
+        }
+        for span in 1..length {
+            for position in 0..length - span {
+                for split in 0..span {
+                    for (left, body) in &self.productions {
+                        if let [GrammarSymbol::Nonterminal(first), GrammarSymbol::Nonterminal(second)] = body.as_slice() {
+                            if table[position][split].contains(first) && table[position + split + 1][span - split - 1].contains(second) {
+                                table[position][span].insert(left);
+                            }
+                        }
+                    }
+                }
+            }
+        }
+        table[0][length - 1].contains(&self.start)
+    }
+
+    /// Indique si la grammaire engendre `word`, par l'algorithme CYK sur sa forme normale de Chomsky
+    pub fn accept(&self, word : &str) -> bool {
+        self.to_cnf().cyk(word)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use super::super::test_words::{enumerate_words, balanced};
+
+    #[test]
+    fn text_format() {
+        let grammar : Grammar = Grammar::from_text("# expressions\nE -> E + T | T\nT -> ( E ) | x\n\nT -> xT").unwrap();
+        assert_eq!(grammar.get_start(), &State::from_str("E"));
+        assert_eq!(grammar.get_productions().len(), 5);
+        assert_eq!(grammar.get_terminals().len(), 4);
+        assert_eq!(grammar.to_text().unwrap(), "E -> E \"+\" T | T\nT -> \"(\" E \")\" | \"x\" | \"x\" T\n");
+        assert_eq!(Grammar::from_text(&grammar.to_text().unwrap()).unwrap(), grammar);
+        let names : Grammar = Grammar::new(State::from_str("a"), vec![
+            (State::from_str("a"), vec![GrammarSymbol::Terminal(Symbol::from_str("a")), GrammarSymbol::Nonterminal(State::from_str("b"))]),
+            (State::from_str("b"), vec![GrammarSymbol::Terminal(Symbol::from_str("ab")), GrammarSymbol::Terminal(Symbol::from_str("\"\\"))]),
+            (State::from_str("b"), vec![]),
+        ]);
+        assert_eq!(Grammar::from_text(&names.to_text().unwrap()).unwrap(), names);
+        assert!(Grammar::new(State::from_str("S"), vec![(State::from_str("S"), vec![GrammarSymbol::Nonterminal(State::from_str("A"))])]).to_text().is_err());
+        assert!(Grammar::new(State::from_str("A B"), vec![(State::from_str("A B"), vec![])]).to_text().is_err());
+        assert_eq!(Grammar::from_text("S -> \"ab").unwrap_err(), "line 1: unterminated terminal");
+        assert_eq!(Grammar::from_text("S -> a\nS b").unwrap_err(), "line 2: expected '->'");
+        assert!(Grammar::from_text("A B -> a").is_err());
+        assert!(Grammar::from_text("# rien").is_err());
+    }
+
+    #[test]
+    fn cyk_and_pda_agree() {
+        for text in ["S -> ( S ) S | [ S ] S | ε", "S -> a S b | ε", "S -> a S a | b S b | a | b | ε", "S -> A B\nA -> a A | ε\nB -> b B | b"] {
+            let grammar : Grammar = Grammar::from_text(text).unwrap();
+            let cnf : Grammar = grammar.to_cnf();
+            assert!(cnf.is_cnf(), "{:?}", cnf.to_text());
+            let pda : PushdownAutomaton = grammar.to_pda();
+            let letters : String = grammar.get_terminals().iter().map(|terminal| terminal.get_value().as_str()).collect();
+            for word in enumerate_words(&letters, 6) {
+                assert_eq!(cnf.cyk(&word), pda.accept(&word, AcceptanceMode::EmptyStack), "{} {}", text, word);
+            }
+        }
+    }
+
+    #[test]
+    fn pda_to_grammar() {
+        let pda : PushdownAutomaton = PushdownAutomaton::from_json_file("src/automates/PDA1.json");
+        for mode in [AcceptanceMode::EmptyStack, AcceptanceMode::FinalState] {
+            let cnf : Grammar = Grammar::from_pda(&pda, mode).to_cnf();
+            for word in enumerate_words("()[]", 6) {
+                assert_eq!(cnf.cyk(&word), balanced(&word), "{:?} {}", mode, word);
+            }
+        }
+        //aller-retour grammaire -> automate -> grammaire
+        let grammar : Grammar = Grammar::from_text("S -> a S b | c").unwrap();
+        let back : Grammar = Grammar::from_pda(&grammar.to_pda(), AcceptanceMode::EmptyStack).to_cnf();
+        for word in enumerate_words("abc", 5) {
+            assert_eq!(back.cyk(&word), grammar.accept(&word), "{}", word);
+        }
+        //le symbole d'entrée S porte le nom de l'axiome choisi par défaut: l'automate n'accepte que SS
+        let (q, z, a, s) : (State, Symbol, Symbol, Symbol) = (State::from_str("q"), Symbol::from_str("Z"), Symbol::from_str("A"), Symbol::from_str("S"));
+        let pda : PushdownAutomaton = PushdownAutomaton::new(q.clone(), z.clone(), vec![
+            (q.clone(), Some(s.clone()), z, vec![a.clone()], q.clone()),
+            (q.clone(), Some(s), a, Vec::new(), q),
+        ], HashSet::new());
+        let grammar : Grammar = Grammar::from_pda(&pda, AcceptanceMode::EmptyStack);
+        let back : PushdownAutomaton = grammar.to_pda();
+        for word in enumerate_words("S", 4) {
+            assert_eq!(grammar.accept(&word), word == "SS", "{}", word);
+            assert_eq!(back.accept(&word, AcceptanceMode::EmptyStack), word == "SS", "{}", word);
+        }
+    }
+}
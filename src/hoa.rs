@@ -0,0 +1,657 @@
+use super::{Transition, State, Symbol, FiniteStateMachine, NonDeterministicFiniteAutomaton, BuchiAutomaton};
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::fs;
+
+/// Lexème du format HOA, avec le numéro de sa ligne
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    /// Nom d'en-tête suivi de `:`, par exemple `States`
+    Header(String),
+    Identifier(String),
+    Integer(usize),
+    Text(String),
+    Punctuation(char),
+    Body,
+    End,
+}
+
+/// Découpe un texte HOA en lexèmes, en ignorant les commentaires `/* ... */`
+fn tokenize(text : &str) -> Result<Vec<(usize, Token)>, String> {
+    let letters : Vec<char> = text.chars().collect();
+    let mut tokens : Vec<(usize, Token)> = Vec::new();
+    let mut line : usize = 1;
+    let mut position : usize = 0;
+    while position < letters.len() {
+        let letter : char = letters[position];
+        let rest : String = letters[position..letters.len().min(position + 8)].iter().collect();
+        if letter == '\n' {
+            line += 1;
+            position += 1;
+        } else if letter.is_whitespace() {
+            position += 1;
+        } else if rest.starts_with("/*") {
+            let start_line : usize = line;
+            position += 2;
+            while position < letters.len() && !(letters[position] == '*' && letters.get(position + 1) == Some(&'/')) {
+                if letters[position] == '\n' {
+                    line += 1;
+                }
+                position += 1;
+            }
+            if position >= letters.len() {
+                return Err(format!("line {}: unterminated comment", start_line));
+            }
+            position += 2;
+        } else if rest.starts_with("--BODY--") {
+            tokens.push((line, Token::Body));
+            position += 8;
+        } else if rest.starts_with("--END--") {
+            tokens.push((line, Token::End));
+            position += 7;
+        } else if letter == '"' {
+            let mut value : String = String::new();
+            position += 1;
+            loop {
+                match letters.get(position) {
+                    None | Some('\n') => return Err(format!("line {}: unterminated string", line)),
+                    Some('"') => break,
+                    Some('\\') if position + 1 < letters.len() => {
+                        value.push(letters[position + 1]);
+                        position += 2;
+                    },
+                    Some(other) => {
+                        value.push(*other);
+                        position += 1;
+                    },
+                }
+            }
+            tokens.push((line, Token::Text(value)));
+            position += 1;
+        } else if letter.is_ascii_digit() {
+            let start : usize = position;
+            while position < letters.len() && letters[position].is_ascii_digit() {
+                position += 1;
+            }
+            let digits : String = letters[start..position].iter().collect();
+            tokens.push((line, Token::Integer(digits.parse().map_err(|_| format!("line {}: integer {} is too large", line, digits))?)));
+        } else if letter.is_alphabetic() || letter == '_' {
+            let start : usize = position;
+            while position < letters.len() && (letters[position].is_alphanumeric() || letters[position] == '_' || letters[position] == '-') {
+                position += 1;
+            }
+            let word : String = letters[start..position].iter().collect();
+            if letters.get(position) == Some(&':') {
+                tokens.push((line, Token::Header(word)));
+                position += 1;
+            } else {
+                tokens.push((line, Token::Identifier(word)));
+            }
+        } else if "[]{}()!&|@".contains(letter) {
+            tokens.push((line, Token::Punctuation(letter)));
+            position += 1;
+        } else {
+            return Err(format!("line {}: unexpected character '{}'", line, letter));
+        }
+    }
+    Ok(tokens)
+}
+
+/// Etiquette d'une transition: une formule booléenne sur les propositions atomiques, désignées par leur numéro
+#[derive(Debug, Clone)]
+enum Label {
+    True,
+    False,
+    Proposition(usize),
+    Not(Box<Label>),
+    And(Box<Label>, Box<Label>),
+    Or(Box<Label>, Box<Label>),
+}
+
+/// Conjonction de littéraux: les propositions vraies puis les propositions fausses
+type Cube = (BTreeSet<usize>, BTreeSet<usize>);
+
+impl Label {
+    /// Retourne la forme normale disjonctive de l'étiquette, ou de sa négation si `positive` est faux
+    ///
+    /// Les conjonctions contradictoires, qu'aucune valuation ne satisfait, sont retirées.
+    fn cubes(&self, positive : bool) -> Vec<Cube> {
+        match (self, positive) {
+            (Label::True, true) | (Label::False, false) => vec![(BTreeSet::new(), BTreeSet::new())],
+            (Label::True, false) | (Label::False, true) => Vec::new(),
+            (Label::Proposition(index), true) => vec![(BTreeSet::from([*index]), BTreeSet::new())],
+            (Label::Proposition(index), false) => vec![(BTreeSet::new(), BTreeSet::from([*index]))],
+            (Label::Not(label), _) => label.cubes(!positive),
+            (Label::Or(left, right), true) | (Label::And(left, right), false) => {
+                let mut cubes : Vec<Cube> = left.cubes(positive);
+                cubes.extend(right.cubes(positive));
+                cubes
+            },
+            (Label::And(left, right), true) | (Label::Or(left, right), false) => {
+                let rights : Vec<Cube> = right.cubes(positive);
+                left.cubes(positive).iter()
+                    .flat_map(|(true_left, false_left)| rights.iter().map(move |(true_right, false_right)| {
+                        (true_left.union(true_right).copied().collect::<BTreeSet<usize>>(), false_left.union(false_right).copied().collect::<BTreeSet<usize>>())
+                    }))
+                    .filter(|(trues, falses)| trues.is_disjoint(falses))
+                    .collect()
+            },
+        }
+    }
+
+    /// Retourne les propositions `i` dont la valuation où seule `i` est vraie satisfait l'étiquette
+    ///
+    /// Retourne `None` si l'étiquette est aussi satisfaite par une valuation où aucune ou plusieurs propositions
+    /// sont vraies: une telle valuation ne correspond à aucun symbole.
+    fn letters(&self, propositions : usize) -> Option<BTreeSet<usize>> {
+        let mut letters : BTreeSet<usize> = BTreeSet::new();
+        for (trues, falses) in self.cubes(true) {
+            //seule une conjonction qui fixe toutes les propositions, dont une seule vraie, désigne un symbole
+            if trues.len() != 1 || trues.len() + falses.len() != propositions {
+                return None;
+            }
+            letters.extend(trues);
+        }
+        Some(letters)
+    }
+}
+
+/// Automate lu dans un texte HOA: états nommés, états initiaux, états acceptants et transitions étiquetées par symbole
+struct Hoa {
+    //ligne de l'en-tête `Finite-Words:`, qui réserve l'automate aux mots finis
+    finite_words : Option<usize>,
+    states : Vec<State>,
+    starts : HashSet<State>,
+    ends : HashSet<State>,
+    alphabet : Vec<Symbol>,
+    transitions : Vec<(State, Symbol, State)>,
+}
+
+/// Analyseur d'un texte HOA découpé en lexèmes
+struct Parser {
+    tokens : Vec<(usize, Token)>,
+    position : usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.position).map(|(_, token)| token)
+    }
+
+    /// Retourne la ligne du prochain lexème, ou celle du dernier en fin de texte
+    fn line(&self) -> usize {
+        self.tokens.get(self.position).or(self.tokens.last()).map_or(1, |(line, _)| *line)
+    }
+
+    fn error<T>(&self, message : &str) -> Result<T, String> {
+        Err(format!("line {}: {}", self.line(), message))
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let token : Option<Token> = self.peek().cloned();
+        self.position += 1;
+        token
+    }
+
+    fn expect(&mut self, expected : Token, description : &str) -> Result<(), String> {
+        if self.peek() == Some(&expected) {
+            self.position += 1;
+            Ok(())
+        } else {
+            self.error(&format!("expected {}", description))
+        }
+    }
+
+    fn integer(&mut self) -> Result<usize, String> {
+        match self.peek() {
+            Some(Token::Integer(value)) => {
+                let value : usize = *value;
+                self.position += 1;
+                Ok(value)
+            },
+            _ => self.error("expected an integer"),
+        }
+    }
+
+    /// Lit un ensemble d'acceptation optionnel `{0 1 ...}`
+    fn acceptance_sets(&mut self) -> Result<Vec<usize>, String> {
+        let mut sets : Vec<usize> = Vec::new();
+        if self.peek() == Some(&Token::Punctuation('{')) {
+            self.position += 1;
+            while self.peek() != Some(&Token::Punctuation('}')) {
+                sets.push(self.integer()?);
+            }
+            self.position += 1;
+        }
+        Ok(sets)
+    }
+
+    /// disjonction := conjonction ('|' conjonction)*
+    fn disjunction(&mut self, propositions : usize) -> Result<Label, String> {
+        let mut label : Label = self.conjunction(propositions)?;
+        while self.peek() == Some(&Token::Punctuation('|')) {
+            self.position += 1;
+            label = Label::Or(Box::new(label), Box::new(self.conjunction(propositions)?));
+        }
+        Ok(label)
+    }
+
+    /// conjonction := atome ('&' atome)*
+    fn conjunction(&mut self, propositions : usize) -> Result<Label, String> {
+        let mut label : Label = self.atom(propositions)?;
+        while self.peek() == Some(&Token::Punctuation('&')) {
+            self.position += 1;
+            label = Label::And(Box::new(label), Box::new(self.atom(propositions)?));
+        }
+        Ok(label)
+    }
+
+    /// atome := 't' | 'f' | entier | '!' atome | '(' disjonction ')'
+    fn atom(&mut self, propositions : usize) -> Result<Label, String> {
+        match self.peek().cloned() {
+            Some(Token::Identifier(value)) if value == "t" => {
+                self.position += 1;
+                Ok(Label::True)
+            },
+            Some(Token::Identifier(value)) if value == "f" => {
+                self.position += 1;
+                Ok(Label::False)
+            },
+            Some(Token::Integer(index)) => {
+                if index >= propositions {
+                    return self.error(&format!("atomic proposition {} is not declared", index));
+                }
+                self.position += 1;
+                Ok(Label::Proposition(index))
+            },
+            Some(Token::Punctuation('!')) => {
+                self.position += 1;
+                Ok(Label::Not(Box::new(self.atom(propositions)?)))
+            },
+            Some(Token::Punctuation('(')) => {
+                self.position += 1;
+                let label : Label = self.disjunction(propositions)?;
+                self.expect(Token::Punctuation(')'), "')'")?;
+                Ok(label)
+            },
+            Some(Token::Punctuation('@')) => self.error("aliases are not supported"),
+            _ => self.error("expected a label"),
+        }
+    }
+
+    /// Lit l'en-tête puis le corps d'un automate
+    fn parse(&mut self) -> Result<Hoa, String> {
+        self.expect(Token::Header(String::from("HOA")), "'HOA:'")?;
+        self.expect(Token::Identifier(String::from("v1")), "version v1")?;
+        let mut count : Option<usize> = None;
+        let mut start_indices : Vec<usize> = Vec::new();
+        let mut alphabet : Vec<Symbol> = Vec::new();
+        //vrai pour la condition t, où tous les états acceptent, faux pour la condition Inf(0)
+        let mut all_accepting : Option<bool> = None;
+        let mut finite_words : Option<usize> = None;
+        loop {
+            let line : usize = self.line();
+            match self.next() {
+                Some(Token::Body) => break,
+                Some(Token::Header(header)) => match header.as_str() {
+                    "States" => count = Some(self.integer()?),
+                    "Start" => {
+                        start_indices.push(self.integer()?);
+                        if self.peek() == Some(&Token::Punctuation('&')) {
+                            return self.error("conjunctions of initial states are not supported");
+                        }
+                    },
+                    "AP" => {
+                        let size : usize = self.integer()?;
+                        for _ in 0..size {
+                            match self.next() {
+                                Some(Token::Text(name)) => alphabet.push(Symbol::new(name)),
+                                _ => return Err(format!("line {}: expected {} atomic propositions", line, size)),
+                            }
+                        }
+                    },
+                    "Acceptance" => {
+                        let sets : usize = self.integer()?;
+                        let mut condition : String = String::new();
+                        while let Some(Token::Identifier(_) | Token::Integer(_) | Token::Punctuation(_)) = self.peek() {
+                            condition.push_str(&match self.next().unwrap() {
+                                Token::Identifier(word) => word,
+                                Token::Integer(value) => value.to_string(),
+                                Token::Punctuation(letter) => String::from(letter),
+                                _ => unreachable!(),
+                            });
+                        }
+                        all_accepting = match (sets, condition.as_str()) {
+                            (0, "t") => Some(true),
+                            (1, "Inf(0)") => Some(false),
+                            _ => return Err(format!("line {}: unsupported acceptance condition {} {}", line, sets, condition)),
+                        };
+                    },
+                    "Alias" => return Err(format!("line {}: aliases are not supported", line)),
+                    "Finite-Words" => finite_words = Some(line),
+                    //les autres en-têtes (name, tool, acc-name, properties...) sont ignorés
+                    _ => while let Some(Token::Identifier(_) | Token::Integer(_) | Token::Text(_) | Token::Punctuation(_)) = self.peek() {
+                        self.position += 1;
+                    },
+                },
+                _ => return Err(format!("line {}: expected a header or '--BODY--'", line)),
+            }
+        }
+        let all_accepting : bool = all_accepting.ok_or_else(|| String::from("missing Acceptance header"))?;
+
+        //corps: les états sont nommés par leur nom entre guillemets, ou à défaut par leur numéro
+        let mut names : HashMap<usize, State> = HashMap::new();
+        let mut ends : HashSet<usize> = HashSet::new();
+        let mut edges : Vec<(usize, usize, usize)> = Vec::new();
+        let mut labels : Vec<BTreeSet<usize>> = Vec::new();
+        loop {
+            let line : usize = self.line();
+            match self.next() {
+                Some(Token::End) => break,
+                Some(Token::Header(header)) if header == "State" => {
+                    let index : usize = self.integer()?;
+                    if count.is_some_and(|count| index >= count) {
+                        return Err(format!("line {}: state {} is out of range", line, index));
+                    }
+                    if names.contains_key(&index) {
+                        return Err(format!("line {}: state {} is defined twice", line, index));
+                    }
+                    let name : State = match self.peek().cloned() {
+                        Some(Token::Text(name)) => {
+                            self.position += 1;
+                            State::new(name)
+                        },
+                        _ => State::new(index.to_string()),
+                    };
+                    names.insert(index, name);
+                    let sets : Vec<usize> = self.acceptance_sets()?;
+                    if let Some(set) = sets.iter().find(|set| all_accepting || **set != 0) {
+                        return Err(format!("line {}: acceptance set {} is not declared", line, set));
+                    }
+                    if !sets.is_empty() || all_accepting {
+                        ends.insert(index);
+                    }
+                    while self.peek() == Some(&Token::Punctuation('[')) {
+                        let label_line : usize = self.line();
+                        self.position += 1;
+                        let label : Label = self.disjunction(alphabet.len())?;
+                        self.expect(Token::Punctuation(']'), "']'")?;
+                        let edge_line : usize = self.line();
+                        let image : usize = self.integer()?;
+                        if self.peek() == Some(&Token::Punctuation('&')) {
+                            return self.error("universal branching is not supported");
+                        }
+                        if count.is_some_and(|count| image >= count) {
+                            return Err(format!("line {}: state {} is out of range", edge_line, image));
+                        }
+                        if !self.acceptance_sets()?.is_empty() {
+                            return Err(format!("line {}: transition-based acceptance is not supported", edge_line));
+                        }
+                        let letters : BTreeSet<usize> = match label.letters(alphabet.len()) {
+                            Some(letters) => letters,
+                            None => return Err(format!("line {}: label holds for a valuation where zero or several atomic propositions are true", label_line)),
+                        };
+                        edges.push((index, labels.len(), image));
+                        labels.push(letters);
+                    }
+                    if let Some(Token::Integer(_)) = self.peek() {
+                        return self.error("implicit labels are not supported");
+                    }
+                },
+                None => return Err(format!("line {}: expected '--END--'", line)),
+                _ => return Err(format!("line {}: expected 'State:' or '--END--'", line)),
+            }
+        }
+        if self.peek().is_some() {
+            return self.error("unexpected content after '--END--'");
+        }
+
+        //les états seulement cités gardent leur numéro comme nom
+        let size : usize = count.unwrap_or(0).max(names.keys().chain(start_indices.iter()).chain(edges.iter().map(|(_, _, image)| image)).map(|index| index + 1).max().unwrap_or(0));
+        let name = |index : &usize| names.get(index).cloned().unwrap_or_else(|| State::new(index.to_string()));
+        let mut transitions : Vec<(State, Symbol, State)> = Vec::new();
+        for (state, label, image) in &edges {
+            for proposition in &labels[*label] {
+                transitions.push((name(state), alphabet[*proposition].clone(), name(image)));
+            }
+        }
+        Ok(Hoa {
+            finite_words,
+            states: (0..size).map(|index| name(&index)).collect(),
+            starts: start_indices.iter().map(name).collect(),
+            ends: ends.iter().map(name).collect(),
+            alphabet,
+            transitions,
+        })
+    }
+}
+
+/// Etats initiaux, transitions et machine à état fini d'un automate lu dans un texte HOA
+type HoaParts = (HashSet<State>, HashMap<Transition<State>, HashSet<State>>, FiniteStateMachine);
+
+/// Lit un texte HOA, voir `NonDeterministicFiniteAutomaton::from_hoa`
+///
+/// Un automate marqué `Finite-Words:` ne peut pas être lu comme un automate sur les mots infinis (`omega`).
+fn read_hoa(text : &str, omega : bool) -> Result<HoaParts, String> {
+    let hoa : Hoa = Parser { tokens: tokenize(text)?, position: 0 }.parse()?;
+    if let (true, Some(line)) = (omega, hoa.finite_words) {
+        return Err(format!("line {}: a finite-word automaton cannot be read as a Büchi automaton", line));
+    }
+    let mut delta : HashMap<Transition<State>, HashSet<State>> = HashMap::new();
+    for (state, symbol, image) in hoa.transitions {
+        delta.entry(Transition::new(symbol, state)).or_default().insert(image);
+    }
+    let fsm : FiniteStateMachine = FiniteStateMachine::new(hoa.states.into_iter().collect(), hoa.alphabet.into_iter().collect(), hoa.ends);
+    Ok((hoa.starts, delta, fsm))
+}
+
+/// Ecrit un automate au format HOA, voir `NonDeterministicFiniteAutomaton::to_hoa`
+///
+/// Un automate sur les mots finis (`omega` faux) est marqué par l'en-tête `Finite-Words:` au lieu de `acc-name: Buchi`.
+fn write_hoa(starts : &HashSet<State>, delta : &HashMap<Transition<State>, HashSet<State>>, fsm : &FiniteStateMachine, omega : bool) -> String {
+    let quote = |name : &str| format!("\"{}\"", name.replace('\\', "\\\\").replace('"', "\\\""));
+    let mut states : Vec<&State> = fsm.get_states().iter().collect();
+    states.sort();
+    let indices : HashMap<&State, usize> = states.iter().enumerate().map(|(index, state)| (*state, index)).collect();
+    let mut alphabet : Vec<&Symbol> = fsm.get_alphabet().iter().collect();
+    alphabet.sort();
+    let mut start_indices : Vec<usize> = starts.iter().map(|state| indices[state]).collect();
+    start_indices.sort();
+
+    let mut text : String = format!("HOA: v1\nStates: {}\n", states.len());
+    for index in start_indices {
+        text.push_str(&format!("Start: {}\n", index));
+    }
+    text.push_str(&format!("AP: {}", alphabet.len()));
+    for symbol in &alphabet {
+        text.push_str(&format!(" {}", quote(symbol.get_value())));
+    }
+    if omega {
+        text.push_str("\nacc-name: Buchi\nAcceptance: 1 Inf(0)\n");
+    } else {
+        //un en-tête commençant par une majuscule doit être compris par l'outil qui lit le fichier, sinon il le refuse
+        text.push_str("\nAcceptance: 1 Inf(0)\nFinite-Words:\n");
+    }
+    text.push_str("--BODY--\n");
+    //le symbole numéro i est la valuation où seule la proposition i est vraie
+    let labels : Vec<String> = (0..alphabet.len())
+        .map(|proposition| (0..alphabet.len()).map(|index| if index == proposition { index.to_string() } else { format!("!{}", index) }).collect::<Vec<String>>().join("&"))
+        .collect();
+    for state in &states {
+        text.push_str(&format!("State: {} {}", indices[state], quote(state.get_name())));
+        if fsm.get_ends().contains(*state) {
+            text.push_str(" {0}");
+        }
+        text.push('\n');
+        for (proposition, symbol) in alphabet.iter().enumerate() {
+            let mut images : Vec<usize> = delta.get(&Transition::new((*symbol).clone(), (*state).clone())).into_iter().flatten().map(|image| indices[image]).collect();
+            images.sort();
+            for image in images {
+                text.push_str(&format!("[{}] {}\n", labels[proposition], image));
+            }
+        }
+    }
+    text.push_str("--END--\n");
+    text
+}
+
+impl NonDeterministicFiniteAutomaton {
+    /// Créer un automate non déterministe depuis un texte au format HOA (Hanoi Omega-Automata)
+    ///
+    /// Chaque proposition atomique est un symbole de l'alphabet: lire le symbole `x` correspond à la valuation
+    /// où seule la proposition `x` est vraie, et une transition existe pour chaque symbole dont la valuation
+    /// satisfait son étiquette. Une étiquette satisfaite par une valuation où aucune ou plusieurs propositions
+    /// sont vraies, comme `[!0]` ou `[0]` dès qu'il y a deux propositions, est refusée: la transition ne
+    /// correspondrait à aucun symbole. Les états sont nommés par leur nom entre guillemets, ou à défaut par leur numéro.
+    /// Seules les conditions d'acceptation `0 t` (tous les états sont finaux) et `1 Inf(0)` (les états de
+    /// l'ensemble 0 sont finaux) sont reconnues; les alias, les étiquettes implicites, l'acceptation sur les
+    /// transitions et les branchements universels sont refusés.
+    ///
+    /// # Arguments
+    ///
+    /// * `text` - Le texte HOA
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use automate::*;
+    /// fn main() {
+    ///     let text : &str = "HOA: v1\nStart: 0\nAP: 2 \"a\" \"b\"\nAcceptance: 1 Inf(0)\n--BODY--\nState: 0\n[0&!1] 1\nState: 1 {0}\n[!0&1] 0\n--END--";
+    ///     let nfa : NonDeterministicFiniteAutomaton = NonDeterministicFiniteAutomaton::from_hoa(text).unwrap();
+    ///     assert!(nfa.accept("aba"));
+    ///     assert!(!nfa.accept("ab"));
+    ///     assert!(NonDeterministicFiniteAutomaton::from_hoa(&text.replace("[0&!1]", "[0]")).is_err());
+    ///     assert_eq!(NonDeterministicFiniteAutomaton::from_hoa("HOA: v1\nAlias: @a 0").unwrap_err(), "line 2: aliases are not supported");
+    /// }
+    /// ```
+    ///
+    /// # Return
+    ///
+    /// * `Result<NonDeterministicFiniteAutomaton, String>` - L'automate, une erreur indiquant la ligne fautive
+    ///
+    pub fn from_hoa(text : &str) -> Result<Self, String> {
+        let (starts, delta, fsm) = read_hoa(text, false)?;
+        Ok(NonDeterministicFiniteAutomaton::new(starts, delta, fsm))
+    }
+
+    /// Créer un automate non déterministe depuis un chemin vers un fichier HOA, voir `from_hoa`
+    pub fn from_hoa_file(path : &str) -> Result<Self, String> {
+        let text : String = fs::read_to_string(path).map_err(|error| format!("{}: {}", path, error))?;
+        NonDeterministicFiniteAutomaton::from_hoa(&text)
+    }
+
+    /// Ecrit l'automate au format HOA, lisible par `from_hoa`
+    ///
+    /// Les états sont numérotés dans l'ordre de leurs noms, qui sont conservés entre guillemets. Les symboles
+    /// deviennent les propositions atomiques et les états finaux forment l'ensemble d'acceptation 0. Le format
+    /// HOA décrit des automates sur les mots infinis: l'en-tête `Finite-Words:` indique que l'automate lit des
+    /// mots finis, et un outil qui ne le connaît pas refuse le fichier au lieu d'y lire un automate de Büchi.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use automate::*;
+    /// fn main() {
+    ///     let dfa : DeterministicFiniteAutomaton = DeterministicFiniteAutomaton::from_json_file("src/automates/DFA1.json");
+    ///     let text : String = dfa.to_nfa().to_hoa();
+    ///     assert!(text.contains("State: 0 \"q_0\" {0}\n[0&!1] 1\n"));
+    ///     assert!(NonDeterministicFiniteAutomaton::from_hoa(&text).unwrap().accept("abab"));
+    /// }
+    /// ```
+    ///
+    pub fn to_hoa(&self) -> String {
+        write_hoa(self.get_starts(), self.get_delta(), &FiniteStateMachine::new(self.get_states().clone(), self.get_alphabet().clone(), self.get_ends().clone()), false)
+    }
+}
+
+impl BuchiAutomaton {
+    /// Créer un automate de Büchi depuis un texte au format HOA, voir `NonDeterministicFiniteAutomaton::from_hoa`
+    ///
+    /// Un automate marqué `Finite-Words:`, écrit par `NonDeterministicFiniteAutomaton::to_hoa`, est refusé.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use automate::*;
+    /// fn main() {
+    ///     let buchi : BuchiAutomaton = BuchiAutomaton::from_hoa_file("src/automates/HOA1.hoa").unwrap();
+    ///     assert!(buchi.accept_lasso("b", "ab"));
+    ///     assert!(!buchi.accept_lasso("a", "b"));
+    /// }
+    /// ```
+    ///
+    pub fn from_hoa(text : &str) -> Result<Self, String> {
+        let (starts, delta, fsm) = read_hoa(text, true)?;
+        Ok(BuchiAutomaton::new(starts, delta, fsm))
+    }
+
+    /// Créer un automate de Büchi depuis un chemin vers un fichier HOA, voir `from_hoa`
+    pub fn from_hoa_file(path : &str) -> Result<Self, String> {
+        let text : String = fs::read_to_string(path).map_err(|error| format!("{}: {}", path, error))?;
+        BuchiAutomaton::from_hoa(&text)
+    }
+
+    /// Ecrit l'automate au format HOA avec la condition de Büchi `Inf(0)`, voir `NonDeterministicFiniteAutomaton::to_hoa`
+    pub fn to_hoa(&self) -> String {
+        write_hoa(self.get_starts(), self.get_delta(), &FiniteStateMachine::new(self.get_states().clone(), self.get_alphabet().clone(), self.get_ends().clone()), true)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn assert_same(nfa : &NonDeterministicFiniteAutomaton, other : &NonDeterministicFiniteAutomaton) {
+        assert_eq!(nfa.get_starts(), other.get_starts());
+        assert_eq!(nfa.get_states(), other.get_states());
+        assert_eq!(nfa.get_alphabet(), other.get_alphabet());
+        assert_eq!(nfa.get_ends(), other.get_ends());
+        assert_eq!(nfa.get_delta(), other.get_delta());
+    }
+
+    #[test]
+    fn fixtures_round_trip() {
+        for path in ["src/automates/HOA1.hoa", "src/automates/HOA2.hoa"] {
+            let nfa : NonDeterministicFiniteAutomaton = NonDeterministicFiniteAutomaton::from_hoa_file(path).unwrap();
+            let text : String = nfa.to_hoa();
+            let back : NonDeterministicFiniteAutomaton = NonDeterministicFiniteAutomaton::from_hoa(&text).unwrap();
+            assert_same(&nfa, &back);
+            assert_eq!(back.to_hoa(), text);
+        }
+        //les états sans nom gardent leur numéro, les étiquettes sont évaluées par symbole
+        let hoa2 : NonDeterministicFiniteAutomaton = NonDeterministicFiniteAutomaton::from_hoa_file("src/automates/HOA2.hoa").unwrap();
+        assert_eq!(hoa2.get_states().len(), 3);
+        assert_eq!(hoa2.get_ends(), &HashSet::from([State::from_str("0"), State::from_str("1"), State::from_str("2")]));
+        assert!(hoa2.accept("abc") && hoa2.accept("cc") && !hoa2.accept("ca"));
+        let buchi : BuchiAutomaton = BuchiAutomaton::from_hoa_file("src/automates/HOA1.hoa").unwrap();
+        assert_eq!(BuchiAutomaton::from_hoa(&buchi.to_hoa()).unwrap().to_hoa(), buchi.to_hoa());
+        //un automate sur les mots finis n'est pas écrit comme un automate de Büchi
+        assert!(buchi.to_hoa().contains("acc-name: Buchi\n") && !buchi.to_hoa().contains("Finite-Words:"));
+        let finite : String = hoa2.to_hoa();
+        assert!(finite.contains("Finite-Words:\n") && !finite.contains("acc-name"));
+        assert_eq!(BuchiAutomaton::from_hoa(&finite).unwrap_err(), "line 6: a finite-word automaton cannot be read as a Büchi automaton");
+        //un automate json survit à un aller-retour
+        let nfa : NonDeterministicFiniteAutomaton = NonDeterministicFiniteAutomaton::from_json_file("src/automates/NFA1.json");
+        assert_same(&NonDeterministicFiniteAutomaton::from_hoa(&nfa.to_hoa()).unwrap(), &nfa);
+    }
+
+    #[test]
+    fn errors() {
+        let cases : [(&str, &str); 11] = [
+            ("", "line 1: expected 'HOA:'"),
+            ("HOA: v1\nStates: 1\n--BODY--\n--END--", "missing Acceptance header"),
+            ("HOA: v1\nAcceptance: 2 Inf(0)&Inf(1)\n--BODY--\n--END--", "line 2: unsupported acceptance condition 2 Inf(0)&Inf(1)"),
+            ("HOA: v1\nAP: 1 \"a\"\nAcceptance: 0 t\n--BODY--\nState: 0\n[0 | 1] 0\n--END--", "line 6: atomic proposition 1 is not declared"),
+            ("HOA: v1\nStates: 1\nAcceptance: 0 t\n--BODY--\nState: 0\n[t] 1\n--END--", "line 6: state 1 is out of range"),
+            ("HOA: v1\nAcceptance: 1 Inf(0)\n--BODY--\nState: 0\n[t] 0 {0}\n--END--", "line 5: transition-based acceptance is not supported"),
+            ("HOA: v1\nAcceptance: 0 t\n/* pas de fin\n--BODY--", "line 3: unterminated comment"),
+            ("HOA: v1\nAcceptance: 0 t\n--BODY--\nState: 0\n", "line 4: expected '--END--'"),
+            //la valuation vide satisfait !0, la valuation {0, 1} satisfait 0, et t les satisfait toutes
+            ("HOA: v1\nAP: 2 \"a\" \"b\"\nAcceptance: 0 t\n--BODY--\nState: 0\n[!0] 0\n--END--", "line 6: label holds for a valuation where zero or several atomic propositions are true"),
+            ("HOA: v1\nAP: 2 \"a\" \"b\"\nAcceptance: 0 t\n--BODY--\nState: 0\n[0&!1] 0\n[0] 0\n--END--", "line 7: label holds for a valuation where zero or several atomic propositions are true"),
+            ("HOA: v1\nAP: 1 \"a\"\nAcceptance: 0 t\n--BODY--\nState: 0\n[t] 0\n--END--", "line 6: label holds for a valuation where zero or several atomic propositions are true"),
+        ];
+        for (text, message) in cases {
+            assert_eq!(NonDeterministicFiniteAutomaton::from_hoa(text).unwrap_err(), message, "{}", text);
+        }
+    }
+}
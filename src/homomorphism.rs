@@ -0,0 +1,235 @@
+use super::{Transition, State, Symbol, FiniteStateMachine, DeterministicFiniteAutomaton, NonDeterministicFiniteAutomaton};
+use std::collections::{HashMap, HashSet};
+
+impl NonDeterministicFiniteAutomaton {
+    /// Applique un homomorphisme: chaque symbole est remplacé par un mot
+    ///
+    /// Chaque transition est remplacée par une chaîne d'états intermédiaires lisant l'image de son symbole.
+    /// Une image vide revient à une epsilon-transition, qui est ensuite supprimée.
+    ///
+    /// # Arguments
+    ///
+    /// * `homomorphism` - L'image de chaque symbole de l'alphabet
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use automate::*;
+    /// use std::collections::HashMap;
+    /// fn main() {
+    ///     let nfa : NonDeterministicFiniteAutomaton = NonDeterministicFiniteAutomaton::from_json_file("src/automates/NFA3.json");
+    ///     //a -> 0, b -> 11, c -> ε
+    ///     let homomorphism : HashMap<Symbol, Vec<Symbol>> = HashMap::from([
+    ///         (Symbol::from_str("a"), vec![Symbol::from_str("0")]),
+    ///         (Symbol::from_str("b"), vec![Symbol::from_str("1"), Symbol::from_str("1")]),
+    ///         (Symbol::from_str("c"), vec![]),
+    ///     ]);
+    ///     let image : NonDeterministicFiniteAutomaton = nfa.apply_homomorphism(&homomorphism).unwrap();
+    ///     assert!(image.accept("011"));
+    /// }
+    /// ```
+    ///
+    /// # Return
+    ///
+    /// * `Result<NonDeterministicFiniteAutomaton, String>` - L'automate reconnaissant l'image du langage, une erreur si un symbole n'a pas d'image
+    ///
+    pub fn apply_homomorphism(&self, homomorphism : &HashMap<Symbol, Vec<Symbol>>) -> Result<NonDeterministicFiniteAutomaton, String> {
+        let mut transitions : Vec<(State, Option<Symbol>, State)> = Vec::new();
+        let mut alphabet : HashSet<Symbol> = HashSet::new();
+        let mut sorted_delta : Vec<(&Transition<State>, &HashSet<State>)> = self.get_delta().iter().collect();
+        sorted_delta.sort_by(|left, right| (left.0.get_content(), left.0.get_symbol()).cmp(&(right.0.get_content(), right.0.get_symbol())));
+        for (transition, images) in sorted_delta {
+            let word : &Vec<Symbol> = homomorphism.get(transition.get_symbol())
+                .ok_or_else(|| format!("symbol {} has no image", transition.get_symbol().get_value()))?;
+            alphabet.extend(word.iter().cloned());
+            let mut sorted_images : Vec<&State> = images.iter().collect();
+            sorted_images.sort();
+            for image in sorted_images {
+                if word.is_empty() {
+                    transitions.push((transition.get_content().clone(), None, image.clone()));
+                    continue;
+                }
+                //etats intermediaires, nommés d'après la transition qu'ils remplacent
+                let mut previous : State = transition.get_content().clone();
+                for (index, symbol) in word.iter().enumerate() {
+                    let next : State = if index + 1 == word.len() {
+                        image.clone()
+                    } else {
+                        State::new(format!("({},{},{})#{}", transition.get_content().get_name(), transition.get_symbol().get_value(), image.get_name(), index + 1))
+                    };
+                    transitions.push((previous, Some(symbol.clone()), next.clone()));
+                    previous = next;
+                }
+            }
+        }
+        let result : NonDeterministicFiniteAutomaton = NonDeterministicFiniteAutomaton::from_epsilon_transitions(self.get_starts().clone(), &transitions, self.get_ends(), alphabet);
+        //les etats d'origine sans transition restent des etats de l'automate
+        let states : HashSet<State> = result.get_states().union(self.get_states()).cloned().collect();
+        let fsm : FiniteStateMachine = FiniteStateMachine::new(states, result.get_alphabet().clone(), result.get_ends().clone());
+        Ok(NonDeterministicFiniteAutomaton::new(result.get_starts().clone(), result.get_delta().clone(), fsm))
+    }
+
+    /// Renomme les symboles de l'alphabet
+    ///
+    /// # Arguments
+    ///
+    /// * `renaming` - Le nouveau nom de chaque symbole, qui doit être une bijection sur l'alphabet
+    ///
+    /// # Return
+    ///
+    /// * `Result<NonDeterministicFiniteAutomaton, String>` - L'automate renommé, une erreur si `renaming` n'est pas une bijection
+    ///
+    pub fn rename_alphabet(&self, renaming : &HashMap<Symbol, Symbol>) -> Result<NonDeterministicFiniteAutomaton, String> {
+        check_bijection(self.get_alphabet(), renaming)?;
+        let delta : HashMap<Transition<State>, HashSet<State>> = self.get_delta().iter()
+            .map(|(transition, images)| (Transition::new(renaming[transition.get_symbol()].clone(), transition.get_content().clone()), images.clone()))
+            .collect();
+        let alphabet : HashSet<Symbol> = self.get_alphabet().iter().map(|symbol| renaming[symbol].clone()).collect();
+        let fsm : FiniteStateMachine = FiniteStateMachine::new(self.get_states().clone(), alphabet, self.get_ends().clone());
+        Ok(NonDeterministicFiniteAutomaton::new(self.get_starts().clone(), delta, fsm))
+    }
+}
+
+impl DeterministicFiniteAutomaton {
+    /// Applique l'inverse d'un homomorphisme: les mots dont l'image est acceptée par self
+    ///
+    /// L'automate garde ses états, la lecture d'un symbole `a` depuis un état revient à lire son image h(a).
+    /// L'alphabet du résultat est l'ensemble des symboles ayant une image.
+    ///
+    /// # Arguments
+    ///
+    /// * `homomorphism` - L'image de chaque symbole du nouvel alphabet, exprimée sur l'alphabet de self
+    ///
+    /// # Return
+    ///
+    /// * `DeterministicFiniteAutomaton` - L'automate reconnaissant h⁻¹(L(self))
+    ///
+    pub fn inverse_homomorphism(&self, homomorphism : &HashMap<Symbol, Vec<Symbol>>) -> DeterministicFiniteAutomaton {
+        let mut delta : HashMap<Transition<State>, State> = HashMap::new();
+        for state in self.get_states() {
+            for (symbol, word) in homomorphism {
+                let mut current : Option<State> = Some(state.clone());
+                for letter in word {
+                    current = current.and_then(|current| self.apply_delta(Transition::new(letter.clone(), current)).cloned());
+                }
+                if let Some(image) = current {
+                    delta.insert(Transition::new(symbol.clone(), state.clone()), image);
+                }
+            }
+        }
+        let fsm : FiniteStateMachine = FiniteStateMachine::new(self.get_states().clone(), homomorphism.keys().cloned().collect(), self.get_ends().clone());
+        DeterministicFiniteAutomaton::new(self.get_start().clone(), delta, fsm)
+    }
+
+    /// Renomme les symboles de l'alphabet
+    ///
+    /// # Arguments
+    ///
+    /// * `renaming` - Le nouveau nom de chaque symbole, qui doit être une bijection sur l'alphabet
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use automate::*;
+    /// use std::collections::HashMap;
+    /// fn main() {
+    ///     let dfa : DeterministicFiniteAutomaton = DeterministicFiniteAutomaton::from_json_file("src/automates/DFA2.json");
+    ///     let renaming : HashMap<Symbol, Symbol> = HashMap::from([
+    ///         (Symbol::from_str("0"), Symbol::from_str("low")),
+    ///         (Symbol::from_str("1"), Symbol::from_str("high")),
+    ///     ]);
+    ///     let renamed : DeterministicFiniteAutomaton = dfa.rename_alphabet(&renaming).unwrap();
+    ///     assert!(renamed.accept_symbols(&[Symbol::from_str("low"), Symbol::from_str("high")]));
+    /// }
+    /// ```
+    ///
+    /// # Return
+    ///
+    /// * `Result<DeterministicFiniteAutomaton, String>` - L'automate renommé, une erreur si `renaming` n'est pas une bijection
+    ///
+    pub fn rename_alphabet(&self, renaming : &HashMap<Symbol, Symbol>) -> Result<DeterministicFiniteAutomaton, String> {
+        check_bijection(self.get_alphabet(), renaming)?;
+        let delta : HashMap<Transition<State>, State> = self.get_delta().iter()
+            .map(|(transition, image)| (Transition::new(renaming[transition.get_symbol()].clone(), transition.get_content().clone()), image.clone()))
+            .collect();
+        let alphabet : HashSet<Symbol> = self.get_alphabet().iter().map(|symbol| renaming[symbol].clone()).collect();
+        let fsm : FiniteStateMachine = FiniteStateMachine::new(self.get_states().clone(), alphabet, self.get_ends().clone());
+        Ok(DeterministicFiniteAutomaton::new(self.get_start().clone(), delta, fsm))
+    }
+}
+
+/// Vérifie que `renaming` définit une bijection entre `alphabet` et son image
+fn check_bijection(alphabet : &HashSet<Symbol>, renaming : &HashMap<Symbol, Symbol>) -> Result<(), String> {
+    let mut sorted : Vec<&Symbol> = alphabet.iter().collect();
+    sorted.sort();
+    let mut images : HashMap<&Symbol, &Symbol> = HashMap::new();
+    for symbol in sorted {
+        let image : &Symbol = renaming.get(symbol).ok_or_else(|| format!("symbol {} has no image", symbol.get_value()))?;
+        if let Some(previous) = images.insert(image, symbol) {
+            return Err(format!("symbols {} and {} have the same image {}", previous.get_value(), symbol.get_value(), image.get_value()));
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use super::super::test_words::enumerate_words;
+
+    /// Image d'un mot par l'homomorphisme, concaténée en String
+    fn image(homomorphism : &HashMap<Symbol, Vec<Symbol>>, word : &str) -> String {
+        word.chars().flat_map(|letter| homomorphism[&Symbol::new(String::from(letter))].iter().map(|symbol| symbol.get_value().clone())).collect()
+    }
+
+    #[test]
+    fn homomorphisms() {
+        let nfa3 : NonDeterministicFiniteAutomaton = NonDeterministicFiniteAutomaton::from_json_file("src/automates/NFA3.json");
+        let nfa1 : NonDeterministicFiniteAutomaton = NonDeterministicFiniteAutomaton::from_json_file("src/automates/NFA1.json");
+        let homomorphism : HashMap<Symbol, Vec<Symbol>> = HashMap::from([
+            (Symbol::from_str("a"), vec![Symbol::from_str("0")]),
+            (Symbol::from_str("b"), vec![Symbol::from_str("1"), Symbol::from_str("0")]),
+            (Symbol::from_str("c"), vec![]),
+        ]);
+        let sources : Vec<String> = enumerate_words("abc", 6);
+        for nfa in [&nfa1, &nfa3] {
+            let result : NonDeterministicFiniteAutomaton = nfa.apply_homomorphism(&homomorphism).unwrap();
+            let images : HashSet<String> = sources.iter().filter(|word| nfa.accept(word)).map(|word| image(&homomorphism, word)).collect();
+            for word in enumerate_words("01", 6) {
+                assert_eq!(result.accept(&word), images.contains(&word), "{}", word);
+            }
+        }
+        assert!(nfa3.apply_homomorphism(&HashMap::new()).is_err());
+
+        //inverse: h(a) = 01, h(b) = ε, h(c) = 1
+        let dfa2 : DeterministicFiniteAutomaton = DeterministicFiniteAutomaton::from_json_file("src/automates/DFA2.json");
+        let inverse_homomorphism : HashMap<Symbol, Vec<Symbol>> = HashMap::from([
+            (Symbol::from_str("a"), vec![Symbol::from_str("0"), Symbol::from_str("1")]),
+            (Symbol::from_str("b"), vec![]),
+            (Symbol::from_str("c"), vec![Symbol::from_str("1")]),
+        ]);
+        let inverse : DeterministicFiniteAutomaton = dfa2.inverse_homomorphism(&inverse_homomorphism);
+        for word in enumerate_words("abc", 5) {
+            assert_eq!(inverse.accept(&word), dfa2.accept(&image(&inverse_homomorphism, &word)), "{}", word);
+        }
+    }
+
+    #[test]
+    fn alphabet_renaming() {
+        let dfa2 : DeterministicFiniteAutomaton = DeterministicFiniteAutomaton::from_json_file("src/automates/DFA2.json");
+        let (low, high) : (Symbol, Symbol) = (Symbol::from_str("low"), Symbol::from_str("high"));
+        let renaming : HashMap<Symbol, Symbol> = HashMap::from([(Symbol::from_str("0"), low.clone()), (Symbol::from_str("1"), high.clone())]);
+        let renamed : DeterministicFiniteAutomaton = dfa2.rename_alphabet(&renaming).unwrap();
+        assert_eq!(renamed.get_alphabet(), &HashSet::from([low.clone(), high.clone()]));
+        assert!(renamed.accept_symbols(&[low.clone(), low.clone(), high.clone()]));
+        assert!(!renamed.accept_symbols(&[high.clone(), high.clone()]));
+        let renamed_nfa : NonDeterministicFiniteAutomaton = dfa2.to_nfa().rename_alphabet(&renaming).unwrap();
+        assert!(renamed_nfa.accept_symbols(&[high.clone(), low.clone()]));
+
+        //pas une bijection: deux symboles ont la même image, ou un symbole n'a pas d'image
+        let not_injective : HashMap<Symbol, Symbol> = HashMap::from([(Symbol::from_str("0"), low.clone()), (Symbol::from_str("1"), low.clone())]);
+        assert!(dfa2.rename_alphabet(&not_injective).is_err());
+        let partial : HashMap<Symbol, Symbol> = HashMap::from([(Symbol::from_str("0"), low)]);
+        assert_eq!(dfa2.to_nfa().rename_alphabet(&partial).unwrap_err(), "symbol 1 has no image");
+    }
+}
@@ -0,0 +1,219 @@
+use super::{State, Symbol, DeterministicFiniteAutomaton, NonDeterministicFiniteAutomaton, to_symbols};
+use std::collections::HashSet;
+
+/// Construit l'automate des mots à distance au plus `distance` de `word`
+///
+/// L'état `i,e` indique que les `i` premières lettres de `word` ont été lues avec `e` erreurs. Les suppressions
+/// sont des epsilon-transitions, supprimées par `from_epsilon_transitions`. Une transposition passe par l'état
+/// intermédiaire `i,e,t` après avoir lu la lettre `i+1` de `word`.
+fn edit_automaton(word : &str, distance : usize, alphabet : &HashSet<Symbol>, transpositions : bool) -> NonDeterministicFiniteAutomaton {
+    let letters : Vec<Symbol> = to_symbols(word);
+    let mut alphabet : HashSet<Symbol> = alphabet.clone();
+    alphabet.extend(letters.iter().cloned());
+    let mut sorted_alphabet : Vec<Symbol> = alphabet.iter().cloned().collect();
+    sorted_alphabet.sort();
+
+    let name = |position : usize, errors : usize| State::new(format!("{},{}", position, errors));
+    let mut transitions : Vec<(State, Option<Symbol>, State)> = Vec::new();
+    let mut ends : HashSet<State> = HashSet::new();
+    for errors in 0..=distance {
+        ends.insert(name(letters.len(), errors));
+        for position in 0..=letters.len() {
+            let state : State = name(position, errors);
+            if let Some(letter) = letters.get(position) {
+                transitions.push((state.clone(), Some(letter.clone()), name(position + 1, errors)));
+            }
+            if errors == distance {
+                continue;
+            }
+            for symbol in &sorted_alphabet {
+                //insertion de symbol
+                transitions.push((state.clone(), Some(symbol.clone()), name(position, errors + 1)));
+                //substitution de la lettre courante par symbol
+                if letters.get(position).is_some_and(|letter| letter != symbol) {
+                    transitions.push((state.clone(), Some(symbol.clone()), name(position + 1, errors + 1)));
+                }
+            }
+            if position < letters.len() {
+                //suppression de la lettre courante
+                transitions.push((state.clone(), None, name(position + 1, errors + 1)));
+            }
+            if transpositions && position + 1 < letters.len() && letters[position] != letters[position + 1] {
+                let swapped : State = State::new(format!("{},{},t", position, errors));
+                transitions.push((state.clone(), Some(letters[position + 1].clone()), swapped.clone()));
+                transitions.push((swapped, Some(letters[position].clone()), name(position + 2, errors + 1)));
+            }
+        }
+    }
+    NonDeterministicFiniteAutomaton::from_epsilon_transitions(HashSet::from([name(0, 0)]), &transitions, &ends, alphabet)
+}
+
+impl NonDeterministicFiniteAutomaton {
+    /// Créer l'automate de Levenshtein d'un mot: les mots obtenus par au plus `distance` insertions, suppressions ou substitutions
+    ///
+    /// # Arguments
+    ///
+    /// * `word` - Le mot de référence
+    /// * `distance` - La distance d'édition maximale
+    /// * `alphabet` - Les symboles pouvant être insérés ou substitués, auxquels s'ajoutent les lettres de `word`
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use automate::*;
+    /// use std::collections::HashSet;
+    /// fn main() {
+    ///     let alphabet : HashSet<Symbol> = HashSet::from([Symbol::from_str("a"), Symbol::from_str("b"), Symbol::from_str("c")]);
+    ///     let nfa : NonDeterministicFiniteAutomaton = NonDeterministicFiniteAutomaton::levenshtein("abc", 1, &alphabet);
+    ///     assert!(nfa.accept("abc"));
+    ///     assert!(nfa.accept("ab"));
+    ///     assert!(nfa.accept("abbc"));
+    ///     assert!(nfa.accept("aac"));
+    ///     assert!(!nfa.accept("bac"));
+    /// }
+    /// ```
+    ///
+    /// # Return
+    ///
+    /// * `NonDeterministicFiniteAutomaton` - L'automate des mots à distance au plus `distance` de `word`
+    ///
+    pub fn levenshtein(word : &str, distance : usize, alphabet : &HashSet<Symbol>) -> NonDeterministicFiniteAutomaton {
+        edit_automaton(word, distance, alphabet, false)
+    }
+
+    /// Créer l'automate de Damerau-Levenshtein d'un mot, qui autorise en plus la transposition de deux lettres adjacentes
+    ///
+    /// La distance utilisée est la distance de Damerau-Levenshtein restreinte: deux lettres transposées ne sont plus modifiées.
+    ///
+    /// # Arguments
+    ///
+    /// * `word` - Le mot de référence
+    /// * `distance` - La distance d'édition maximale
+    /// * `alphabet` - Les symboles pouvant être insérés ou substitués, auxquels s'ajoutent les lettres de `word`
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use automate::*;
+    /// use std::collections::HashSet;
+    /// fn main() {
+    ///     let nfa : NonDeterministicFiniteAutomaton = NonDeterministicFiniteAutomaton::damerau_levenshtein("abc", 1, &HashSet::new());
+    ///     assert!(nfa.accept("bac"));
+    ///     assert!(!nfa.accept("bca"));
+    /// }
+    /// ```
+    ///
+    /// # Return
+    ///
+    /// * `NonDeterministicFiniteAutomaton` - L'automate des mots à distance au plus `distance` de `word`
+    ///
+    pub fn damerau_levenshtein(word : &str, distance : usize, alphabet : &HashSet<Symbol>) -> NonDeterministicFiniteAutomaton {
+        edit_automaton(word, distance, alphabet, true)
+    }
+}
+
+impl DeterministicFiniteAutomaton {
+    /// Créer l'automate de Levenshtein minimal d'un mot, voir `NonDeterministicFiniteAutomaton::levenshtein`
+    pub fn levenshtein(word : &str, distance : usize, alphabet : &HashSet<Symbol>) -> DeterministicFiniteAutomaton {
+        NonDeterministicFiniteAutomaton::levenshtein(word, distance, alphabet).determinize().minimize()
+    }
+
+    /// Créer l'automate de Damerau-Levenshtein minimal d'un mot, voir `NonDeterministicFiniteAutomaton::damerau_levenshtein`
+    pub fn damerau_levenshtein(word : &str, distance : usize, alphabet : &HashSet<Symbol>) -> DeterministicFiniteAutomaton {
+        NonDeterministicFiniteAutomaton::damerau_levenshtein(word, distance, alphabet).determinize().minimize()
+    }
+
+    /// Retourne les mots reconnus par l'automate proches de `word`, pour proposer des corrections orthographiques
+    ///
+    /// Le dictionnaire est intersecté avec l'automate de Levenshtein (ou de Damerau-Levenshtein) du mot,
+    /// construit sur l'alphabet du dictionnaire.
+    ///
+    /// # Arguments
+    ///
+    /// * `word` - Le mot à corriger
+    /// * `distance` - La distance d'édition maximale
+    /// * `transpositions` - Si les transpositions de lettres adjacentes comptent pour une seule erreur
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use automate::*;
+    /// fn main() {
+    ///     let dictionary : DeterministicFiniteAutomaton = DeterministicFiniteAutomaton::from_words(&["chat", "chant", "champ", "achat", "rat"]);
+    ///     assert_eq!(dictionary.suggestions("caht", 1, true), vec!["chat"]);
+    ///     assert_eq!(dictionary.suggestions("chat", 1, false), vec!["achat", "chant", "chat"]);
+    /// }
+    /// ```
+    ///
+    /// # Return
+    ///
+    /// * `Vec<String>` - Les mots du dictionnaire à distance au plus `distance` de `word`, triés
+    ///
+    pub fn suggestions(&self, word : &str, distance : usize, transpositions : bool) -> Vec<String> {
+        let automaton : NonDeterministicFiniteAutomaton = edit_automaton(word, distance, self.get_alphabet(), transpositions);
+        let near : DeterministicFiniteAutomaton = self.intersection(&automaton.determinize());
+        near.accepted_words().unwrap_or_default().iter()
+            .map(|word| word.iter().map(|symbol| symbol.get_value().as_str()).collect())
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use super::super::test_words::enumerate_words;
+
+    /// Distance d'édition par programmation dynamique, avec transpositions restreintes si `transpositions`
+    fn edit_distance(left : &str, right : &str, transpositions : bool) -> usize {
+        let left : Vec<char> = left.chars().collect();
+        let right : Vec<char> = right.chars().collect();
+        let mut table : Vec<Vec<usize>> = vec![vec![0; right.len() + 1]; left.len() + 1];
+        for i in 0..=left.len() {
+            for j in 0..=right.len() {
+                table[i][j] = if i == 0 || j == 0 {
+                    i + j
+                } else {
+                    let substitution : usize = table[i - 1][j - 1] + usize::from(left[i - 1] != right[j - 1]);
+                    substitution.min(table[i - 1][j] + 1).min(table[i][j - 1] + 1)
+                };
+                if transpositions && i > 1 && j > 1 && left[i - 1] == right[j - 2] && left[i - 2] == right[j - 1] {
+                    table[i][j] = table[i][j].min(table[i - 2][j - 2] + 1);
+                }
+            }
+        }
+        table[left.len()][right.len()]
+    }
+
+    #[test]
+    fn edit_distances() {
+        let alphabet : HashSet<Symbol> = HashSet::from([Symbol::from_str("a"), Symbol::from_str("b"), Symbol::from_str("c")]);
+        for word in ["", "ab", "aab", "abca"] {
+            for distance in 0..3 {
+                let levenshtein : NonDeterministicFiniteAutomaton = NonDeterministicFiniteAutomaton::levenshtein(word, distance, &alphabet);
+                let damerau : NonDeterministicFiniteAutomaton = NonDeterministicFiniteAutomaton::damerau_levenshtein(word, distance, &alphabet);
+                let minimal : DeterministicFiniteAutomaton = DeterministicFiniteAutomaton::damerau_levenshtein(word, distance, &alphabet);
+                for text in enumerate_words("abc", word.len() + distance + 1) {
+                    assert_eq!(levenshtein.accept(&text), edit_distance(word, &text, false) <= distance, "{} {} {}", word, distance, text);
+                    assert_eq!(damerau.accept(&text), edit_distance(word, &text, true) <= distance, "{} {} {}", word, distance, text);
+                    assert_eq!(minimal.accept(&text), damerau.accept(&text));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn dictionary_suggestions() {
+        let words : [&str; 6] = ["abc", "acb", "bca", "cab", "aabb", "c"];
+        let dictionary : DeterministicFiniteAutomaton = DeterministicFiniteAutomaton::from_words(&words);
+        for word in ["abc", "ba", "cc", "abcb"] {
+            for (distance, transpositions) in [(1, false), (1, true), (2, false)] {
+                let mut expected : Vec<String> = words.iter()
+                    .filter(|candidate| edit_distance(word, candidate, transpositions) <= distance)
+                    .map(|candidate| String::from(*candidate))
+                    .collect();
+                expected.sort();
+                assert_eq!(dictionary.suggestions(word, distance, transpositions), expected, "{} {}", word, distance);
+            }
+        }
+    }
+}
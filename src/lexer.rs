@@ -0,0 +1,230 @@
+use super::{Transition, State, Symbol, DeterministicFiniteAutomaton, NonDeterministicFiniteAutomaton};
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::ops::Range;
+
+/// Analyseur lexical: un automate déterministe dont chaque état final porte le type de lexème reconnu
+#[derive(Debug, Clone)]
+pub struct Lexer {
+    dfa: DeterministicFiniteAutomaton,
+    kinds: Vec<String>,
+    accepting: HashMap<State, usize>,
+    live: HashSet<State>,
+}
+
+/// Lexème reconnu par un `Lexer`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Token {
+    kind: String,
+    span: Range<usize>,
+    text: String,
+}
+
+impl Token {
+    /// Retourne le type du lexème, c'est à dire le nom de la règle qui l'a reconnu
+    pub fn get_kind(&self) -> &str {
+        &self.kind
+    }
+
+    /// Retourne la position (en octets) du lexème dans le texte analysé
+    pub fn get_span(&self) -> Range<usize> {
+        self.span.clone()
+    }
+
+    /// Retourne le texte du lexème
+    pub fn get_text(&self) -> &str {
+        &self.text
+    }
+}
+
+impl Lexer {
+    /// Créer un analyseur lexical depuis une liste ordonnée de règles
+    ///
+    /// Les automates des règles sont réunis en une seule union disjointe puis déterminisés par la construction des
+    /// sous-ensembles. Un état du résultat porte le type de la première règle, dans l'ordre de la liste,
+    /// dont un état final appartient à l'ensemble qu'il représente: une règle prioritaire l'emporte sur
+    /// les suivantes quand elles reconnaissent le même lexème.
+    ///
+    /// # Arguments
+    ///
+    /// * `rules` - Les couples (type de lexème, automate), par priorité décroissante; un automate déterministe peut être passé via `to_nfa`
+    ///
+    /// # Return
+    ///
+    /// * `Result<Lexer, String>` - L'analyseur, une erreur si la liste est vide ou si une règle accepte le mot vide
+    ///
+    pub fn new(rules : &[(&str, NonDeterministicFiniteAutomaton)]) -> Result<Self, String> {
+        if rules.is_empty() {
+            return Err(String::from("a lexer needs at least one rule"));
+        }
+        if let Some((kind, _)) = rules.iter().find(|(_, nfa)| nfa.accepts_empty_word()) {
+            return Err(format!("rule \"{}\" matches the empty word", kind));
+        }
+        //union disjointe de toutes les règles, avec les états finaux de chacune
+        let automata : Vec<&NonDeterministicFiniteAutomaton> = rules.iter().map(|(_, nfa)| nfa).collect();
+        let (combined, owners) : (NonDeterministicFiniteAutomaton, Vec<HashSet<State>>) = NonDeterministicFiniteAutomaton::disjoint_union(&automata);
+        let (dfa, subsets) : (DeterministicFiniteAutomaton, HashMap<State, BTreeSet<State>>) = combined.determinize_subsets();
+        let accepting : HashMap<State, usize> = subsets.iter()
+            .filter_map(|(state, subset)| {
+                owners.iter().position(|ends| subset.iter().any(|member| ends.contains(member))).map(|rule| (state.clone(), rule))
+            })
+            .collect();
+        let live : HashSet<State> = dfa.distances_to_acceptance().into_keys().collect();
+        Ok(Lexer {
+            dfa,
+            kinds: rules.iter().map(|(kind, _)| String::from(*kind)).collect(),
+            accepting,
+            live,
+        })
+    }
+
+    /// Créer un analyseur lexical depuis une liste ordonnée de règles données par des expressions rationnelles
+    ///
+    /// # Arguments
+    ///
+    /// * `rules` - Les couples (type de lexème, expression rationnelle), par priorité décroissante
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use automate::*;
+    /// fn main() {
+    ///     let lexer : Lexer = Lexer::from_regexes(&[("if", "if"), ("ident", "[a-z]+"), ("number", "[0-9]+"), ("space", " +")]).unwrap();
+    ///     let tokens : Vec<Token> = lexer.tokenize("if iffy 42").unwrap();
+    ///     let kinds : Vec<&str> = tokens.iter().map(|token| token.get_kind()).collect();
+    ///     assert_eq!(kinds, vec!["if", "space", "ident", "space", "number"]);
+    ///     assert_eq!(tokens[2].get_text(), "iffy");
+    ///     assert_eq!(tokens[2].get_span(), 3..7);
+    ///     assert_eq!(lexer.tokenize("if 4!2"), Err(4));
+    /// }
+    /// ```
+    ///
+    /// # Return
+    ///
+    /// * `Result<Lexer, String>` - L'analyseur, une erreur si une expression est invalide ou si une règle accepte le mot vide
+    ///
+    pub fn from_regexes(rules : &[(&str, &str)]) -> Result<Self, String> {
+        let mut automata : Vec<(&str, NonDeterministicFiniteAutomaton)> = Vec::new();
+        for (kind, pattern) in rules {
+            let nfa : NonDeterministicFiniteAutomaton = NonDeterministicFiniteAutomaton::from_regex(pattern)
+                .map_err(|error| format!("rule \"{}\": {}", kind, error))?;
+            automata.push((kind, nfa));
+        }
+        Lexer::new(&automata)
+    }
+
+    /// Retourne l'automate déterministe de l'analyseur
+    pub fn get_dfa(&self) -> &DeterministicFiniteAutomaton {
+        &self.dfa
+    }
+
+    /// Retourne les types de lexèmes, par priorité décroissante
+    pub fn get_kinds(&self) -> &Vec<String> {
+        &self.kinds
+    }
+
+    /// Retourne le type de lexème reconnu dans l'état `state`, `None` si l'état n'est pas final
+    pub fn get_kind(&self, state : &State) -> Option<&str> {
+        self.accepting.get(state).map(|rule| self.kinds[*rule].as_str())
+    }
+
+    /// Retourne le plus long lexème commençant à la position `start` de `input`
+    fn longest_token(&self, input : &str, start : usize) -> Option<Token> {
+        let mut state : &State = self.dfa.get_start();
+        let mut best : Option<(usize, usize)> = None;
+        for (offset, letter) in input[start..].char_indices() {
+            match self.dfa.apply_delta(Transition::new(Symbol::new(String::from(letter)), state.clone())) {
+                Some(image) if self.live.contains(image) => state = image,
+                _ => break,
+            }
+            if let Some(rule) = self.accepting.get(state) {
+                best = Some((start + offset + letter.len_utf8(), *rule));
+            }
+        }
+        best.map(|(end, rule)| Token { kind: self.kinds[rule].clone(), span: start..end, text: String::from(&input[start..end]) })
+    }
+
+    /// Découpe `input` en lexèmes, en reconnaissant à chaque position le plus long lexème possible
+    ///
+    /// L'itérateur retourne `Err(position)` quand aucun lexème ne commence à la position (en octets) `position`,
+    /// puis reprend l'analyse au caractère suivant.
+    pub fn tokens<'a>(&'a self, input : &'a str) -> Tokens<'a> {
+        Tokens { lexer: self, input, position: 0 }
+    }
+
+    /// Découpe entièrement `input` en lexèmes
+    ///
+    /// # Return
+    ///
+    /// * `Result<Vec<Token>, usize>` - Les lexèmes, ou la position (en octets) du premier caractère où aucun lexème ne commence
+    ///
+    pub fn tokenize(&self, input : &str) -> Result<Vec<Token>, usize> {
+        self.tokens(input).collect()
+    }
+}
+
+/// Itérateur sur les lexèmes d'un texte, voir `Lexer::tokens`
+#[derive(Debug, Clone)]
+pub struct Tokens<'a> {
+    lexer: &'a Lexer,
+    input: &'a str,
+    position: usize,
+}
+
+impl Iterator for Tokens<'_> {
+    type Item = Result<Token, usize>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let letter : char = self.input[self.position..].chars().next()?;
+        match self.lexer.longest_token(self.input, self.position) {
+            Some(token) => {
+                self.position = token.span.end;
+                Some(Ok(token))
+            },
+            None => {
+                let error : usize = self.position;
+                self.position += letter.len_utf8();
+                Some(Err(error))
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use super::super::test_words::enumerate_words;
+
+    #[test]
+    fn priorities_and_maximal_munch() {
+        let lexer : Lexer = Lexer::from_regexes(&[("else", "else"), ("elif", "elif"), ("ident", "[a-z]+"), ("number", "[0-9]+(\\.[0-9]+)?"), ("dot", "\\."), ("space", "[ ]+")]).unwrap();
+        let tokens : Vec<(String, String)> = lexer.tokenize("else elsewhere 3.14.5 elif").unwrap().iter()
+            .map(|token| (String::from(token.get_kind()), String::from(token.get_text())))
+            .collect();
+        let expected : Vec<(&str, &str)> = vec![("else", "else"), ("space", " "), ("ident", "elsewhere"), ("space", " "), ("number", "3.14"), ("dot", "."), ("number", "5"), ("space", " "), ("elif", "elif")];
+        assert_eq!(tokens, expected.iter().map(|(kind, text)| (String::from(*kind), String::from(*text))).collect::<Vec<(String, String)>>());
+
+        //le lexème le plus long l'emporte, même s'il faut revenir en arrière après un préfixe sans issue
+        let backtrack : Lexer = Lexer::from_regexes(&[("a", "a"), ("abc", "abc")]).unwrap();
+        let spans : Vec<Result<Range<usize>, usize>> = backtrack.tokens("abababc").map(|token| token.map(|token| token.get_span())).collect();
+        assert_eq!(spans, vec![Ok(0..1), Err(1), Ok(2..3), Err(3), Ok(4..7)]);
+    }
+
+    #[test]
+    fn lexer_matches_rules() {
+        let rules : [(&str, &str); 3] = [("ab", "ab"), ("a_b", "a+b*"), ("b", "b")];
+        let lexer : Lexer = Lexer::from_regexes(&rules).unwrap();
+        let automata : Vec<DeterministicFiniteAutomaton> = rules.iter().map(|(_, pattern)| DeterministicFiniteAutomaton::from_regex(pattern).unwrap()).collect();
+        for word in enumerate_words("ab", 5) {
+            //chaque état atteint porte la première règle qui accepte le mot lu
+            let expected : Option<&str> = automata.iter().position(|dfa| dfa.accept(&word)).map(|rule| rules[rule].0);
+            let mut state : State = lexer.get_dfa().get_start().clone();
+            for letter in word.chars() {
+                state = lexer.get_dfa().apply_delta(Transition::new(Symbol::new(String::from(letter)), state)).unwrap().clone();
+            }
+            assert_eq!(lexer.get_kind(&state), expected, "{}", word);
+        }
+        assert!(Lexer::from_regexes(&[("maybe", "a?")]).is_err());
+        assert!(Lexer::from_regexes(&[("broken", "(a")]).is_err());
+        assert!(Lexer::new(&[]).is_err());
+    }
+}
@@ -1,4 +1,9 @@
-use std::collections::{ HashSet};
+//les exemples json des commentaires de documentation sont indentés par des tabulations
+#![allow(clippy::tabs_in_doc_comments)]
+//les tests comparent des booléens avec assert_eq!
+#![cfg_attr(test, allow(clippy::bool_assert_comparison))]
+use std::collections::{ HashSet, HashMap};
+use std::collections::BTreeSet;
 mod state;
 pub use state::State;
 mod symbol;
@@ -11,6 +16,54 @@ mod dfa;
 pub use dfa::DeterministicFiniteAutomaton;
 mod nfa;
 pub use nfa::NonDeterministicFiniteAutomaton;
+mod minimization;
+pub use minimization::MinimizationStrategy;
+mod quotient;
+mod homomorphism;
+mod search;
+pub use search::{Match, MatchKind};
+mod aho_corasick;
+pub use aho_corasick::{AhoCorasick, KeywordMatchKind};
+mod dictionary;
+mod levenshtein;
+mod regex;
+mod lexer;
+pub use lexer::{Lexer, Token, Tokens};
+mod mealy;
+pub use mealy::MealyMachine;
+mod moore;
+pub use moore::MooreMachine;
+mod fst;
+pub use fst::{FST, FstArc};
+mod pda;
+pub use pda::{PushdownAutomaton, PdaArc, PdaConfiguration, AcceptanceMode};
+mod grammar;
+pub use grammar::{Grammar, GrammarSymbol, Production};
+mod linear_grammar;
+mod turing;
+pub use turing::{TuringMachine, TuringConfiguration, TuringAction, Move};
+mod buchi;
+pub use buchi::BuchiAutomaton;
+mod hoa;
+mod semiring;
+pub use semiring::{Semiring, Selective, Tropical, Probability, Boolean, Counting};
+mod weighted;
+pub use weighted::{WeightedAutomaton, WeightedPath};
+mod rng;
+pub use rng::Rng;
+mod probabilistic;
+pub use probabilistic::{ProbabilisticAutomaton, ProbabilisticArc};
+mod charset;
+pub use charset::CharSet;
+mod symbolic;
+pub use symbolic::{SymbolicAutomaton, SymbolicArc};
+mod formula;
+pub use formula::BooleanFormula;
+mod alternating;
+pub use alternating::AlternatingAutomaton;
+mod two_way;
+pub use two_way::{TwoWayAutomaton, TwoWayAction, TwoWayOutcome, LEFT_ENDMARKER, RIGHT_ENDMARKER};
+mod paths;
 
 
 /// insert tout les elements de apres dans avant
@@ -18,5 +71,77 @@ fn insert_all(mut old: HashSet<State>, new: HashSet<State>)-> HashSet<State>{
     for state in new{
         old.insert(state);
     }
-    return old;
+    old
+}
+
+/// Retourne un état nommé d'après `base` qui n'appartient pas à `states`
+pub(crate) fn fresh_state(states: &HashSet<State>, base: &str) -> State {
+    let mut name: String = String::from(base);
+    while states.contains(&State::new(name.clone())) {
+        name.push('_');
+    }
+    State::new(name)
+}
+
+/// Convertit un mot en suite de symboles d'un caractère
+pub(crate) fn to_symbols(word: &str) -> Vec<Symbol> {
+    word.chars().map(|letter| Symbol::new(String::from(letter))).collect()
+}
+
+/// Nomme des ensembles d'états d'après leurs éléments, par exemple `{q_0,q_1}`
+///
+/// Deux ensembles distincts reçoivent deux noms distincts, même quand leurs éléments s'écrivent pareil: `{a,b}` et
+/// `{"a,b"}` sont nommés `{a,b}` et `{a,b}_`.
+#[derive(Default)]
+pub(crate) struct SetNames {
+    names: HashMap<BTreeSet<State>, State>,
+    taken: HashSet<State>,
+}
+
+impl SetNames {
+    /// Retourne le nom de `states`, créé par `fresh_state` la première fois
+    pub(crate) fn name(&mut self, states: &BTreeSet<State>) -> State {
+        if let Some(name) = self.names.get(states) {
+            return name.clone();
+        }
+        let names: Vec<&str> = states.iter().map(|state| state.get_name().as_str()).collect();
+        let name: State = fresh_state(&self.taken, &format!("{{{}}}", names.join(",")));
+        self.taken.insert(name.clone());
+        self.names.insert(states.clone(), name.clone());
+        name
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod test_words {
+    use super::Symbol;
+
+    /// Concatene les symboles d'un mot
+    pub(crate) fn word_to_string(word : &[Symbol]) -> String {
+        word.iter().map(|symbol| symbol.get_value().clone()).collect()
+    }
+
+    /// Enumere tous les mots de longueur au plus `max_length` sur l'alphabet `letters`
+    pub(crate) fn enumerate_words(letters : &str, max_length : usize) -> Vec<String> {
+        let mut words : Vec<String> = vec![String::new()];
+        let mut last : Vec<String> = vec![String::new()];
+        for _ in 0..max_length {
+            last = last.iter().flat_map(|word| letters.chars().map(move |letter| format!("{}{}", word, letter))).collect();
+            words.extend(last.clone());
+        }
+        words
+    }
+
+    /// Indique si un mot sur `()[]` est bien parenthésé
+    pub(crate) fn balanced(word : &str) -> bool {
+        let mut stack : Vec<char> = Vec::new();
+        for letter in word.chars() {
+            match letter {
+                '(' | '[' => stack.push(letter),
+                ')' => if stack.pop() != Some('(') { return false; },
+                _ => if stack.pop() != Some('[') { return false; },
+            }
+        }
+        stack.is_empty()
+    }
 }
@@ -0,0 +1,256 @@
+use super::{State, Symbol, Grammar, GrammarSymbol, Production, DeterministicFiniteAutomaton, NonDeterministicFiniteAutomaton, fresh_state};
+use std::collections::HashSet;
+
+/// Sépare le corps d'une règle linéaire en ses terminaux et son éventuel non-terminal, placé à droite si `right` et à gauche sinon
+///
+/// Retourne `None` si le corps contient plusieurs non-terminaux ou un non-terminal mal placé.
+fn split_linear(body : &[GrammarSymbol], right : bool) -> Option<(Vec<Symbol>, Option<State>)> {
+    let (nonterminal, terminals) : (Option<&GrammarSymbol>, &[GrammarSymbol]) = match (right, body) {
+        (true, [terminals @ .., last @ GrammarSymbol::Nonterminal(_)]) => (Some(last), terminals),
+        (false, [first @ GrammarSymbol::Nonterminal(_), terminals @ ..]) => (Some(first), terminals),
+        _ => (None, body),
+    };
+    let terminals : Vec<Symbol> = terminals.iter()
+        .map(|symbol| match symbol {
+            GrammarSymbol::Terminal(terminal) => Some(terminal.clone()),
+            GrammarSymbol::Nonterminal(_) => None,
+        })
+        .collect::<Option<Vec<Symbol>>>()?;
+    let nonterminal : Option<State> = nonterminal.map(|symbol| match symbol {
+        GrammarSymbol::Nonterminal(nonterminal) => nonterminal.clone(),
+        GrammarSymbol::Terminal(_) => unreachable!(),
+    });
+    Some((terminals, nonterminal))
+}
+
+impl Grammar {
+    /// Indique si la grammaire est linéaire à droite: chaque règle est de la forme `A -> w B` ou `A -> w`, `w` étant un mot de terminaux
+    pub fn is_right_linear(&self) -> bool {
+        self.get_productions().iter().all(|(_, body)| split_linear(body, true).is_some())
+    }
+
+    /// Indique si la grammaire est linéaire à gauche: chaque règle est de la forme `A -> B w` ou `A -> w`, `w` étant un mot de terminaux
+    pub fn is_left_linear(&self) -> bool {
+        self.get_productions().iter().all(|(_, body)| split_linear(body, false).is_some())
+    }
+
+    /// Convertit une grammaire linéaire à droite ou à gauche en automate non déterministe
+    ///
+    /// Pour une grammaire linéaire à droite, les états sont les non-terminaux et un nouvel état final: la règle
+    /// `A -> w B` mène de `A` à `B` en lisant `w`, et la règle `A -> w` mène de `A` à l'état final. Pour une
+    /// grammaire linéaire à gauche, les règles se lisent à l'envers depuis un nouvel état initial: `A -> B w`
+    /// mène de `B` à `A` et `A -> w` mène de l'état initial à `A`, l'axiome étant final. Les mots de plusieurs
+    /// terminaux passent par des états intermédiaires, puis les epsilon-transitions sont supprimées.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use automate::*;
+    /// fn main() {
+    ///     let right : Grammar = Grammar::from_text("S -> aS | bA\nA -> bA | ε").unwrap();
+    ///     let left : Grammar = Grammar::from_text("S -> Sb | Ab\nA -> Aa | ε").unwrap();
+    ///     for grammar in [right, left] {
+    ///         let nfa : NonDeterministicFiniteAutomaton = grammar.to_nfa().unwrap();
+    ///         assert!(nfa.accept("aabbb"));
+    ///         assert!(!nfa.accept("aaba"));
+    ///     }
+    ///     assert!(Grammar::from_text("S -> aSb | ε").unwrap().to_nfa().is_err());
+    /// }
+    /// ```
+    ///
+    /// # Return
+    ///
+    /// * `Result<NonDeterministicFiniteAutomaton, String>` - L'automate reconnaissant le langage de la grammaire, une erreur si elle n'est pas linéaire
+    ///
+    pub fn to_nfa(&self) -> Result<NonDeterministicFiniteAutomaton, String> {
+        let right : bool = self.is_right_linear();
+        if !right && !self.is_left_linear() {
+            return Err(String::from("grammar is neither right-linear nor left-linear"));
+        }
+        let mut states : HashSet<State> = self.get_nonterminals();
+        let extra : State = fresh_state(&states, if right { "end" } else { "start" });
+        states.insert(extra.clone());
+        let mut transitions : Vec<(State, Option<Symbol>, State)> = Vec::new();
+        for (left, body) in self.get_productions() {
+            let (terminals, nonterminal) : (Vec<Symbol>, Option<State>) = split_linear(body, right).unwrap();
+            let (from, to) : (State, State) = match (right, nonterminal) {
+                (true, Some(nonterminal)) => (left.clone(), nonterminal),
+                (true, None) => (left.clone(), extra.clone()),
+                (false, Some(nonterminal)) => (nonterminal, left.clone()),
+                (false, None) => (extra.clone(), left.clone()),
+            };
+            if terminals.is_empty() {
+                transitions.push((from, None, to));
+                continue;
+            }
+            let mut current : State = from;
+            for (index, terminal) in terminals.iter().enumerate() {
+                let next : State = if index + 1 == terminals.len() {
+                    to.clone()
+                } else {
+                    let state : State = fresh_state(&states, &format!("{}_{}", current.get_name(), terminal.get_value()));
+                    states.insert(state.clone());
+                    state
+                };
+                transitions.push((current, Some(terminal.clone()), next.clone()));
+                current = next;
+            }
+        }
+        let (starts, ends) : (HashSet<State>, HashSet<State>) = if right {
+            (HashSet::from([self.get_start().clone()]), HashSet::from([extra]))
+        } else {
+            (HashSet::from([extra]), HashSet::from([self.get_start().clone()]))
+        };
+        Ok(NonDeterministicFiniteAutomaton::from_epsilon_transitions(starts, &transitions, &ends, self.get_terminals()))
+    }
+}
+
+impl NonDeterministicFiniteAutomaton {
+    /// Créer un automate non déterministe depuis la description textuelle d'une grammaire linéaire à droite ou à gauche
+    ///
+    /// Voir `Grammar::from_text` pour le format et `Grammar::to_nfa` pour la conversion.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use automate::*;
+    /// fn main() {
+    ///     let nfa : NonDeterministicFiniteAutomaton = NonDeterministicFiniteAutomaton::from_linear_grammar("S -> ab S | c").unwrap();
+    ///     assert!(nfa.accept("ababc"));
+    ///     assert!(!nfa.accept("abab"));
+    /// }
+    /// ```
+    ///
+    pub fn from_linear_grammar(text : &str) -> Result<NonDeterministicFiniteAutomaton, String> {
+        Grammar::from_text(text)?.to_nfa()
+    }
+
+    /// Convertit l'automate en grammaire linéaire à droite dont les non-terminaux sont les états
+    ///
+    /// La transition de `p` vers `q` par `a` devient la règle `p -> a q` et chaque état final `p` reçoit la règle
+    /// `p -> ε`. Si l'automate a plusieurs états initiaux, un nouvel axiome `S` se réécrit en chacun d'eux.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use automate::*;
+    /// fn main() {
+    ///     let nfa : NonDeterministicFiniteAutomaton = NonDeterministicFiniteAutomaton::from_json_file("src/automates/NFA1.json");
+    ///     let grammar : Grammar = nfa.to_right_linear_grammar();
+    ///     assert!(grammar.is_right_linear());
+    ///     let back : NonDeterministicFiniteAutomaton = grammar.to_nfa().unwrap();
+    ///     for word in ["", "a", "ab", "aabbb", "aabb"] {
+    ///         assert_eq!(back.accept(word), nfa.accept(word));
+    ///     }
+    /// }
+    /// ```
+    ///
+    /// # Return
+    ///
+    /// * `Grammar` - La grammaire linéaire à droite engendrant le langage de l'automate
+    ///
+    pub fn to_right_linear_grammar(&self) -> Grammar {
+        let mut starts : Vec<&State> = self.get_starts().iter().collect();
+        starts.sort();
+        let start : State = match starts.as_slice() {
+            [start] => (*start).clone(),
+            _ => fresh_state(self.get_states(), "S"),
+        };
+        let mut productions : Vec<Production> = Vec::new();
+        if starts.len() != 1 {
+            for state in &starts {
+                productions.push((start.clone(), vec![GrammarSymbol::Nonterminal((*state).clone())]));
+            }
+        }
+        let mut arcs : Vec<(&State, &Symbol, &State)> = self.get_delta().iter()
+            .flat_map(|(transition, images)| images.iter().map(move |image| (transition.get_content(), transition.get_symbol(), image)))
+            .collect();
+        arcs.sort();
+        for (state, symbol, image) in arcs {
+            productions.push((state.clone(), vec![GrammarSymbol::Terminal(symbol.clone()), GrammarSymbol::Nonterminal(image.clone())]));
+        }
+        let mut ends : Vec<&State> = self.get_ends().iter().collect();
+        ends.sort();
+        for state in ends {
+            productions.push((state.clone(), Vec::new()));
+        }
+        Grammar::new(start, productions)
+    }
+}
+
+impl DeterministicFiniteAutomaton {
+    /// Convertit l'automate en grammaire linéaire à droite dont les non-terminaux sont les états, voir `NonDeterministicFiniteAutomaton::to_right_linear_grammar`
+    pub fn to_right_linear_grammar(&self) -> Grammar {
+        self.to_nfa().to_right_linear_grammar()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use super::super::test_words::enumerate_words;
+    use super::super::{Transition, FiniteStateMachine};
+    use std::collections::HashMap;
+
+    #[test]
+    fn linear_grammars() {
+        let right : Grammar = Grammar::from_text("S -> a b S | b A\nA -> b A | c | ε").unwrap();
+        let left : Grammar = Grammar::from_text("S -> A | S b\nA -> a a | A c").unwrap();
+        let mixed : Grammar = Grammar::from_text("S -> a A | B b\nA -> a\nB -> b").unwrap();
+        assert!(right.is_right_linear() && !right.is_left_linear());
+        assert!(left.is_left_linear() && !left.is_right_linear());
+        assert!(!mixed.is_right_linear() && !mixed.is_left_linear());
+        assert_eq!(mixed.to_nfa().unwrap_err(), "grammar is neither right-linear nor left-linear");
+        let right_nfa : NonDeterministicFiniteAutomaton = right.to_nfa().unwrap();
+        let left_nfa : NonDeterministicFiniteAutomaton = left.to_nfa().unwrap();
+        for word in enumerate_words("abc", 7) {
+            //(ab)* b+ (c|ε) et aa c* b*
+            let body : &str = word.trim_start_matches("ab");
+            let tail : &str = body.strip_suffix('c').unwrap_or(body);
+            assert_eq!(right_nfa.accept(&word), !tail.is_empty() && tail.chars().all(|letter| letter == 'b'), "{}", word);
+            let rest : Option<&str> = word.strip_prefix("aa").map(|rest| rest.trim_start_matches('c').trim_start_matches('b'));
+            assert_eq!(left_nfa.accept(&word), rest == Some(""), "{}", word);
+        }
+    }
+
+    #[test]
+    fn automaton_round_trip() {
+        let dfa : DeterministicFiniteAutomaton = DeterministicFiniteAutomaton::from_json_file("src/automates/DFA1.json");
+        let grammar : Grammar = dfa.to_right_linear_grammar();
+        assert!(grammar.is_right_linear());
+        assert_eq!(grammar.get_start(), dfa.get_start());
+        let back : NonDeterministicFiniteAutomaton = NonDeterministicFiniteAutomaton::from_linear_grammar(&grammar.to_text().unwrap()).unwrap();
+        let letters : String = dfa.get_alphabet().iter().map(|symbol| symbol.get_value().as_str()).collect();
+        for word in enumerate_words(&letters, 6) {
+            assert_eq!(back.accept(&word), dfa.accept(&word), "{}", word);
+        }
+        //plusieurs états initiaux: un nouvel axiome
+        let union : NonDeterministicFiniteAutomaton = grammar.to_nfa().unwrap().union_nfa(&Grammar::from_text("S -> c S | ε").unwrap().to_nfa().unwrap());
+        let union_grammar : Grammar = union.to_right_linear_grammar();
+        assert_eq!(union_grammar.get_start(), &State::from_str("S"));
+        let union_back : NonDeterministicFiniteAutomaton = union_grammar.to_nfa().unwrap();
+        for word in enumerate_words(&format!("{}c", letters), 5) {
+            assert_eq!(union_back.accept(&word), union.accept(&word), "{}", word);
+        }
+        //des états qui portent le nom d'une lettre, puis une lettre de plusieurs caractères
+        let arcs = |arcs : &[(&str, &str, &str)]| arcs.iter()
+            .map(|(state, symbol, image)| (Transition::new(Symbol::from_str(symbol), State::from_str(state)), State::from_str(image)))
+            .collect::<HashMap<Transition<State>, State>>();
+        let letters_dfa : DeterministicFiniteAutomaton = DeterministicFiniteAutomaton::new(State::from_str("a"),
+            arcs(&[("a", "a", "b"), ("a", "b", "a"), ("b", "a", "a"), ("b", "b", "b")]),
+            FiniteStateMachine::new(HashSet::from([State::from_str("a"), State::from_str("b")]), HashSet::from([Symbol::from_str("a"), Symbol::from_str("b")]), HashSet::from([State::from_str("b")])));
+        let letters_grammar : Grammar = letters_dfa.to_right_linear_grammar();
+        assert_eq!(Grammar::from_text(&letters_grammar.to_text().unwrap()).unwrap(), letters_grammar);
+        let letters_back : NonDeterministicFiniteAutomaton = NonDeterministicFiniteAutomaton::from_linear_grammar(&letters_grammar.to_text().unwrap()).unwrap();
+        for word in enumerate_words("ab", 6) {
+            assert_eq!(letters_back.accept(&word), letters_dfa.accept(&word), "{}", word);
+        }
+        let long_dfa : DeterministicFiniteAutomaton = DeterministicFiniteAutomaton::new(State::from_str("p"),
+            arcs(&[("p", "ab", "q"), ("q", "a", "p")]),
+            FiniteStateMachine::new(HashSet::from([State::from_str("p"), State::from_str("q")]), HashSet::from([Symbol::from_str("ab"), Symbol::from_str("a")]), HashSet::from([State::from_str("q")])));
+        let long_grammar : Grammar = long_dfa.to_right_linear_grammar();
+        let long_back : NonDeterministicFiniteAutomaton = NonDeterministicFiniteAutomaton::from_linear_grammar(&long_grammar.to_text().unwrap()).unwrap();
+        assert_eq!(long_back.get_alphabet(), long_dfa.get_alphabet());
+        assert_eq!(Grammar::from_text(&long_grammar.to_text().unwrap()).unwrap(), long_grammar);
+    }
+}
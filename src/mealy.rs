@@ -0,0 +1,393 @@
+use super::{Transition, State, Symbol, MooreMachine, SetNames, to_symbols};
+use std::collections::{BTreeSet, HashMap, HashSet, VecDeque};
+use std::fs;
+use serde_json::{Value, from_str, map::Map};
+
+/// Signature d'un état pendant la minimisation: sa classe, puis la sortie et la classe de l'image pour chaque symbole d'entrée
+type Signature<'a> = (usize, Vec<Option<(&'a Symbol, usize)>>);
+
+/// Machine de Mealy: un automate déterministe dont chaque transition produit un symbole de sortie
+#[derive(Debug, Clone)]
+pub struct MealyMachine {
+    start: State,
+    delta: HashMap<Transition<State>, State>,
+    lambda: HashMap<Transition<State>, Symbol>,
+    states: HashSet<State>,
+    inputs: HashSet<Symbol>,
+    outputs: HashSet<Symbol>,
+}
+
+impl MealyMachine {
+    /// Créer une machine de Mealy
+    ///
+    /// # Arguments
+    ///
+    /// * `_start` - L'état initial de la machine
+    /// * `_delta` - Une HashMap decrivant les transitions de la machine
+    /// * `_lambda` - Une HashMap associant à chaque transition son symbole de sortie
+    /// * `_states` - Les états de la machine
+    /// * `_inputs` - L'alphabet d'entrée
+    /// * `_outputs` - L'alphabet de sortie
+    ///
+    /// # Return
+    ///
+    /// * `MealyMachine` - La machine de Mealy correspondante
+    ///
+    pub fn new(_start : State, _delta : HashMap<Transition<State>, State>, _lambda : HashMap<Transition<State>, Symbol>, _states : HashSet<State>, _inputs : HashSet<Symbol>, _outputs : HashSet<Symbol>) -> Self {
+        MealyMachine {
+            start: _start,
+            delta: _delta,
+            lambda: _lambda,
+            states: _states,
+            inputs: _inputs,
+            outputs: _outputs,
+        }
+    }
+
+    /// Créer une machine de Mealy depuis un contenu json
+    ///
+    /// # Examples
+    ///
+    /// Le contenu du json: chaque transition porte en plus son symbole de sortie
+    ///
+    /// ```json
+    /// {
+    ///     "states" : ["q_0","q_1"],
+    ///     "input_alphabet" : ["a","b"],
+    ///     "output_alphabet" : ["0","1"],
+    ///     "start" : "q_0",
+    ///     "delta" : [
+    ///         {
+    ///             "state" : "q_0",
+    ///             "symbol" : "a",
+    ///             "image" : "q_1",
+    ///             "output" : "1"
+    ///         }
+    ///     ]
+    /// }
+    /// ```
+    ///
+    /// # Return
+    ///
+    /// * `MealyMachine` - La machine de Mealy correspondante
+    ///
+    pub fn from_json(content_json: &Map<String, Value>) -> Self {
+        let name = |value : &Value| String::from(value.as_str().unwrap());
+        let start : State = State::new(name(content_json.get("start").unwrap()));
+        let mut states : HashSet<State> = HashSet::from([start.clone()]);
+        let mut inputs : HashSet<Symbol> = HashSet::new();
+        let mut outputs : HashSet<Symbol> = HashSet::new();
+        for state in content_json.get("states").unwrap().as_array().unwrap() {
+            states.insert(State::new(name(state)));
+        }
+        for symbol in content_json.get("input_alphabet").unwrap().as_array().unwrap() {
+            inputs.insert(Symbol::new(name(symbol)));
+        }
+        for symbol in content_json.get("output_alphabet").unwrap().as_array().unwrap() {
+            outputs.insert(Symbol::new(name(symbol)));
+        }
+        let mut delta : HashMap<Transition<State>, State> = HashMap::new();
+        let mut lambda : HashMap<Transition<State>, Symbol> = HashMap::new();
+        for element_delta in content_json.get("delta").unwrap().as_array().unwrap() {
+            let transition_json : &Map<String, Value> = element_delta.as_object().unwrap();
+            let symbol : Symbol = Symbol::new(name(transition_json.get("symbol").unwrap()));
+            let state : State = State::new(name(transition_json.get("state").unwrap()));
+            let image : State = State::new(name(transition_json.get("image").unwrap()));
+            let output : Symbol = Symbol::new(name(transition_json.get("output").unwrap()));
+            let transition : Transition<State> = Transition::new(symbol.clone(), state.clone());
+            delta.insert(transition.clone(), image.clone());
+            lambda.insert(transition, output.clone());
+            states.insert(state);
+            states.insert(image);
+            inputs.insert(symbol);
+            outputs.insert(output);
+        }
+        MealyMachine::new(start, delta, lambda, states, inputs, outputs)
+    }
+
+    /// Créer une machine de Mealy depuis un chemin vers un fichier json, voir `from_json`
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use automate::*;
+    /// fn main() {
+    ///     let mealy : MealyMachine = MealyMachine::from_json_file("src/automates/MEALY1.json");
+    ///     assert_eq!(mealy.get_start(), &State::from_str("init"));
+    /// }
+    /// ```
+    ///
+    pub fn from_json_file(path: &str) -> Self {
+        let content_json: Value = {
+            // Charge le contenu du fichier en tant que String
+            let content : String = fs::read_to_string(path).unwrap();
+            // Parse le texte en structure Json
+            from_str::<Value>(&content).unwrap()
+        };
+        MealyMachine::from_json(content_json.as_object().unwrap())
+    }
+
+    /// Retourne l'état initial de la machine
+    pub fn get_start(&self) -> &State {
+        &self.start
+    }
+
+    /// Retourne les transitions de la machine
+    pub fn get_delta(&self) -> &HashMap<Transition<State>, State> {
+        &self.delta
+    }
+
+    /// Retourne les symboles de sortie des transitions
+    pub fn get_lambda(&self) -> &HashMap<Transition<State>, Symbol> {
+        &self.lambda
+    }
+
+    /// Retourne les états de la machine
+    pub fn get_states(&self) -> &HashSet<State> {
+        &self.states
+    }
+
+    /// Retourne l'alphabet d'entrée
+    pub fn get_inputs(&self) -> &HashSet<Symbol> {
+        &self.inputs
+    }
+
+    /// Retourne l'alphabet de sortie
+    pub fn get_outputs(&self) -> &HashSet<Symbol> {
+        &self.outputs
+    }
+
+    /// Applique une transition, retourne l'état atteint et le symbole produit
+    pub fn apply_delta(&self, transition : Transition<State>) -> Option<(&State, &Symbol)> {
+        Some((self.delta.get(&transition)?, self.lambda.get(&transition)?))
+    }
+
+    /// Traduit une suite de symboles d'entrée, voir `translate`
+    pub fn translate_symbols(&self, input : &[Symbol]) -> Option<Vec<Symbol>> {
+        let mut state : &State = self.get_start();
+        let mut output : Vec<Symbol> = Vec::new();
+        for symbol in input {
+            let (image, produced) : (&State, &Symbol) = self.apply_delta(Transition::new(symbol.clone(), state.clone()))?;
+            output.push(produced.clone());
+            state = image;
+        }
+        Some(output)
+    }
+
+    /// Traduit un mot d'entrée, lu caractère par caractère, en la suite des symboles produits par les transitions
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use automate::*;
+    /// fn main() {
+    ///     //la machine produit 1 quand l'entrée est égale à la précédente
+    ///     let mealy : MealyMachine = MealyMachine::from_json_file("src/automates/MEALY1.json");
+    ///     let output : Vec<Symbol> = mealy.translate("00110").unwrap();
+    ///     assert_eq!(output, ["0", "1", "0", "1", "0"].map(Symbol::from_str));
+    ///     assert_eq!(mealy.translate("02"), None);
+    /// }
+    /// ```
+    ///
+    /// # Return
+    ///
+    /// * `Option<Vec<Symbol>>` - Un symbole par symbole d'entrée, `None` si une transition n'est pas définie
+    ///
+    pub fn translate(&self, input : &str) -> Option<Vec<Symbol>> {
+        let input : Vec<Symbol> = to_symbols(input);
+        self.translate_symbols(&input)
+    }
+
+    /// Convertit la machine en machine de Moore
+    ///
+    /// Chaque état `(q,o)` de la machine de Moore représente l'état `q` atteint en produisant `o`, et produit `o`.
+    /// L'état initial, qui n'est atteint par aucune transition, produit `initial_output`: la machine de Moore
+    /// produit donc `initial_output` suivi de la traduction de la machine de Mealy.
+    ///
+    /// # Arguments
+    ///
+    /// * `initial_output` - Le symbole produit par l'état initial de la machine de Moore
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use automate::*;
+    /// fn main() {
+    ///     let mealy : MealyMachine = MealyMachine::from_json_file("src/automates/MEALY1.json");
+    ///     let moore : MooreMachine = mealy.to_moore(Symbol::from_str("0"));
+    ///     assert_eq!(moore.translate("00110").unwrap(), ["0", "0", "1", "0", "1", "0"].map(Symbol::from_str));
+    /// }
+    /// ```
+    ///
+    /// # Return
+    ///
+    /// * `MooreMachine` - La machine de Moore équivalente
+    ///
+    pub fn to_moore(&self, initial_output : Symbol) -> MooreMachine {
+        let name = |state : &State, output : &Symbol| State::new(format!("({},{})", state.get_name(), output.get_value()));
+        let start : State = name(self.get_start(), &initial_output);
+        let mut states : HashSet<State> = HashSet::from([start.clone()]);
+        let mut delta : HashMap<Transition<State>, State> = HashMap::new();
+        let mut lambda : HashMap<State, Symbol> = HashMap::from([(start.clone(), initial_output.clone())]);
+        let mut queue : VecDeque<(State, Symbol)> = VecDeque::from([(self.get_start().clone(), initial_output.clone())]);
+        while let Some((state, output)) = queue.pop_front() {
+            let current : State = name(&state, &output);
+            for symbol in self.get_inputs() {
+                if let Some((image, produced)) = self.apply_delta(Transition::new(symbol.clone(), state.clone())) {
+                    let next : State = name(image, produced);
+                    if states.insert(next.clone()) {
+                        lambda.insert(next.clone(), produced.clone());
+                        queue.push_back((image.clone(), produced.clone()));
+                    }
+                    delta.insert(Transition::new(symbol.clone(), current.clone()), next);
+                }
+            }
+        }
+        let mut outputs : HashSet<Symbol> = self.get_outputs().clone();
+        outputs.insert(initial_output);
+        MooreMachine::new(start, delta, lambda, states, self.get_inputs().clone(), outputs)
+    }
+
+    /// Retourne les états accessibles depuis l'état initial
+    fn reachable_states(&self) -> HashSet<State> {
+        let mut reached : HashSet<State> = HashSet::from([self.get_start().clone()]);
+        let mut stack : Vec<State> = vec![self.get_start().clone()];
+        while let Some(state) = stack.pop() {
+            for symbol in self.get_inputs() {
+                if let Some(image) = self.delta.get(&Transition::new(symbol.clone(), state.clone())) {
+                    if reached.insert(image.clone()) {
+                        stack.push(image.clone());
+                    }
+                }
+            }
+        }
+        reached
+    }
+
+    /// Minimise la machine: les états accessibles qui produisent les mêmes traductions sont fusionnés
+    ///
+    /// Les classes sont raffinées à la manière de l'algorithme de Moore, en partant des symboles produits par
+    /// chaque état; une transition absente est traitée comme une sortie particulière. Chaque état du résultat
+    /// est nommé d'après l'ensemble d'états qu'il regroupe.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use automate::*;
+    /// fn main() {
+    ///     let moore : MooreMachine = MooreMachine::from_json_file("src/automates/MOORE1.json");
+    ///     let mealy : MealyMachine = moore.to_mealy();
+    ///     assert_eq!(mealy.minimize().get_states().len(), 2);
+    /// }
+    /// ```
+    ///
+    /// # Return
+    ///
+    /// * `MealyMachine` - La machine minimale équivalente
+    ///
+    pub fn minimize(&self) -> MealyMachine {
+        let mut inputs : Vec<&Symbol> = self.get_inputs().iter().collect();
+        inputs.sort();
+        let mut states : Vec<State> = self.reachable_states().into_iter().collect();
+        states.sort();
+        let mut classes : HashMap<State, usize> = states.iter().map(|state| (state.clone(), 0)).collect();
+        let mut count : usize = 1;
+        loop {
+            let signatures : Vec<Signature> = states.iter()
+                .map(|state| {
+                    let moves : Vec<Option<(&Symbol, usize)>> = inputs.iter()
+                        .map(|symbol| self.apply_delta(Transition::new((*symbol).clone(), state.clone())).map(|(image, output)| (output, classes[image])))
+                        .collect();
+                    (classes[state], moves)
+                })
+                .collect();
+            let mut numbering : HashMap<&Signature, usize> = HashMap::new();
+            let refined : HashMap<State, usize> = states.iter().zip(signatures.iter())
+                .map(|(state, signature)| {
+                    let next : usize = numbering.len();
+                    (state.clone(), *numbering.entry(signature).or_insert(next))
+                })
+                .collect();
+            let refined_count : usize = numbering.len();
+            classes = refined;
+            if refined_count == count {
+                break;
+            }
+            count = refined_count;
+        }
+        let mut blocks : HashMap<usize, BTreeSet<State>> = HashMap::new();
+        for (state, class) in &classes {
+            blocks.entry(*class).or_default().insert(state.clone());
+        }
+        let mut blocks : Vec<(usize, BTreeSet<State>)> = blocks.into_iter().collect();
+        blocks.sort_by(|(_, left), (_, right)| left.cmp(right));
+        let mut block_names : SetNames = SetNames::default();
+        let names : HashMap<usize, State> = blocks.iter().map(|(class, block)| (*class, block_names.name(block))).collect();
+        let mut delta : HashMap<Transition<State>, State> = HashMap::new();
+        let mut lambda : HashMap<Transition<State>, Symbol> = HashMap::new();
+        for (transition, image) in self.get_delta() {
+            if let Some(class) = classes.get(transition.get_content()) {
+                let merged : Transition<State> = Transition::new(transition.get_symbol().clone(), names[class].clone());
+                delta.insert(merged.clone(), names[&classes[image]].clone());
+                lambda.insert(merged, self.lambda[transition].clone());
+            }
+        }
+        MealyMachine::new(
+            names[&classes[self.get_start()]].clone(),
+            delta,
+            lambda,
+            names.values().cloned().collect(),
+            self.get_inputs().clone(),
+            self.get_outputs().clone(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use super::super::test_words::enumerate_words;
+    use serde_json::json;
+
+    #[test]
+    fn translate_and_convert() {
+        let mealy : MealyMachine = MealyMachine::from_json_file("src/automates/MEALY1.json");
+        assert_eq!(mealy.get_states().len(), 3);
+        assert_eq!(mealy.translate(""), Some(Vec::new()));
+        let moore : MooreMachine = mealy.to_moore(Symbol::from_str("-"));
+        let back : MealyMachine = moore.to_mealy();
+        for word in enumerate_words("01", 6) {
+            let output : Vec<Symbol> = mealy.translate(&word).unwrap();
+            let mut expected : Vec<Symbol> = vec![Symbol::from_str("-")];
+            expected.extend(output.iter().cloned());
+            assert_eq!(moore.translate(&word).unwrap(), expected);
+            assert_eq!(back.translate(&word).unwrap(), output);
+        }
+    }
+
+    #[test]
+    fn minimization() {
+        //b et c sont équivalents, d n'est pas accessible
+        let mealy : MealyMachine = MealyMachine::from_json(json!({
+            "states" : ["a", "b", "c", "d"],
+            "input_alphabet" : ["x", "y"],
+            "output_alphabet" : ["0", "1"],
+            "start" : "a",
+            "delta" : [
+                { "state" : "a", "symbol" : "x", "image" : "b", "output" : "0" },
+                { "state" : "a", "symbol" : "y", "image" : "c", "output" : "0" },
+                { "state" : "b", "symbol" : "x", "image" : "c", "output" : "1" },
+                { "state" : "c", "symbol" : "x", "image" : "b", "output" : "1" },
+                { "state" : "d", "symbol" : "x", "image" : "a", "output" : "1" }
+            ]
+        }).as_object().unwrap());
+        let minimal : MealyMachine = mealy.minimize();
+        assert_eq!(minimal.get_states().len(), 2);
+        assert!(minimal.get_states().contains(&State::from_str("{b,c}")));
+        for word in enumerate_words("xy", 5) {
+            assert_eq!(minimal.translate(&word), mealy.translate(&word), "{}", word);
+        }
+        let fixture : MealyMachine = MealyMachine::from_json_file("src/automates/MEALY1.json");
+        assert_eq!(fixture.minimize().get_states().len(), 3);
+    }
+}
@@ -0,0 +1,277 @@
+use super::{Transition, State, Symbol, FiniteStateMachine, DeterministicFiniteAutomaton, NonDeterministicFiniteAutomaton, SetNames};
+use super::paths;
+use std::collections::{BTreeSet, HashMap, HashSet};
+
+/// Algorithme utilisé pour minimiser un automate déterministe
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MinimizationStrategy {
+    /// Raffinement de partition de Hopcroft, en O(n log n)
+    Hopcroft,
+    /// Raffinement de partition de Moore, par itérations successives jusqu'à stabilité
+    Moore,
+    /// Double renversement de Brzozowski: determinize(reverse(determinize(reverse(A))))
+    Brzozowski,
+}
+
+/// Calcule la partition des états en classes d'équivalence
+type PartitionFn = fn(&DeterministicFiniteAutomaton, &[Symbol]) -> Vec<BTreeSet<State>>;
+
+impl DeterministicFiniteAutomaton {
+    /// Minimise l'automate avec l'algorithme de Hopcroft
+    ///
+    /// # Return
+    ///
+    /// * `DeterministicFiniteAutomaton` - L'automate complet et minimal reconnaissant le même langage
+    ///
+    pub fn minimize(&self) -> DeterministicFiniteAutomaton {
+        self.minimize_with(MinimizationStrategy::Hopcroft)
+    }
+
+    /// Minimise l'automate avec l'algorithme choisi
+    ///
+    /// Quelle que soit la stratégie, le résultat est l'automate complet et minimal du langage: les résultats
+    /// des différentes stratégies sont isomorphes, seuls les noms des états diffèrent. Chaque état est nommé
+    /// d'après l'ensemble d'états qu'il regroupe.
+    ///
+    /// # Arguments
+    ///
+    /// * `strategy` - L'algorithme de minimisation à utiliser
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use automate::*;
+    /// fn main() {
+    ///     let dfa : DeterministicFiniteAutomaton = DeterministicFiniteAutomaton::from_json_file("src/automates/DFA2.json");
+    ///     let hopcroft : DeterministicFiniteAutomaton = dfa.minimize_with(MinimizationStrategy::Hopcroft);
+    ///     let moore : DeterministicFiniteAutomaton = dfa.minimize_with(MinimizationStrategy::Moore);
+    ///     assert!(hopcroft.is_isomorphic(&moore).is_some());
+    /// }
+    /// ```
+    ///
+    /// # Return
+    ///
+    /// * `DeterministicFiniteAutomaton` - L'automate complet et minimal reconnaissant le même langage
+    ///
+    pub fn minimize_with(&self, strategy : MinimizationStrategy) -> DeterministicFiniteAutomaton {
+        match strategy {
+            MinimizationStrategy::Hopcroft => self.trim_unreachable().complete().quotient(hopcroft_partition),
+            MinimizationStrategy::Moore => self.trim_unreachable().complete().quotient(moore_partition),
+            MinimizationStrategy::Brzozowski => self.minimize_brzozowski(),
+        }
+    }
+
+    /// Minimise l'automate par double renversement (algorithme de Brzozowski)
+    pub fn minimize_brzozowski(&self) -> DeterministicFiniteAutomaton {
+        self.to_nfa().minimize_brzozowski()
+    }
+
+    /// Retourne une copie de l'automate privée de ses états inaccessibles
+    fn trim_unreachable(&self) -> DeterministicFiniteAutomaton {
+        let reachable : HashSet<State> = paths::reachable(&self.adjacency(), &HashSet::from([self.get_start().clone()]));
+        let delta : HashMap<Transition<State>, State> = self.get_delta().iter()
+            .filter(|(transition, _)| reachable.contains(transition.get_content()))
+            .map(|(transition, image)| (transition.clone(), image.clone()))
+            .collect();
+        let ends : HashSet<State> = self.get_ends().intersection(&reachable).cloned().collect();
+        let fsm : FiniteStateMachine = FiniteStateMachine::new(reachable, self.get_alphabet().clone(), ends);
+        DeterministicFiniteAutomaton::new(self.get_start().clone(), delta, fsm)
+    }
+
+    /// Construit l'automate quotient à partir de la partition des états calculée par `partition`
+    ///
+    /// L'automate doit être complet et sans état inaccessible.
+    fn quotient(&self, partition : PartitionFn) -> DeterministicFiniteAutomaton {
+        let mut alphabet : Vec<Symbol> = self.get_alphabet().iter().cloned().collect();
+        alphabet.sort();
+        let blocks : Vec<BTreeSet<State>> = partition(self, &alphabet);
+        let mut names : HashMap<State, State> = HashMap::new();
+        let mut block_names : SetNames = SetNames::default();
+        for block in &blocks {
+            let name : State = block_names.name(block);
+            for state in block {
+                names.insert(state.clone(), name.clone());
+            }
+        }
+        let delta : HashMap<Transition<State>, State> = self.get_delta().iter()
+            .map(|(transition, image)| (Transition::new(transition.get_symbol().clone(), names[transition.get_content()].clone()), names[image].clone()))
+            .collect();
+        let ends : HashSet<State> = self.get_ends().iter().map(|end| names[end].clone()).collect();
+        let fsm : FiniteStateMachine = FiniteStateMachine::new(names.values().cloned().collect(), self.get_alphabet().clone(), ends);
+        DeterministicFiniteAutomaton::new(names[self.get_start()].clone(), delta, fsm)
+    }
+}
+
+impl NonDeterministicFiniteAutomaton {
+    /// Minimise l'automate par double renversement (algorithme de Brzozowski)
+    ///
+    /// Calcule determinize(reverse(determinize(reverse(self)))) sans passer par un raffinement de partition.
+    /// La déterminisation d'un automate co-déterministe et accessible donnant directement l'automate minimal,
+    /// cette méthode est parfois plus rapide en pratique, même si elle est exponentielle dans le pire cas.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use automate::*;
+    /// fn main() {
+    ///     let nfa : NonDeterministicFiniteAutomaton = NonDeterministicFiniteAutomaton::from_json_file("src/automates/NFA2.json");
+    ///     let minimal : DeterministicFiniteAutomaton = nfa.minimize_brzozowski();
+    ///     //0*001: 4 états utiles et un puits
+    ///     assert_eq!(minimal.get_states().len(), 5);
+    ///     assert!(minimal.accept("00001"));
+    /// }
+    /// ```
+    ///
+    /// # Return
+    ///
+    /// * `DeterministicFiniteAutomaton` - L'automate complet et minimal reconnaissant le même langage
+    ///
+    pub fn minimize_brzozowski(&self) -> DeterministicFiniteAutomaton {
+        self.reverse().determinize().to_nfa().reverse().determinize()
+    }
+}
+
+/// Partition initiale des états: finaux et non finaux, sans bloc vide
+fn initial_partition(dfa : &DeterministicFiniteAutomaton) -> Vec<BTreeSet<State>> {
+    let (ends, others) : (BTreeSet<State>, BTreeSet<State>) = dfa.get_states().iter().cloned().partition(|state| dfa.get_ends().contains(state));
+    [ends, others].into_iter().filter(|block| !block.is_empty()).collect()
+}
+
+/// Raffinement de Moore: les blocs sont séparés selon les blocs atteints par chaque symbole jusqu'à stabilité
+fn moore_partition(dfa : &DeterministicFiniteAutomaton, alphabet : &[Symbol]) -> Vec<BTreeSet<State>> {
+    let mut blocks : Vec<BTreeSet<State>> = initial_partition(dfa);
+    loop {
+        let mut indexes : HashMap<&State, usize> = HashMap::new();
+        for (index, block) in blocks.iter().enumerate() {
+            for state in block {
+                indexes.insert(state, index);
+            }
+        }
+        //signature d'un etat: son bloc et les blocs de ses images
+        let mut refined : HashMap<Vec<usize>, BTreeSet<State>> = HashMap::new();
+        for state in dfa.get_states() {
+            let mut signature : Vec<usize> = vec![indexes[state]];
+            for symbol in alphabet {
+                let image : &State = dfa.apply_delta(Transition::new(symbol.clone(), state.clone())).unwrap();
+                signature.push(indexes[image]);
+            }
+            refined.entry(signature).or_default().insert(state.clone());
+        }
+        if refined.len() == blocks.len() {
+            return blocks;
+        }
+        blocks = refined.into_values().collect();
+        blocks.sort();
+    }
+}
+
+/// Raffinement de Hopcroft: chaque couple (bloc, symbole) de la file sert de séparateur, seule la plus petite
+/// moitié d'un bloc séparé est ajoutée à la file
+fn hopcroft_partition(dfa : &DeterministicFiniteAutomaton, alphabet : &[Symbol]) -> Vec<BTreeSet<State>> {
+    //antécédents de chaque etat pour chaque symbole
+    let mut predecessors : HashMap<(State, Symbol), Vec<State>> = HashMap::new();
+    for (transition, image) in dfa.get_delta() {
+        predecessors.entry((image.clone(), transition.get_symbol().clone())).or_default().push(transition.get_content().clone());
+    }
+    let mut blocks : Vec<BTreeSet<State>> = initial_partition(dfa);
+    let mut indexes : HashMap<State, usize> = HashMap::new();
+    for (index, block) in blocks.iter().enumerate() {
+        for state in block {
+            indexes.insert(state.clone(), index);
+        }
+    }
+    let mut waiting : HashSet<(usize, Symbol)> = HashSet::new();
+    if let Some(smallest) = (0..blocks.len()).min_by_key(|index| blocks[*index].len()) {
+        for symbol in alphabet {
+            waiting.insert((smallest, symbol.clone()));
+        }
+    }
+    while let Some(splitter) = waiting.iter().next().cloned() {
+        waiting.remove(&splitter);
+        let (splitter_block, symbol) = splitter;
+        //etats menant dans le bloc separateur en lisant le symbole
+        let mut touched : HashMap<usize, BTreeSet<State>> = HashMap::new();
+        for state in &blocks[splitter_block] {
+            for previous in predecessors.get(&(state.clone(), symbol.clone())).into_iter().flatten() {
+                touched.entry(indexes[previous]).or_default().insert(previous.clone());
+            }
+        }
+        for (index, inside) in touched {
+            if inside.len() == blocks[index].len() {
+                continue;
+            }
+            let outside : BTreeSet<State> = blocks[index].difference(&inside).cloned().collect();
+            let new_index : usize = blocks.len();
+            for state in &outside {
+                indexes.insert(state.clone(), new_index);
+            }
+            let smallest : usize = if inside.len() <= outside.len() { index } else { new_index };
+            blocks[index] = inside;
+            blocks.push(outside);
+            for symbol in alphabet {
+                if waiting.contains(&(index, symbol.clone())) {
+                    waiting.insert((new_index, symbol.clone()));
+                } else {
+                    waiting.insert((smallest, symbol.clone()));
+                }
+            }
+        }
+    }
+    blocks
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use super::super::test_words::enumerate_words;
+
+    #[test]
+    fn strategies_are_isomorphic() {
+        let strategies : [MinimizationStrategy; 3] = [MinimizationStrategy::Hopcroft, MinimizationStrategy::Moore, MinimizationStrategy::Brzozowski];
+        let mut samples : Vec<(DeterministicFiniteAutomaton, &str)> = Vec::new();
+        for name in ["DFA1", "DFA2", "DFA3"] {
+            samples.push((DeterministicFiniteAutomaton::from_json_file(&format!("src/automates/{}.json", name)), "abc01"));
+        }
+        for name in ["NFA1", "NFA2", "NFA3"] {
+            samples.push((NonDeterministicFiniteAutomaton::from_json_file(&format!("src/automates/{}.json", name)).determinize(), "abc01"));
+        }
+        for (dfa, letters) in &samples {
+            let minimals : Vec<DeterministicFiniteAutomaton> = strategies.iter().map(|strategy| dfa.minimize_with(*strategy)).collect();
+            for minimal in &minimals {
+                assert!(minimal.is_complete());
+                assert!(minimal.is_isomorphic(&minimals[0]).is_some());
+                assert_eq!(minimal.canonicalize().get_delta(), minimals[0].canonicalize().get_delta());
+                for word in enumerate_words(letters, 4) {
+                    assert_eq!(minimal.accept(&word), dfa.accept(&word), "{}", word);
+                }
+            }
+        }
+        //(ab)*: deux etats et un puits
+        assert_eq!(samples[0].0.minimize().get_states().len(), 3);
+        assert_eq!(samples[1].0.minimize().get_states().len(), 4);
+        //{b, ab, abc}: q_0, q_1, q_2, q_3 et un puits
+        assert_eq!(samples[2].0.minimize().get_states().len(), 5);
+    }
+
+    #[test]
+    fn merges_equivalent_states() {
+        //q_1 et q_2 sont equivalents: a(a|b)*
+        let dfa : DeterministicFiniteAutomaton = DeterministicFiniteAutomaton::from_json(serde_json::json!({
+            "states" : ["q_0", "q_1", "q_2"],
+            "alphabet" : ["a", "b"],
+            "ends" : ["q_1", "q_2"],
+            "start" : "q_0",
+            "delta" : [
+                {"state" : "q_0", "symbol" : "a", "image" : "q_1"},
+                {"state" : "q_1", "symbol" : "a", "image" : "q_2"},
+                {"state" : "q_1", "symbol" : "b", "image" : "q_2"},
+                {"state" : "q_2", "symbol" : "a", "image" : "q_1"},
+                {"state" : "q_2", "symbol" : "b", "image" : "q_1"}
+            ]
+        }).as_object().unwrap());
+        let minimal : DeterministicFiniteAutomaton = dfa.minimize_with(MinimizationStrategy::Moore);
+        assert!(minimal.get_states().contains(&State::from_str("{q_1,q_2}")));
+        assert_eq!(minimal.get_states().len(), 3);
+        assert_eq!(dfa.minimize_brzozowski().get_states().len(), 3);
+    }
+}
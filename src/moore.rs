@@ -0,0 +1,259 @@
+use super::{Transition, State, Symbol, MealyMachine, to_symbols};
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use serde_json::{Value, from_str, map::Map};
+
+/// Machine de Moore: un automate déterministe dont chaque état produit un symbole de sortie
+#[derive(Debug, Clone)]
+pub struct MooreMachine {
+    start: State,
+    delta: HashMap<Transition<State>, State>,
+    lambda: HashMap<State, Symbol>,
+    states: HashSet<State>,
+    inputs: HashSet<Symbol>,
+    outputs: HashSet<Symbol>,
+}
+
+impl MooreMachine {
+    /// Créer une machine de Moore
+    ///
+    /// # Arguments
+    ///
+    /// * `_start` - L'état initial de la machine
+    /// * `_delta` - Une HashMap decrivant les transitions de la machine
+    /// * `_lambda` - Une HashMap associant à chaque état son symbole de sortie
+    /// * `_states` - Les états de la machine
+    /// * `_inputs` - L'alphabet d'entrée
+    /// * `_outputs` - L'alphabet de sortie
+    ///
+    /// # Return
+    ///
+    /// * `MooreMachine` - La machine de Moore correspondante
+    ///
+    pub fn new(_start : State, _delta : HashMap<Transition<State>, State>, _lambda : HashMap<State, Symbol>, _states : HashSet<State>, _inputs : HashSet<Symbol>, _outputs : HashSet<Symbol>) -> Self {
+        MooreMachine {
+            start: _start,
+            delta: _delta,
+            lambda: _lambda,
+            states: _states,
+            inputs: _inputs,
+            outputs: _outputs,
+        }
+    }
+
+    /// Créer une machine de Moore depuis un contenu json
+    ///
+    /// # Examples
+    ///
+    /// Le contenu du json: les transitions sont celles d'un automate déterministe, les sorties sont données par état
+    ///
+    /// ```json
+    /// {
+    ///     "states" : ["even","odd"],
+    ///     "input_alphabet" : ["0","1"],
+    ///     "output_alphabet" : ["E","O"],
+    ///     "start" : "even",
+    ///     "delta" : [
+    ///         {
+    ///             "state" : "even",
+    ///             "symbol" : "1",
+    ///             "image" : "odd"
+    ///         }
+    ///     ],
+    ///     "outputs" : [
+    ///         {
+    ///             "state" : "even",
+    ///             "output" : "E"
+    ///         }
+    ///     ]
+    /// }
+    /// ```
+    ///
+    /// # Return
+    ///
+    /// * `MooreMachine` - La machine de Moore correspondante
+    ///
+    pub fn from_json(content_json: &Map<String, Value>) -> Self {
+        let name = |value : &Value| String::from(value.as_str().unwrap());
+        let start : State = State::new(name(content_json.get("start").unwrap()));
+        let mut states : HashSet<State> = HashSet::from([start.clone()]);
+        let mut inputs : HashSet<Symbol> = HashSet::new();
+        let mut outputs : HashSet<Symbol> = HashSet::new();
+        for state in content_json.get("states").unwrap().as_array().unwrap() {
+            states.insert(State::new(name(state)));
+        }
+        for symbol in content_json.get("input_alphabet").unwrap().as_array().unwrap() {
+            inputs.insert(Symbol::new(name(symbol)));
+        }
+        for symbol in content_json.get("output_alphabet").unwrap().as_array().unwrap() {
+            outputs.insert(Symbol::new(name(symbol)));
+        }
+        let mut delta : HashMap<Transition<State>, State> = HashMap::new();
+        for element_delta in content_json.get("delta").unwrap().as_array().unwrap() {
+            let transition_json : &Map<String, Value> = element_delta.as_object().unwrap();
+            let symbol : Symbol = Symbol::new(name(transition_json.get("symbol").unwrap()));
+            let state : State = State::new(name(transition_json.get("state").unwrap()));
+            let image : State = State::new(name(transition_json.get("image").unwrap()));
+            delta.insert(Transition::new(symbol.clone(), state.clone()), image.clone());
+            states.insert(state);
+            states.insert(image);
+            inputs.insert(symbol);
+        }
+        let mut lambda : HashMap<State, Symbol> = HashMap::new();
+        for element_output in content_json.get("outputs").unwrap().as_array().unwrap() {
+            let output_json : &Map<String, Value> = element_output.as_object().unwrap();
+            let state : State = State::new(name(output_json.get("state").unwrap()));
+            let output : Symbol = Symbol::new(name(output_json.get("output").unwrap()));
+            lambda.insert(state.clone(), output.clone());
+            states.insert(state);
+            outputs.insert(output);
+        }
+        MooreMachine::new(start, delta, lambda, states, inputs, outputs)
+    }
+
+    /// Créer une machine de Moore depuis un chemin vers un fichier json, voir `from_json`
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use automate::*;
+    /// fn main() {
+    ///     let moore : MooreMachine = MooreMachine::from_json_file("src/automates/MOORE1.json");
+    ///     assert_eq!(moore.get_output(&State::from_str("odd")), Some(&Symbol::from_str("O")));
+    /// }
+    /// ```
+    ///
+    pub fn from_json_file(path: &str) -> Self {
+        let content_json: Value = {
+            // Charge le contenu du fichier en tant que String
+            let content : String = fs::read_to_string(path).unwrap();
+            // Parse le texte en structure Json
+            from_str::<Value>(&content).unwrap()
+        };
+        MooreMachine::from_json(content_json.as_object().unwrap())
+    }
+
+    /// Retourne l'état initial de la machine
+    pub fn get_start(&self) -> &State {
+        &self.start
+    }
+
+    /// Retourne les transitions de la machine
+    pub fn get_delta(&self) -> &HashMap<Transition<State>, State> {
+        &self.delta
+    }
+
+    /// Retourne les symboles de sortie des états
+    pub fn get_lambda(&self) -> &HashMap<State, Symbol> {
+        &self.lambda
+    }
+
+    /// Retourne le symbole produit par `state`
+    pub fn get_output(&self, state : &State) -> Option<&Symbol> {
+        self.lambda.get(state)
+    }
+
+    /// Retourne les états de la machine
+    pub fn get_states(&self) -> &HashSet<State> {
+        &self.states
+    }
+
+    /// Retourne l'alphabet d'entrée
+    pub fn get_inputs(&self) -> &HashSet<Symbol> {
+        &self.inputs
+    }
+
+    /// Retourne l'alphabet de sortie
+    pub fn get_outputs(&self) -> &HashSet<Symbol> {
+        &self.outputs
+    }
+
+    pub fn apply_delta(&self, transition : Transition<State>) -> Option<&State> {
+        self.delta.get(&transition)
+    }
+
+    /// Traduit une suite de symboles d'entrée, voir `translate`
+    pub fn translate_symbols(&self, input : &[Symbol]) -> Option<Vec<Symbol>> {
+        let mut state : &State = self.get_start();
+        let mut output : Vec<Symbol> = vec![self.get_output(state)?.clone()];
+        for symbol in input {
+            state = self.apply_delta(Transition::new(symbol.clone(), state.clone()))?;
+            output.push(self.get_output(state)?.clone());
+        }
+        Some(output)
+    }
+
+    /// Traduit un mot d'entrée, lu caractère par caractère, en la suite des symboles produits par les états traversés
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use automate::*;
+    /// fn main() {
+    ///     //la machine indique la parité du nombre de 1 lus
+    ///     let moore : MooreMachine = MooreMachine::from_json_file("src/automates/MOORE1.json");
+    ///     assert_eq!(moore.translate("101").unwrap(), ["E", "O", "O", "E"].map(Symbol::from_str));
+    /// }
+    /// ```
+    ///
+    /// # Return
+    ///
+    /// * `Option<Vec<Symbol>>` - Le symbole de l'état initial puis un symbole par symbole d'entrée, `None` si une transition ou une sortie n'est pas définie
+    ///
+    pub fn translate(&self, input : &str) -> Option<Vec<Symbol>> {
+        let input : Vec<Symbol> = to_symbols(input);
+        self.translate_symbols(&input)
+    }
+
+    /// Convertit la machine en machine de Mealy, chaque transition produisant le symbole de l'état qu'elle atteint
+    ///
+    /// La machine de Mealy produit la traduction de la machine de Moore privée de son premier symbole.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use automate::*;
+    /// fn main() {
+    ///     let moore : MooreMachine = MooreMachine::from_json_file("src/automates/MOORE1.json");
+    ///     assert_eq!(moore.to_mealy().translate("101").unwrap(), ["O", "O", "E"].map(Symbol::from_str));
+    /// }
+    /// ```
+    ///
+    /// # Return
+    ///
+    /// * `MealyMachine` - La machine de Mealy équivalente, sur les mêmes états
+    ///
+    pub fn to_mealy(&self) -> MealyMachine {
+        let mut delta : HashMap<Transition<State>, State> = HashMap::new();
+        let mut lambda : HashMap<Transition<State>, Symbol> = HashMap::new();
+        for (transition, image) in self.get_delta() {
+            if let Some(output) = self.get_output(image) {
+                delta.insert(transition.clone(), image.clone());
+                lambda.insert(transition.clone(), output.clone());
+            }
+        }
+        MealyMachine::new(self.get_start().clone(), delta, lambda, self.get_states().clone(), self.get_inputs().clone(), self.get_outputs().clone())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use super::super::test_words::enumerate_words;
+
+    #[test]
+    fn translate_and_convert() {
+        let moore : MooreMachine = MooreMachine::from_json_file("src/automates/MOORE1.json");
+        assert_eq!(moore.translate(""), Some(vec![Symbol::from_str("E")]));
+        assert_eq!(moore.translate("2"), None);
+        let round_trip : MooreMachine = moore.to_mealy().to_moore(Symbol::from_str("E"));
+        for word in enumerate_words("01", 6) {
+            let output : Vec<Symbol> = moore.translate(&word).unwrap();
+            let parity : usize = word.chars().filter(|letter| *letter == '1').count() % 2;
+            assert_eq!(output.last(), Some(&Symbol::from_str(["E", "O"][parity])));
+            assert_eq!(round_trip.translate(&word).unwrap(), output);
+        }
+        //l'état initial produit E: il se confond avec l'état (even,E) atteint par les transitions
+        assert_eq!(round_trip.get_states().len(), 2);
+    }
+}
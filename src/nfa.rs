@@ -1,5 +1,6 @@
-use super::{Transition, State,Symbol, FiniteStateMachine, insert_all};
-use std::collections::{HashMap, HashSet};
+use super::{Transition, State,Symbol, FiniteStateMachine, DeterministicFiniteAutomaton, insert_all, fresh_state, SetNames};
+use super::paths::{self, Adjacency};
+use std::collections::{BTreeSet, HashMap, HashSet, VecDeque};
 use std::{fs};
 use serde_json::{Value, from_str, map::Map};
 
@@ -212,9 +213,9 @@ impl NonDeterministicFiniteAutomaton {
         //let fsm = FiniteStateMachine::from_json(content_json);
         let fsm : FiniteStateMachine = FiniteStateMachine::new(states, alphabet, ends);
         NonDeterministicFiniteAutomaton { 
-            starts: starts, 
-            delta: delta, 
-            fsm: fsm
+            starts, 
+            delta, 
+            fsm
         }
     }
 
@@ -310,24 +311,21 @@ impl NonDeterministicFiniteAutomaton {
 
     /// Retournes les differents états de l'automate
     pub fn get_states(&self) -> &HashSet<State> {
-        &self.fsm.get_states()
+        self.fsm.get_states()
     }
 
     /// Retourne l'alphabet de l'automate
     pub fn get_alphabet(&self) -> &HashSet<Symbol> {
-        &self.fsm.get_alphabet()
+        self.fsm.get_alphabet()
     }
 
     /// Retourne les états finaux de l'automate
     pub fn get_ends(&self) -> &HashSet<State> {
-        &self.fsm.get_ends()
+        self.fsm.get_ends()
     }
     /// Applique une transition et renvoie un set d'etat (representant l'image de la transition)
     pub fn apply_delta(&self, transition : Transition<State>)-> Option<HashSet<State>>{
-        if let Some(n) = self.get_delta().get(&transition) {
-            return Some(n.clone());
-        }
-        return None;
+        self.get_delta().get(&transition).cloned()
     }
 
     /// Applique les transition et renvoie un set d'etat (representant l'image de la transition)
@@ -339,15 +337,14 @@ impl NonDeterministicFiniteAutomaton {
         for state in set_transition.get_content().clone(){
             transition = Transition::new(symbol.clone(), state);
             current = self.apply_delta(transition);
-            if current != None {
-                images = insert_all(images, current.unwrap());
-            }else{
+            if let Some(current) = current {
+                images = insert_all(images, current);
             }
         }
         if images.is_empty() {
             return None;
         }
-        return Some(images);
+        Some(images)
     }
 
     /// indique si un mot est accepté dans la langue de l'automate
@@ -361,13 +358,12 @@ impl NonDeterministicFiniteAutomaton {
             transition = Transition::new(symbol, currents.clone());
             //execution de delta pour reccuperer l'image
             temp =self.apply_deltas(transition);
-            if temp==None {
+            currents = match temp {
+                Some(images) => images,
                 //si aucune image n'a ete trouver, ca ne sert à rien de poursuitre
-                return false;
-            }
-            currents =temp.unwrap();
+                None => return false,
+            };
         }
-        dbg!(currents.clone());
         for state in currents{
             //si on trouve un etat qui fait parti des etats finaux de l'automate, on valide le mot
             if self.get_ends().contains(&state){
@@ -375,14 +371,465 @@ impl NonDeterministicFiniteAutomaton {
             }
         }
         //aucun des etats de currents ne fait parti des etats finaux
+        false
+    }
+
+    /// indique si un mot, donné comme une suite de symboles, est accepté dans la langue de l'automate
+    ///
+    /// Contrairement à `accept`, les symboles peuvent contenir plusieurs caractères.
+    pub fn accept_symbols(&self, word : &[Symbol]) -> bool {
+        let mut currents : HashSet<State> = self.get_starts().clone();
+        for symbol in word {
+            currents = match self.apply_deltas(Transition::new(symbol.clone(), currents)) {
+                Some(images) => images,
+                None => return false,
+            };
+        }
+        currents.iter().any(|state| self.get_ends().contains(state))
+    }
+
+    /// Créer un automate non déterministe à partir de transitions pouvant être des epsilon-transitions (symbole `None`)
+    ///
+    /// Les epsilon-transitions sont supprimées: chaque état reçoit les transitions de sa epsilon-clôture et devient
+    /// final si sa clôture contient un état final.
+    pub(crate) fn from_epsilon_transitions(starts : HashSet<State>, transitions : &[(State, Option<Symbol>, State)], ends : &HashSet<State>, alphabet : HashSet<Symbol>) -> Self {
+        let mut states : HashSet<State> = starts.union(ends).cloned().collect();
+        let mut epsilons : HashMap<State, Vec<State>> = HashMap::new();
+        let mut moves : HashMap<State, Vec<(Symbol, State)>> = HashMap::new();
+        for (state, symbol, image) in transitions {
+            states.insert(state.clone());
+            states.insert(image.clone());
+            match symbol {
+                Some(symbol) => moves.entry(state.clone()).or_default().push((symbol.clone(), image.clone())),
+                None => epsilons.entry(state.clone()).or_default().push(image.clone()),
+            }
+        }
+        let mut delta : HashMap<Transition<State>, HashSet<State>> = HashMap::new();
+        let mut new_ends : HashSet<State> = HashSet::new();
+        for state in &states {
+            //epsilon-cloture de state
+            let mut closure : HashSet<State> = HashSet::from([state.clone()]);
+            let mut stack : Vec<State> = vec![state.clone()];
+            while let Some(current) = stack.pop() {
+                for next in epsilons.get(&current).into_iter().flatten() {
+                    if closure.insert(next.clone()) {
+                        stack.push(next.clone());
+                    }
+                }
+            }
+            for reached in &closure {
+                if ends.contains(reached) {
+                    new_ends.insert(state.clone());
+                }
+                for (symbol, image) in moves.get(reached).into_iter().flatten() {
+                    delta.entry(Transition::new(symbol.clone(), state.clone())).or_default().insert(image.clone());
+                }
+            }
+        }
+        let fsm : FiniteStateMachine = FiniteStateMachine::new(states, alphabet, new_ends);
+        NonDeterministicFiniteAutomaton::new(starts, delta, fsm)
+    }
+
+    /// Retourne la liste d'adjacence de l'automate (etat -> couples (symbole, image) triés)
+    pub(crate) fn adjacency(&self) -> Adjacency {
+        paths::build_adjacency(self.get_delta().iter().flat_map(|(transition, images)| {
+            images.iter().map(move |image| {
+                (transition.get_content().clone(), transition.get_symbol().clone(), image.clone())
+            })
+        }))
+    }
+
+    /// Retourne le plus court mot accepté par l'automate
+    ///
+    /// Un mot est accepté s'il existe un chemin d'un état initial vers un état final, le parcours en largeur se fait
+    /// donc directement sur les états sans déterminiser l'automate.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use automate::*;
+    /// fn main() {
+    ///     let nfa : NonDeterministicFiniteAutomaton = NonDeterministicFiniteAutomaton::from_json_file("src/automates/NFA2.json");
+    ///     let word : Vec<Symbol> = nfa.shortest_accepted_word().unwrap();
+    ///     assert_eq!(word, vec![Symbol::from_str("0"), Symbol::from_str("0"), Symbol::from_str("1")]);
+    /// }
+    /// ```
+    ///
+    /// # Return
+    ///
+    /// * `Option<Vec<Symbol>>` - Le mot le plus court, `None` si le langage est vide
+    ///
+    pub fn shortest_accepted_word(&self) -> Option<Vec<Symbol>> {
+        paths::shortest_word(&self.adjacency(), self.get_starts(), self.get_ends())
+    }
+
+    /// Indique si le langage de l'automate est fini
+    pub fn is_finite(&self) -> bool {
+        paths::is_finite(&self.adjacency(), self.get_starts(), self.get_ends())
+    }
+
+    /// Retourne le plus long mot accepté par l'automate
+    ///
+    /// # Return
+    ///
+    /// * `Option<Vec<Symbol>>` - Le mot le plus long, `None` si le langage est vide ou infini
+    ///
+    pub fn longest_accepted_word(&self) -> Option<Vec<Symbol>> {
+        paths::longest_word(&self.adjacency(), self.get_starts(), self.get_ends())
+    }
+
+    /// Retourne, pour chaque état, le nombre minimal de symboles à lire pour atteindre un état final
+    ///
+    /// Les états depuis lesquels aucun état final n'est accessible n'apparaissent pas dans la HashMap.
+    /// La table est calculée en un parcours en largeur de tout l'automate: pour suivre la lecture d'un texte,
+    /// elle est calculée une seule fois puis consultée à chaque état atteint.
+    ///
+    /// # Return
+    ///
+    /// * `HashMap<State, usize>` - La distance de chaque état à l'acceptation
+    ///
+    pub fn distances_to_acceptance(&self) -> HashMap<State, usize> {
+        paths::distances_to(&self.adjacency(), self.get_ends())
+    }
+
+    /// Indique si le langage de l'automate est inclus dans celui de `other`
+    ///
+    /// L'algorithme utilise des antichaînes afin d'éviter la déterminisation complète de `other`: on explore
+    /// des couples (état de self, ensemble d'états de other) atteints par un même mot. Un couple (p, S) est
+    /// ignoré dès qu'un couple (p, S') avec S' inclus dans S a déjà été rencontré, car tout contre-exemple
+    /// trouvé depuis (p, S) l'est aussi depuis (p, S').
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - L'automate dont le langage doit contenir celui de self
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use automate::*;
+    /// fn main() {
+    ///     let nfa2 : NonDeterministicFiniteAutomaton = NonDeterministicFiniteAutomaton::from_json_file("src/automates/NFA2.json");
+    ///     let nfa3 : NonDeterministicFiniteAutomaton = NonDeterministicFiniteAutomaton::from_json_file("src/automates/NFA3.json");
+    ///     assert_eq!(nfa2.is_subset_of(&nfa2), Ok(()));
+    ///     //"ab" est accepté par nfa3 mais pas par nfa2
+    ///     assert_eq!(nfa3.is_subset_of(&nfa2), Err(vec![Symbol::from_str("a"), Symbol::from_str("b")]));
+    /// }
+    /// ```
+    ///
+    /// # Return
+    ///
+    /// * `Result<(), Vec<Symbol>>` - `Ok(())` si l'inclusion est vérifiée, sinon un mot accepté par self et refusé par `other`
+    ///
+    pub fn is_subset_of(&self, other : &NonDeterministicFiniteAutomaton) -> Result<(), Vec<Symbol>> {
+        let adjacency : Adjacency = self.adjacency();
+        let other_starts : BTreeSet<State> = other.get_starts().iter().cloned().collect();
+        let mut starts : Vec<&State> = self.get_starts().iter().collect();
+        starts.sort();
+        //antichaine: pour chaque etat de self, les ensembles minimaux d'etats de other deja rencontrés
+        let mut antichain : HashMap<State, Vec<BTreeSet<State>>> = HashMap::new();
+        let mut queue : VecDeque<(State, BTreeSet<State>, Vec<Symbol>)> = VecDeque::new();
+        for start in starts {
+            if add_to_antichain(&mut antichain, start, &other_starts) {
+                queue.push_back((start.clone(), other_starts.clone(), Vec::new()));
+            }
+        }
+        while let Some((state, other_states, word)) = queue.pop_front() {
+            if self.get_ends().contains(&state) && other_states.iter().all(|other_state| !other.get_ends().contains(other_state)) {
+                return Err(word);
+            }
+            for (symbol, image) in adjacency.get(&state).into_iter().flatten() {
+                let other_images : BTreeSet<State> = match other.apply_deltas(Transition::new(symbol.clone(), other_states.iter().cloned().collect())) {
+                    Some(images) => images.into_iter().collect(),
+                    None => BTreeSet::new(),
+                };
+                if add_to_antichain(&mut antichain, image, &other_images) {
+                    let mut next_word : Vec<Symbol> = word.clone();
+                    next_word.push(symbol.clone());
+                    queue.push_back((image.clone(), other_images, next_word));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Retourne une copie de l'automate dont chaque état est préfixé par `prefix`
+    fn rename(&self, prefix : &str) -> NonDeterministicFiniteAutomaton {
+        let rename_state = |state : &State| State::new(format!("{}{}", prefix, state.get_name()));
+        let rename_set = |states : &HashSet<State>| states.iter().map(rename_state).collect::<HashSet<State>>();
+        let delta : HashMap<Transition<State>, HashSet<State>> = self.get_delta().iter()
+            .map(|(transition, images)| (Transition::new(transition.get_symbol().clone(), rename_state(transition.get_content())), rename_set(images)))
+            .collect();
+        let fsm : FiniteStateMachine = FiniteStateMachine::new(rename_set(self.get_states()), self.get_alphabet().clone(), rename_set(self.get_ends()));
+        NonDeterministicFiniteAutomaton::new(rename_set(self.get_starts()), delta, fsm)
+    }
+
+    /// Indique si le mot vide est accepté, c'est à dire si un état initial est final
+    pub fn accepts_empty_word(&self) -> bool {
+        self.get_starts().iter().any(|start| self.get_ends().contains(start))
+    }
+
+    /// Retourne l'automate miroir, qui reconnaît les mots de self lus à l'envers
+    ///
+    /// Les états initiaux et finaux sont échangés et chaque transition est inversée.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use automate::*;
+    /// fn main() {
+    ///     let nfa : NonDeterministicFiniteAutomaton = NonDeterministicFiniteAutomaton::from_json_file("src/automates/NFA2.json");
+    ///     let reversed : NonDeterministicFiniteAutomaton = nfa.reverse();
+    ///     assert!(nfa.accept("0001"));
+    ///     assert!(reversed.accept("1000"));
+    /// }
+    /// ```
+    ///
+    /// # Return
+    ///
+    /// * `NonDeterministicFiniteAutomaton` - L'automate reconnaissant le langage miroir
+    ///
+    pub fn reverse(&self) -> NonDeterministicFiniteAutomaton {
+        let mut delta : HashMap<Transition<State>, HashSet<State>> = HashMap::new();
+        for (transition, images) in self.get_delta() {
+            for image in images {
+                delta.entry(Transition::new(transition.get_symbol().clone(), image.clone()))
+                    .or_default()
+                    .insert(transition.get_content().clone());
+            }
+        }
+        let fsm : FiniteStateMachine = FiniteStateMachine::new(self.get_states().clone(), self.get_alphabet().clone(), self.get_starts().clone());
+        NonDeterministicFiniteAutomaton::new(self.get_ends().clone(), delta, fsm)
+    }
+
+    /// Retourne un automate reconnaissant l'union des langages de self et de `other`
+    ///
+    /// Les états de self sont préfixés par `0.` et ceux de `other` par `1.` afin qu'ils restent disjoints.
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - Le second automate, un automate déterministe peut être passé via `to_nfa`
+    ///
+    /// # Return
+    ///
+    /// * `NonDeterministicFiniteAutomaton` - L'automate reconnaissant L(self) ∪ L(other)
+    ///
+    pub fn union_nfa(&self, other : &NonDeterministicFiniteAutomaton) -> NonDeterministicFiniteAutomaton {
+        let left : NonDeterministicFiniteAutomaton = self.rename("0.");
+        let right : NonDeterministicFiniteAutomaton = other.rename("1.");
+        let mut delta : HashMap<Transition<State>, HashSet<State>> = left.get_delta().clone();
+        delta.extend(right.get_delta().clone());
+        let fsm : FiniteStateMachine = FiniteStateMachine::new(
+            left.get_states().union(right.get_states()).cloned().collect(),
+            left.get_alphabet().union(right.get_alphabet()).cloned().collect(),
+            left.get_ends().union(right.get_ends()).cloned().collect(),
+        );
+        NonDeterministicFiniteAutomaton::new(left.get_starts().union(right.get_starts()).cloned().collect(), delta, fsm)
+    }
+
+    /// Réunit une liste d'automates en un seul, les états du i-ème étant préfixés par `i.`
+    ///
+    /// Retourne aussi, pour chaque automate de la liste, ses états finaux renommés dans le résultat.
+    pub(crate) fn disjoint_union(automata : &[&NonDeterministicFiniteAutomaton]) -> (NonDeterministicFiniteAutomaton, Vec<HashSet<State>>) {
+        let mut starts : HashSet<State> = HashSet::new();
+        let mut delta : HashMap<Transition<State>, HashSet<State>> = HashMap::new();
+        let mut states : HashSet<State> = HashSet::new();
+        let mut alphabet : HashSet<Symbol> = HashSet::new();
+        let mut ends : Vec<HashSet<State>> = Vec::new();
+        for (index, automaton) in automata.iter().enumerate() {
+            let renamed : NonDeterministicFiniteAutomaton = automaton.rename(&format!("{}.", index));
+            starts.extend(renamed.get_starts().iter().cloned());
+            delta.extend(renamed.get_delta().clone());
+            states.extend(renamed.get_states().iter().cloned());
+            alphabet.extend(renamed.get_alphabet().iter().cloned());
+            ends.push(renamed.get_ends().clone());
+        }
+        let fsm : FiniteStateMachine = FiniteStateMachine::new(states, alphabet, ends.iter().flatten().cloned().collect());
+        (NonDeterministicFiniteAutomaton::new(starts, delta, fsm), ends)
+    }
+
+    /// Retourne un automate reconnaissant la concaténation des langages de self et de `other`
+    ///
+    /// Sans epsilon-transition, chaque transition de self menant à un état final est doublée par une transition
+    /// vers les états initiaux de `other`. Les états de self sont préfixés par `0.` et ceux de `other` par `1.`.
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - Le second automate, un automate déterministe peut être passé via `to_nfa`
+    ///
+    /// # Return
+    ///
+    /// * `NonDeterministicFiniteAutomaton` - L'automate reconnaissant L(self)·L(other)
+    ///
+    pub fn concat(&self, other : &NonDeterministicFiniteAutomaton) -> NonDeterministicFiniteAutomaton {
+        let left : NonDeterministicFiniteAutomaton = self.rename("0.");
+        let right : NonDeterministicFiniteAutomaton = other.rename("1.");
+        let mut delta : HashMap<Transition<State>, HashSet<State>> = left.get_delta().clone();
+        delta.extend(right.get_delta().clone());
+        for (transition, images) in left.get_delta() {
+            if images.iter().any(|image| left.get_ends().contains(image)) {
+                delta.entry(transition.clone()).or_default().extend(right.get_starts().iter().cloned());
+            }
+        }
+        //si le mot vide appartient à L(self), les mots de L(other) seuls sont acceptés
+        let mut starts : HashSet<State> = left.get_starts().clone();
+        if left.accepts_empty_word() {
+            starts.extend(right.get_starts().iter().cloned());
+        }
+        //si le mot vide appartient à L(other), les mots de L(self) seuls sont acceptés
+        let mut ends : HashSet<State> = right.get_ends().clone();
+        if right.accepts_empty_word() {
+            ends.extend(left.get_ends().iter().cloned());
+        }
+        let fsm : FiniteStateMachine = FiniteStateMachine::new(
+            left.get_states().union(right.get_states()).cloned().collect(),
+            left.get_alphabet().union(right.get_alphabet()).cloned().collect(),
+            ends,
+        );
+        NonDeterministicFiniteAutomaton::new(starts, delta, fsm)
+    }
+
+    /// Ajoute, depuis chaque état final, une copie des transitions sortantes des états initiaux
+    fn loop_back(&self) -> HashMap<Transition<State>, HashSet<State>> {
+        let mut delta : HashMap<Transition<State>, HashSet<State>> = self.get_delta().clone();
+        for (transition, images) in self.get_delta() {
+            if self.get_starts().contains(transition.get_content()) {
+                for end in self.get_ends() {
+                    delta.entry(Transition::new(transition.get_symbol().clone(), end.clone())).or_default().extend(images.iter().cloned());
+                }
+            }
+        }
+        delta
+    }
+
+    /// Retourne un automate reconnaissant l'étoile de Kleene du langage de self
+    ///
+    /// Un nouvel état initial et final est ajouté pour reconnaître le mot vide, puis les états finaux
+    /// reçoivent une copie des transitions sortantes des états initiaux.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use automate::*;
+    /// fn main() {
+    ///     let nfa : NonDeterministicFiniteAutomaton = NonDeterministicFiniteAutomaton::from_json_file("src/automates/NFA3.json");
+    ///     let star : NonDeterministicFiniteAutomaton = nfa.star();
+    ///     assert!(star.accept(""));
+    ///     assert!(star.accept("ababcab"));
+    /// }
+    /// ```
+    ///
+    /// # Return
+    ///
+    /// * `NonDeterministicFiniteAutomaton` - L'automate reconnaissant L(self)*
+    ///
+    pub fn star(&self) -> NonDeterministicFiniteAutomaton {
+        let plus : NonDeterministicFiniteAutomaton = self.plus();
+        let start : State = fresh_state(plus.get_states(), "start");
+        let mut delta : HashMap<Transition<State>, HashSet<State>> = plus.get_delta().clone();
+        for (transition, images) in plus.get_delta() {
+            if plus.get_starts().contains(transition.get_content()) {
+                delta.entry(Transition::new(transition.get_symbol().clone(), start.clone())).or_default().extend(images.iter().cloned());
+            }
+        }
+        let mut states : HashSet<State> = plus.get_states().clone();
+        states.insert(start.clone());
+        let mut ends : HashSet<State> = plus.get_ends().clone();
+        ends.insert(start.clone());
+        let fsm : FiniteStateMachine = FiniteStateMachine::new(states, plus.get_alphabet().clone(), ends);
+        NonDeterministicFiniteAutomaton::new(HashSet::from([start]), delta, fsm)
+    }
+
+    /// Retourne un automate reconnaissant L(self)+, c'est à dire L(self)·L(self)*
+    ///
+    /// # Return
+    ///
+    /// * `NonDeterministicFiniteAutomaton` - L'automate reconnaissant L(self)+
+    ///
+    pub fn plus(&self) -> NonDeterministicFiniteAutomaton {
+        let delta : HashMap<Transition<State>, HashSet<State>> = self.loop_back();
+        NonDeterministicFiniteAutomaton::new(self.get_starts().clone(), delta, self.fsm.clone())
+    }
+
+    /// Déterminise l'automate par la construction des sous-ensembles
+    ///
+    /// Seuls les ensembles accessibles depuis l'ensemble des états initiaux sont construits. Chaque état du
+    /// résultat est nommé d'après l'ensemble qu'il représente, par exemple `{q_0,q_1}`. L'ensemble vide `{}`
+    /// sert de puits, l'automate obtenu est donc complet sur l'alphabet de self.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use automate::*;
+    /// fn main() {
+    ///     let nfa : NonDeterministicFiniteAutomaton = NonDeterministicFiniteAutomaton::from_json_file("src/automates/NFA2.json");
+    ///     let dfa : DeterministicFiniteAutomaton = nfa.determinize();
+    ///     assert_eq!(dfa.get_start(), &State::from_str("{q_0}"));
+    ///     assert!(dfa.accept("0001"));
+    /// }
+    /// ```
+    ///
+    /// # Return
+    ///
+    /// * `DeterministicFiniteAutomaton` - L'automate déterministe reconnaissant le même langage
+    ///
+    pub fn determinize(&self) -> DeterministicFiniteAutomaton {
+        self.determinize_subsets().0
+    }
+
+    /// Construction des sous-ensembles, qui retourne aussi l'ensemble d'états de self représenté par chaque état du résultat
+    pub(crate) fn determinize_subsets(&self) -> (DeterministicFiniteAutomaton, HashMap<State, BTreeSet<State>>) {
+        let mut alphabet : Vec<Symbol> = self.get_alphabet().iter().cloned().collect();
+        alphabet.sort();
+        let start : BTreeSet<State> = self.get_starts().iter().cloned().collect();
+        let mut delta : HashMap<Transition<State>, State> = HashMap::new();
+        let mut names : SetNames = SetNames::default();
+        let mut states : HashSet<State> = HashSet::from([names.name(&start)]);
+        let mut ends : HashSet<State> = HashSet::new();
+        let mut subsets : HashMap<State, BTreeSet<State>> = HashMap::new();
+        let mut stack : Vec<BTreeSet<State>> = vec![start.clone()];
+        while let Some(subset) = stack.pop() {
+            let name : State = names.name(&subset);
+            if subset.iter().any(|state| self.get_ends().contains(state)) {
+                ends.insert(name.clone());
+            }
+            for symbol in &alphabet {
+                let images : BTreeSet<State> = self.apply_deltas(Transition::new(symbol.clone(), subset.iter().cloned().collect()))
+                    .unwrap_or_default()
+                    .into_iter()
+                    .collect();
+                let image : State = names.name(&images);
+                if states.insert(image.clone()) {
+                    stack.push(images);
+                }
+                delta.insert(Transition::new(symbol.clone(), name.clone()), image);
+            }
+            subsets.insert(name, subset);
+        }
+        let fsm : FiniteStateMachine = FiniteStateMachine::new(states, self.get_alphabet().clone(), ends);
+        (DeterministicFiniteAutomaton::new(names.name(&start), delta, fsm), subsets)
+    }
+}
+
+/// Ajoute le couple (state, states) à l'antichaine s'il n'est pas subsumé par un couple existant
+///
+/// Les couples existants subsumés par le nouveau sont retirés. Retourne false si le couple était subsumé.
+fn add_to_antichain(antichain : &mut HashMap<State, Vec<BTreeSet<State>>>, state : &State, states : &BTreeSet<State>) -> bool {
+    let minimals : &mut Vec<BTreeSet<State>> = antichain.entry(state.clone()).or_default();
+    if minimals.iter().any(|minimal| minimal.is_subset(states)) {
         return false;
     }
+    minimals.retain(|minimal| !states.is_subset(minimal));
+    minimals.push(states.clone());
+    true
 }
 
 
 #[cfg(test)]
 mod test {
     use super::*;
+    use super::super::DeterministicFiniteAutomaton;
+    use super::super::test_words::{enumerate_words, word_to_string};
+    use serde_json::json;
 
     #[test]
     fn creation_partial_eq_copy() {      
@@ -412,4 +859,112 @@ mod test {
         assert_eq!(nfa.accept("b"), false);
         assert_eq!(nfa.accept("aabb"), true);
     }
+
+    #[test]
+    fn shortest_longest_distance() {
+        //0*001
+        let nfa2 : NonDeterministicFiniteAutomaton = NonDeterministicFiniteAutomaton::from_json_file("src/automates/NFA2.json");
+        assert_eq!(word_to_string(&nfa2.shortest_accepted_word().unwrap()), "001");
+        assert!(!nfa2.is_finite());
+        assert_eq!(nfa2.longest_accepted_word(), None);
+        let distances : HashMap<State, usize> = nfa2.distances_to_acceptance();
+        assert_eq!(distances.get(&State::from_str("q_0")), Some(&3));
+        assert_eq!(distances.get(&State::from_str("q_2")), Some(&1));
+
+        //langage fini {ab, abc}
+        let nfa3 : NonDeterministicFiniteAutomaton = NonDeterministicFiniteAutomaton::from_json_file("src/automates/NFA3.json");
+        assert!(nfa3.is_finite());
+        assert_eq!(word_to_string(&nfa3.shortest_accepted_word().unwrap()), "ab");
+        assert_eq!(word_to_string(&nfa3.longest_accepted_word().unwrap()), "abc");
+        let distances : HashMap<State, usize> = nfa3.distances_to_acceptance();
+        assert_eq!(distances.get(&State::from_str("p")), Some(&2));
+        assert_eq!(distances.get(&State::from_str("r")), Some(&2));
+    }
+
+    #[test]
+    fn inclusion() {
+        let nfa2 : NonDeterministicFiniteAutomaton = NonDeterministicFiniteAutomaton::from_json_file("src/automates/NFA2.json");
+        let nfa3 : NonDeterministicFiniteAutomaton = NonDeterministicFiniteAutomaton::from_json_file("src/automates/NFA3.json");
+        //(0|1)*1
+        let ends_with_one : NonDeterministicFiniteAutomaton = NonDeterministicFiniteAutomaton::from_json(json!({
+            "states" : ["x", "y"],
+            "alphabet" : ["0", "1"],
+            "ends" : ["y"],
+            "starts" : ["x"],
+            "delta" : [
+                {"state" : "x", "symbol" : "0", "images" : ["x"]},
+                {"state" : "x", "symbol" : "1", "images" : ["x", "y"]}
+            ]
+        }).as_object().unwrap());
+
+        assert_eq!(nfa3.is_subset_of(&nfa3), Ok(()));
+        assert_eq!(nfa2.is_subset_of(&ends_with_one), Ok(()));
+        assert_eq!(word_to_string(&ends_with_one.is_subset_of(&nfa2).unwrap_err()), "1");
+        assert_eq!(word_to_string(&nfa3.is_subset_of(&nfa2).unwrap_err()), "ab");
+    }
+
+    #[test]
+    fn determinize_set_names() {
+        //les ensembles {a,b} et {"a,b"} s'écrivent pareil
+        let nfa : NonDeterministicFiniteAutomaton = NonDeterministicFiniteAutomaton::from_json(json!({
+            "states" : ["p", "a", "b", "a,b", "f"],
+            "alphabet" : ["x", "y", "z"],
+            "ends" : ["f"],
+            "starts" : ["p"],
+            "delta" : [
+                {"state" : "p", "symbol" : "x", "images" : ["a", "b"]},
+                {"state" : "p", "symbol" : "y", "images" : ["a,b"]},
+                {"state" : "a,b", "symbol" : "z", "images" : ["f"]}
+            ]
+        }).as_object().unwrap());
+        let dfa : DeterministicFiniteAutomaton = nfa.determinize();
+        assert_eq!(dfa.get_states().len(), 5);
+        for word in enumerate_words("xyz", 3) {
+            assert_eq!(dfa.accept(&word), word == "yz", "{}", word);
+        }
+    }
+
+    /// Indique si `word` appartient à L(nfa)*, par programmation dynamique sur les découpages du mot
+    fn in_star(nfa : &NonDeterministicFiniteAutomaton, word : &str) -> bool {
+        let mut prefixes : Vec<bool> = vec![false; word.len() + 1];
+        prefixes[0] = true;
+        for end in 1..=word.len() {
+            prefixes[end] = (0..end).any(|begin| prefixes[begin] && nfa.accept(&word[begin..end]));
+        }
+        prefixes[word.len()]
+    }
+
+    #[test]
+    fn rational_operations() {
+        let nfa1 : NonDeterministicFiniteAutomaton = NonDeterministicFiniteAutomaton::from_json_file("src/automates/NFA1.json");
+        let nfa3 : NonDeterministicFiniteAutomaton = NonDeterministicFiniteAutomaton::from_json_file("src/automates/NFA3.json");
+        //les automates deterministes sont acceptés par conversion
+        let dfa1 : NonDeterministicFiniteAutomaton = DeterministicFiniteAutomaton::from_json_file("src/automates/DFA1.json").to_nfa();
+        let dfa3 : NonDeterministicFiniteAutomaton = DeterministicFiniteAutomaton::from_json_file("src/automates/DFA3.json").to_nfa();
+        let automata : Vec<&NonDeterministicFiniteAutomaton> = vec![&nfa1, &nfa3, &dfa1, &dfa3];
+
+        for a in &automata {
+            let reversed : NonDeterministicFiniteAutomaton = a.reverse();
+            let star : NonDeterministicFiniteAutomaton = a.star();
+            let plus : NonDeterministicFiniteAutomaton = a.plus();
+            for word in enumerate_words("abc", 6) {
+                let mirror : String = word.chars().rev().collect();
+                assert_eq!(reversed.accept(&word), a.accept(&mirror), "reverse {}", word);
+                assert_eq!(star.accept(&word), in_star(a, &word), "star {}", word);
+                let expected_plus : bool = (1..=word.len()).any(|split| a.accept(&word[..split]) && in_star(a, &word[split..]))
+                    || (a.accepts_empty_word() && in_star(a, &word));
+                assert_eq!(plus.accept(&word), expected_plus, "plus {}", word);
+            }
+            for b in &automata {
+                let union : NonDeterministicFiniteAutomaton = a.union_nfa(b);
+                let concat : NonDeterministicFiniteAutomaton = a.concat(b);
+                assert!(union.get_states().len() == a.get_states().len() + b.get_states().len());
+                for word in enumerate_words("abc", 6) {
+                    assert_eq!(union.accept(&word), a.accept(&word) || b.accept(&word), "union {}", word);
+                    let expected_concat : bool = (0..=word.len()).any(|split| a.accept(&word[..split]) && b.accept(&word[split..]));
+                    assert_eq!(concat.accept(&word), expected_concat, "concat {}", word);
+                }
+            }
+        }
+    }
 }
\ No newline at end of file
@@ -0,0 +1,317 @@
+use super::{State, Symbol};
+use std::collections::{BTreeSet, HashMap, HashSet, VecDeque};
+
+/// Liste d'adjacence d'un automate: pour chaque état, les couples (symbole, image) triés
+pub(crate) type Adjacency = HashMap<State, Vec<(Symbol, State)>>;
+
+/// Construit une liste d'adjacence triée à partir d'une liste d'arcs (etat, symbole, image)
+pub(crate) fn build_adjacency<I>(edges: I) -> Adjacency
+where
+    I: IntoIterator<Item = (State, Symbol, State)>,
+{
+    let mut adjacency: Adjacency = HashMap::new();
+    for (state, symbol, image) in edges {
+        adjacency.entry(state).or_default().push((symbol, image));
+    }
+    //le tri rend les parcours deterministes (plus petit mot dans l'ordre du symbole)
+    for successors in adjacency.values_mut() {
+        successors.sort();
+    }
+    adjacency
+}
+
+/// Inverse une liste d'adjacence (les arcs sont retournés)
+fn reverse(adjacency: &Adjacency) -> Adjacency {
+    build_adjacency(adjacency.iter().flat_map(|(state, successors)| {
+        successors
+            .iter()
+            .map(move |(symbol, image)| (image.clone(), symbol.clone(), state.clone()))
+    }))
+}
+
+/// Parcours en largeur depuis `starts`, renvoie le plus court mot menant à un état de `ends`
+pub(crate) fn shortest_word(
+    adjacency: &Adjacency,
+    starts: &HashSet<State>,
+    ends: &HashSet<State>,
+) -> Option<Vec<Symbol>> {
+    let mut sorted_starts: Vec<&State> = starts.iter().collect();
+    sorted_starts.sort();
+    //pour chaque etat visité, l'etat et le symbole qui ont permis de l'atteindre
+    let mut parents: HashMap<State, Option<(State, Symbol)>> = HashMap::new();
+    let mut queue: VecDeque<State> = VecDeque::new();
+    for start in sorted_starts {
+        parents.insert(start.clone(), None);
+        queue.push_back(start.clone());
+    }
+    while let Some(state) = queue.pop_front() {
+        if ends.contains(&state) {
+            //reconstruction du mot en remontant les parents
+            let mut word: Vec<Symbol> = Vec::new();
+            let mut current = state;
+            while let Some(Some((parent, symbol))) = parents.get(&current) {
+                word.push(symbol.clone());
+                current = parent.clone();
+            }
+            word.reverse();
+            return Some(word);
+        }
+        for (symbol, image) in adjacency.get(&state).into_iter().flatten() {
+            if !parents.contains_key(image) {
+                parents.insert(image.clone(), Some((state.clone(), symbol.clone())));
+                queue.push_back(image.clone());
+            }
+        }
+    }
+    None
+}
+
+/// Nombre minimal de symboles à lire depuis chaque état pour atteindre un état de `ends`
+///
+/// Les états qui ne peuvent pas atteindre `ends` n'apparaissent pas dans le résultat.
+pub(crate) fn distances_to(adjacency: &Adjacency, ends: &HashSet<State>) -> HashMap<State, usize> {
+    let reversed: Adjacency = reverse(adjacency);
+    let mut distances: HashMap<State, usize> = HashMap::new();
+    let mut queue: VecDeque<State> = VecDeque::new();
+    for end in ends {
+        distances.insert(end.clone(), 0);
+        queue.push_back(end.clone());
+    }
+    while let Some(state) = queue.pop_front() {
+        let distance: usize = distances[&state];
+        for (_, previous) in reversed.get(&state).into_iter().flatten() {
+            if !distances.contains_key(previous) {
+                distances.insert(previous.clone(), distance + 1);
+                queue.push_back(previous.clone());
+            }
+        }
+    }
+    distances
+}
+
+/// Couples d'états (p, r) depuis lesquels un même mot mène à la fois dans `ends` et dans `other_ends`
+///
+/// Le produit des deux automates est parcouru en arrière depuis les couples d'états finaux.
+pub(crate) fn product_coreachable(
+    adjacency: &Adjacency,
+    other_adjacency: &Adjacency,
+    ends: &HashSet<State>,
+    other_ends: &HashSet<State>,
+) -> HashSet<(State, State)> {
+    let reversed: Adjacency = reverse(adjacency);
+    let other_reversed: Adjacency = reverse(other_adjacency);
+    let mut visited: HashSet<(State, State)> = HashSet::new();
+    let mut stack: Vec<(State, State)> = Vec::new();
+    for end in ends {
+        for other_end in other_ends {
+            visited.insert((end.clone(), other_end.clone()));
+            stack.push((end.clone(), other_end.clone()));
+        }
+    }
+    while let Some((state, other_state)) = stack.pop() {
+        for (symbol, previous) in reversed.get(&state).into_iter().flatten() {
+            for (other_symbol, other_previous) in other_reversed.get(&other_state).into_iter().flatten() {
+                if symbol == other_symbol && visited.insert((previous.clone(), other_previous.clone())) {
+                    stack.push((previous.clone(), other_previous.clone()));
+                }
+            }
+        }
+    }
+    visited
+}
+
+/// Etats depuis lesquels un état de `ends` est accessible
+pub(crate) fn coreachable(adjacency: &Adjacency, ends: &HashSet<State>) -> HashSet<State> {
+    reachable(&reverse(adjacency), ends)
+}
+
+/// Etats accessibles depuis `starts`
+pub(crate) fn reachable(adjacency: &Adjacency, starts: &HashSet<State>) -> HashSet<State> {
+    let mut visited: HashSet<State> = starts.clone();
+    let mut stack: Vec<State> = starts.iter().cloned().collect();
+    while let Some(state) = stack.pop() {
+        for (_, image) in adjacency.get(&state).into_iter().flatten() {
+            if visited.insert(image.clone()) {
+                stack.push(image.clone());
+            }
+        }
+    }
+    visited
+}
+
+/// Etats utiles: accessibles depuis `starts` et co-accessibles depuis `ends`
+pub(crate) fn useful_states(
+    adjacency: &Adjacency,
+    starts: &HashSet<State>,
+    ends: &HashSet<State>,
+) -> HashSet<State> {
+    let coreachable: HashSet<State> = coreachable(adjacency, ends);
+    reachable(adjacency, starts)
+        .into_iter()
+        .filter(|state| coreachable.contains(state))
+        .collect()
+}
+
+/// Indique si le langage reconnu est fini, c'est à dire s'il n'existe aucun cycle entre états utiles
+pub(crate) fn is_finite(adjacency: &Adjacency, starts: &HashSet<State>, ends: &HashSet<State>) -> bool {
+    let useful: HashSet<State> = useful_states(adjacency, starts, ends);
+    //tri topologique (Kahn) restreint aux etats utiles
+    let mut in_degrees: HashMap<&State, usize> = useful.iter().map(|state| (state, 0)).collect();
+    for state in &useful {
+        for (_, image) in adjacency.get(state).into_iter().flatten() {
+            if let Some(degree) = in_degrees.get_mut(image) {
+                *degree += 1;
+            }
+        }
+    }
+    let mut queue: Vec<&State> = in_degrees
+        .iter()
+        .filter(|(_, degree)| **degree == 0)
+        .map(|(state, _)| *state)
+        .collect();
+    let mut visited: usize = 0;
+    while let Some(state) = queue.pop() {
+        visited += 1;
+        for (_, image) in adjacency.get(state).into_iter().flatten() {
+            if let Some(degree) = in_degrees.get_mut(image) {
+                *degree -= 1;
+                if *degree == 0 {
+                    queue.push(image);
+                }
+            }
+        }
+    }
+    visited == useful.len()
+}
+
+/// Plus long mot menant de `starts` à `ends`, si le langage est fini et non vide
+pub(crate) fn longest_word(
+    adjacency: &Adjacency,
+    starts: &HashSet<State>,
+    ends: &HashSet<State>,
+) -> Option<Vec<Symbol>> {
+    if !is_finite(adjacency, starts, ends) {
+        return None;
+    }
+    let useful: HashSet<State> = useful_states(adjacency, starts, ends);
+    //plus long mot depuis chaque etat utile, calculé par memoisation (le graphe utile est acyclique)
+    let mut memo: HashMap<State, Vec<Symbol>> = HashMap::new();
+    let mut sorted_starts: Vec<&State> = starts.iter().filter(|state| useful.contains(*state)).collect();
+    sorted_starts.sort();
+    let mut best: Option<Vec<Symbol>> = None;
+    for start in sorted_starts {
+        let word: Vec<Symbol> = longest_from(adjacency, &useful, ends, start, &mut memo);
+        if best.as_ref().is_none_or(|best| word.len() > best.len()) {
+            best = Some(word);
+        }
+    }
+    best
+}
+
+fn longest_from(
+    adjacency: &Adjacency,
+    useful: &HashSet<State>,
+    ends: &HashSet<State>,
+    state: &State,
+    memo: &mut HashMap<State, Vec<Symbol>>,
+) -> Vec<Symbol> {
+    if let Some(word) = memo.get(state) {
+        return word.clone();
+    }
+    //un etat utile non final a forcement un successeur utile, le mot vide n'est retenu que pour un etat final
+    let mut best: Option<Vec<Symbol>> = if ends.contains(state) { Some(Vec::new()) } else { None };
+    for (symbol, image) in adjacency.get(state).into_iter().flatten() {
+        if !useful.contains(image) {
+            continue;
+        }
+        let mut word: Vec<Symbol> = vec![symbol.clone()];
+        word.extend(longest_from(adjacency, useful, ends, image, memo));
+        if best.as_ref().is_none_or(|best| word.len() > best.len()) {
+            best = Some(word);
+        }
+    }
+    let best: Vec<Symbol> = best.unwrap_or_default();
+    memo.insert(state.clone(), best.clone());
+    best
+}
+
+/// Tous les mots menant de `starts` à `ends` dans l'ordre lexicographique, si le langage est fini
+pub(crate) fn all_words(
+    adjacency: &Adjacency,
+    starts: &HashSet<State>,
+    ends: &HashSet<State>,
+) -> Option<Vec<Vec<Symbol>>> {
+    if !is_finite(adjacency, starts, ends) {
+        return None;
+    }
+    let useful: HashSet<State> = useful_states(adjacency, starts, ends);
+    //parcours en profondeur des chemins utiles, en nombre fini puisque le graphe utile est acyclique
+    let mut words: BTreeSet<Vec<Symbol>> = BTreeSet::new();
+    let mut stack: Vec<(State, Vec<Symbol>)> = starts
+        .iter()
+        .filter(|state| useful.contains(*state))
+        .map(|state| (state.clone(), Vec::new()))
+        .collect();
+    while let Some((state, word)) = stack.pop() {
+        for (symbol, image) in adjacency.get(&state).into_iter().flatten() {
+            if useful.contains(image) {
+                let mut next: Vec<Symbol> = word.clone();
+                next.push(symbol.clone());
+                stack.push((image.clone(), next));
+            }
+        }
+        if ends.contains(&state) {
+            words.insert(word);
+        }
+    }
+    Some(words.into_iter().collect())
+}
+
+/// Composantes fortement connexes des états accessibles depuis `starts` (algorithme de Kosaraju)
+///
+/// Les composantes sont données dans un ordre topologique: aucun arc ne mène d'une composante à une précédente.
+pub(crate) fn strongly_connected_components(adjacency: &Adjacency, starts: &HashSet<State>) -> Vec<HashSet<State>> {
+    //premier parcours en profondeur: ordre de fin de traitement des états
+    let mut sorted_starts: Vec<&State> = starts.iter().collect();
+    sorted_starts.sort();
+    let mut visited: HashSet<State> = HashSet::new();
+    let mut finished: Vec<State> = Vec::new();
+    for start in sorted_starts {
+        if !visited.insert(start.clone()) {
+            continue;
+        }
+        let mut stack: Vec<(State, usize)> = vec![(start.clone(), 0)];
+        while let Some((state, index)) = stack.pop() {
+            match adjacency.get(&state).and_then(|successors| successors.get(index)) {
+                Some((_, image)) => {
+                    stack.push((state, index + 1));
+                    if visited.insert(image.clone()) {
+                        stack.push((image.clone(), 0));
+                    }
+                }
+                None => finished.push(state),
+            }
+        }
+    }
+    //second parcours sur le graphe inversé, par ordre de fin décroissant
+    let reversed: Adjacency = reverse(adjacency);
+    let mut assigned: HashSet<State> = HashSet::new();
+    let mut components: Vec<HashSet<State>> = Vec::new();
+    for root in finished.iter().rev() {
+        if !assigned.insert(root.clone()) {
+            continue;
+        }
+        let mut component: HashSet<State> = HashSet::from([root.clone()]);
+        let mut stack: Vec<State> = vec![root.clone()];
+        while let Some(state) = stack.pop() {
+            for (_, previous) in reversed.get(&state).into_iter().flatten() {
+                if visited.contains(previous) && assigned.insert(previous.clone()) {
+                    component.insert(previous.clone());
+                    stack.push(previous.clone());
+                }
+            }
+        }
+        components.push(component);
+    }
+    components
+}
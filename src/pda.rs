@@ -0,0 +1,388 @@
+use super::{State, Symbol, fresh_state, to_symbols};
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::fs;
+use serde_json::{Value, from_str, map::Map};
+
+/// Transition d'un automate à pile: (état, symbole lu ou `None` pour epsilon, sommet dépilé, symboles empilés, image)
+///
+/// Les symboles empilés sont donnés du sommet vers le fond: `["A", "Z"]` laisse `A` au sommet.
+pub type PdaArc = (State, Option<Symbol>, Symbol, Vec<Symbol>, State);
+
+/// Nombre de configurations explorées par défaut par `accept`
+const DEFAULT_LIMIT : usize = 100_000;
+
+/// Mode d'acceptation d'un automate à pile
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AcceptanceMode {
+    /// Le mot est accepté si sa lecture peut se terminer dans un état final
+    FinalState,
+    /// Le mot est accepté si sa lecture peut se terminer avec une pile vide
+    EmptyStack,
+}
+
+/// Configuration d'un automate à pile: l'état courant, le nombre de symboles lus et le contenu de la pile
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct PdaConfiguration {
+    state: State,
+    position: usize,
+    stack: Vec<Symbol>,
+}
+
+impl PdaConfiguration {
+    /// Retourne l'état courant
+    pub fn get_state(&self) -> &State {
+        &self.state
+    }
+
+    /// Retourne le nombre de symboles déjà lus
+    pub fn get_position(&self) -> usize {
+        self.position
+    }
+
+    /// Retourne la pile, du fond vers le sommet: le sommet est le dernier élément
+    pub fn get_stack(&self) -> &Vec<Symbol> {
+        &self.stack
+    }
+}
+
+/// Automate à pile non déterministe
+#[derive(Debug, Clone)]
+pub struct PushdownAutomaton {
+    start: State,
+    stack_start: Symbol,
+    arcs: Vec<PdaArc>,
+    ends: HashSet<State>,
+    states: HashSet<State>,
+    alphabet: HashSet<Symbol>,
+    stack_alphabet: HashSet<Symbol>,
+}
+
+impl PushdownAutomaton {
+    /// Créer un automate à pile
+    ///
+    /// Les états et les alphabets sont déduits des transitions.
+    ///
+    /// # Arguments
+    ///
+    /// * `_start` - L'état initial de l'automate
+    /// * `_stack_start` - Le symbole initialement dans la pile
+    /// * `_arcs` - Les transitions de l'automate
+    /// * `_ends` - Les états finaux, utilisés par le mode `AcceptanceMode::FinalState`
+    ///
+    /// # Return
+    ///
+    /// * `PushdownAutomaton` - L'automate à pile correspondant
+    ///
+    pub fn new(_start : State, _stack_start : Symbol, _arcs : Vec<PdaArc>, _ends : HashSet<State>) -> Self {
+        let mut states : HashSet<State> = _ends.clone();
+        states.insert(_start.clone());
+        let mut alphabet : HashSet<Symbol> = HashSet::new();
+        let mut stack_alphabet : HashSet<Symbol> = HashSet::from([_stack_start.clone()]);
+        for (state, input, pop, push, image) in &_arcs {
+            states.insert(state.clone());
+            states.insert(image.clone());
+            alphabet.extend(input.iter().cloned());
+            stack_alphabet.insert(pop.clone());
+            stack_alphabet.extend(push.iter().cloned());
+        }
+        PushdownAutomaton {
+            start: _start,
+            stack_start: _stack_start,
+            arcs: _arcs,
+            ends: _ends,
+            states,
+            alphabet,
+            stack_alphabet,
+        }
+    }
+
+    /// Créer un automate à pile depuis un contenu json
+    ///
+    /// # Examples
+    ///
+    /// Le contenu du json: chaque transition indique en plus le sommet dépilé et les symboles empilés,
+    /// du sommet vers le fond; un symbole lu `null` est une epsilon-transition
+    ///
+    /// ```json
+    /// {
+    ///     "start" : "q",
+    ///     "stack_start" : "Z",
+    ///     "ends" : ["f"],
+    ///     "delta" : [
+    ///         {
+    ///             "state" : "q",
+    ///             "input" : "(",
+    ///             "pop" : "Z",
+    ///             "push" : ["(", "Z"],
+    ///             "image" : "q"
+    ///         },
+    ///         {
+    ///             "state" : "q",
+    ///             "input" : null,
+    ///             "pop" : "Z",
+    ///             "push" : [],
+    ///             "image" : "f"
+    ///         }
+    ///     ]
+    /// }
+    /// ```
+    ///
+    /// # Return
+    ///
+    /// * `PushdownAutomaton` - L'automate à pile correspondant
+    ///
+    pub fn from_json(content_json: &Map<String, Value>) -> Self {
+        let name = |value : &Value| String::from(value.as_str().unwrap());
+        let start : State = State::new(name(content_json.get("start").unwrap()));
+        let stack_start : Symbol = Symbol::new(name(content_json.get("stack_start").unwrap()));
+        let mut arcs : Vec<PdaArc> = Vec::new();
+        for element_delta in content_json.get("delta").unwrap().as_array().unwrap() {
+            let arc_json : &Map<String, Value> = element_delta.as_object().unwrap();
+            arcs.push((
+                State::new(name(arc_json.get("state").unwrap())),
+                arc_json.get("input").unwrap().as_str().map(|symbol| Symbol::new(String::from(symbol))),
+                Symbol::new(name(arc_json.get("pop").unwrap())),
+                arc_json.get("push").unwrap().as_array().unwrap().iter().map(|symbol| Symbol::new(name(symbol))).collect(),
+                State::new(name(arc_json.get("image").unwrap())),
+            ));
+        }
+        let ends : HashSet<State> = content_json.get("ends").unwrap().as_array().unwrap().iter().map(|end| State::new(name(end))).collect();
+        PushdownAutomaton::new(start, stack_start, arcs, ends)
+    }
+
+    /// Créer un automate à pile depuis un chemin vers un fichier json, voir `from_json`
+    pub fn from_json_file(path: &str) -> Self {
+        let content_json: Value = {
+            // Charge le contenu du fichier en tant que String
+            let content : String = fs::read_to_string(path).unwrap();
+            // Parse le texte en structure Json
+            from_str::<Value>(&content).unwrap()
+        };
+        PushdownAutomaton::from_json(content_json.as_object().unwrap())
+    }
+
+    /// Retourne l'état initial de l'automate
+    pub fn get_start(&self) -> &State {
+        &self.start
+    }
+
+    /// Retourne le symbole initialement dans la pile
+    pub fn get_stack_start(&self) -> &Symbol {
+        &self.stack_start
+    }
+
+    /// Retourne les transitions de l'automate
+    pub fn get_arcs(&self) -> &Vec<PdaArc> {
+        &self.arcs
+    }
+
+    /// Retourne les états finaux de l'automate
+    pub fn get_ends(&self) -> &HashSet<State> {
+        &self.ends
+    }
+
+    /// Retourne les états de l'automate
+    pub fn get_states(&self) -> &HashSet<State> {
+        &self.states
+    }
+
+    /// Retourne l'alphabet d'entrée
+    pub fn get_alphabet(&self) -> &HashSet<Symbol> {
+        &self.alphabet
+    }
+
+    /// Retourne l'alphabet de pile
+    pub fn get_stack_alphabet(&self) -> &HashSet<Symbol> {
+        &self.stack_alphabet
+    }
+
+    /// Retourne les configurations atteintes en une transition depuis `configuration`
+    fn successors(&self, configuration : &PdaConfiguration, input : &[Symbol]) -> Vec<PdaConfiguration> {
+        let top : Option<&Symbol> = configuration.stack.last();
+        self.arcs.iter()
+            .filter(|(state, symbol, pop, _, _)| {
+                state == &configuration.state && Some(pop) == top
+                    && symbol.as_ref().is_none_or(|symbol| input.get(configuration.position) == Some(symbol))
+            })
+            .map(|(_, symbol, _, push, image)| {
+                let mut stack : Vec<Symbol> = configuration.stack.clone();
+                stack.pop();
+                stack.extend(push.iter().rev().cloned());
+                PdaConfiguration {
+                    state: image.clone(),
+                    position: configuration.position + usize::from(symbol.is_some()),
+                    stack,
+                }
+            })
+            .collect()
+    }
+
+    /// Cherche une exécution acceptante pour une suite de symboles, voir `trace`
+    pub fn trace_symbols(&self, input : &[Symbol], mode : AcceptanceMode, limit : usize) -> Result<Option<Vec<PdaConfiguration>>, String> {
+        let accepting = |configuration : &PdaConfiguration| configuration.position == input.len() && match mode {
+            AcceptanceMode::FinalState => self.ends.contains(&configuration.state),
+            AcceptanceMode::EmptyStack => configuration.stack.is_empty(),
+        };
+        let start : PdaConfiguration = PdaConfiguration { state: self.start.clone(), position: 0, stack: vec![self.stack_start.clone()] };
+        let mut parents : HashMap<PdaConfiguration, Option<PdaConfiguration>> = HashMap::from([(start.clone(), None)]);
+        let mut queue : VecDeque<PdaConfiguration> = VecDeque::from([start]);
+        let mut explored : usize = 0;
+        while let Some(configuration) = queue.pop_front() {
+            if accepting(&configuration) {
+                //reconstruction de l'exécution depuis la configuration acceptante
+                let mut trace : Vec<PdaConfiguration> = vec![configuration.clone()];
+                while let Some(Some(parent)) = parents.get(trace.last().unwrap()) {
+                    trace.push(parent.clone());
+                }
+                trace.reverse();
+                return Ok(Some(trace));
+            }
+            explored += 1;
+            if explored > limit {
+                return Err(format!("search limit of {} configurations reached", limit));
+            }
+            for next in self.successors(&configuration, input) {
+                if !parents.contains_key(&next) {
+                    parents.insert(next.clone(), Some(configuration.clone()));
+                    queue.push_back(next);
+                }
+            }
+        }
+        Ok(None)
+    }
+
+    /// Cherche, par un parcours en largeur borné des configurations, une exécution acceptant `word`
+    ///
+    /// Les configurations sont explorées de la plus proche à la plus éloignée de la configuration initiale:
+    /// l'exécution retournée est donc l'une des plus courtes. Les epsilon-transitions pouvant faire grandir
+    /// la pile indéfiniment, la recherche s'arrête après `limit` configurations.
+    ///
+    /// # Arguments
+    ///
+    /// * `word` - Le mot à lire, caractère par caractère
+    /// * `mode` - Le mode d'acceptation
+    /// * `limit` - Le nombre maximal de configurations explorées
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use automate::*;
+    /// fn main() {
+    ///     let pda : PushdownAutomaton = PushdownAutomaton::from_json_file("src/automates/PDA1.json");
+    ///     let trace : Vec<PdaConfiguration> = pda.trace("([])", AcceptanceMode::EmptyStack, 1000).unwrap().unwrap();
+    ///     assert_eq!(trace.len(), 6);
+    ///     assert_eq!(trace[2].get_stack(), &["Z", "(", "["].map(Symbol::from_str).to_vec());
+    ///     assert_eq!(trace[5].get_state(), &State::from_str("f"));
+    ///     assert_eq!(pda.trace("([)]", AcceptanceMode::EmptyStack, 1000), Ok(None));
+    /// }
+    /// ```
+    ///
+    /// # Return
+    ///
+    /// * `Result<Option<Vec<PdaConfiguration>>, String>` - Les configurations d'une exécution acceptante, `None` si le mot est refusé, une erreur si la limite est atteinte
+    ///
+    pub fn trace(&self, word : &str, mode : AcceptanceMode, limit : usize) -> Result<Option<Vec<PdaConfiguration>>, String> {
+        let input : Vec<Symbol> = to_symbols(word);
+        self.trace_symbols(&input, mode, limit)
+    }
+
+    /// indique si un mot est accepté par l'automate dans le mode `mode`
+    ///
+    /// La recherche est bornée à 100 000 configurations, au-delà le mot est considéré comme refusé; voir `trace`.
+    pub fn accept(&self, word : &str, mode : AcceptanceMode) -> bool {
+        matches!(self.trace(word, mode, DEFAULT_LIMIT), Ok(Some(_)))
+    }
+
+    /// Retourne un automate acceptant par pile vide le langage que self accepte par état final
+    ///
+    /// Un nouveau symbole de fond empêche la pile de se vider avant la fin; depuis chaque état final, un nouvel
+    /// état `drain` peut ensuite vider toute la pile par des epsilon-transitions.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use automate::*;
+    /// fn main() {
+    ///     let pda : PushdownAutomaton = PushdownAutomaton::from_json_file("src/automates/PDA1.json");
+    ///     let emptying : PushdownAutomaton = pda.to_empty_stack();
+    ///     assert!(emptying.accept("([])()", AcceptanceMode::EmptyStack));
+    ///     assert!(!emptying.accept("([)]", AcceptanceMode::EmptyStack));
+    /// }
+    /// ```
+    ///
+    /// # Return
+    ///
+    /// * `PushdownAutomaton` - L'automate acceptant par pile vide
+    ///
+    pub fn to_empty_stack(&self) -> PushdownAutomaton {
+        let stack_names : HashSet<State> = self.stack_alphabet.iter().map(|symbol| State::new(symbol.get_value().clone())).collect();
+        let bottom : Symbol = Symbol::new(fresh_state(&stack_names, "#").get_name().clone());
+        let start : State = fresh_state(&self.states, "start");
+        let mut states : HashSet<State> = self.states.clone();
+        states.insert(start.clone());
+        let drain : State = fresh_state(&states, "drain");
+        let mut stack_alphabet : Vec<Symbol> = self.stack_alphabet.iter().cloned().collect();
+        stack_alphabet.push(bottom.clone());
+        stack_alphabet.sort();
+
+        let mut arcs : Vec<PdaArc> = vec![(start.clone(), None, bottom.clone(), vec![self.stack_start.clone(), bottom.clone()], self.start.clone())];
+        arcs.extend(self.arcs.iter().cloned());
+        let mut ends : Vec<&State> = self.ends.iter().collect();
+        ends.sort();
+        for end in ends.into_iter().chain([&drain]) {
+            for symbol in &stack_alphabet {
+                arcs.push((end.clone(), None, symbol.clone(), Vec::new(), drain.clone()));
+            }
+        }
+        PushdownAutomaton::new(start, bottom, arcs, HashSet::new())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use super::super::test_words::{enumerate_words, balanced};
+    use serde_json::json;
+
+    #[test]
+    fn balanced_brackets() {
+        let pda : PushdownAutomaton = PushdownAutomaton::from_json_file("src/automates/PDA1.json");
+        assert_eq!(pda.get_stack_alphabet().len(), 3);
+        for word in enumerate_words("()[]", 6) {
+            assert_eq!(pda.accept(&word, AcceptanceMode::FinalState), balanced(&word), "{}", word);
+            assert_eq!(pda.accept(&word, AcceptanceMode::EmptyStack), balanced(&word), "{}", word);
+        }
+    }
+
+    #[test]
+    fn acceptance_modes_and_limit() {
+        //a^n b^n: l'état final est atteint avant que la pile soit vide
+        let pda : PushdownAutomaton = PushdownAutomaton::from_json(json!({
+            "start" : "p",
+            "stack_start" : "Z",
+            "ends" : ["f"],
+            "delta" : [
+                { "state" : "p", "input" : "a", "pop" : "Z", "push" : ["A", "Z"], "image" : "p" },
+                { "state" : "p", "input" : "a", "pop" : "A", "push" : ["A", "A"], "image" : "p" },
+                { "state" : "p", "input" : "b", "pop" : "A", "push" : [], "image" : "r" },
+                { "state" : "r", "input" : "b", "pop" : "A", "push" : [], "image" : "r" },
+                { "state" : "r", "input" : null, "pop" : "Z", "push" : ["Z"], "image" : "f" }
+            ]
+        }).as_object().unwrap());
+        assert!(pda.accept("aabb", AcceptanceMode::FinalState));
+        assert!(!pda.accept("aabb", AcceptanceMode::EmptyStack));
+        assert!(!pda.accept("aab", AcceptanceMode::FinalState));
+        let trace : Vec<PdaConfiguration> = pda.trace("ab", AcceptanceMode::FinalState, 500).unwrap().unwrap();
+        let positions : Vec<usize> = trace.iter().map(|configuration| configuration.get_position()).collect();
+        assert_eq!(positions, vec![0, 1, 2, 2]);
+
+        //une epsilon-boucle qui empile sans fin rend la recherche infinie quand le mot est refusé
+        let growing : PushdownAutomaton = PushdownAutomaton::new(
+            State::from_str("p"),
+            Symbol::from_str("Z"),
+            vec![(State::from_str("p"), None, Symbol::from_str("Z"), vec![Symbol::from_str("Z"), Symbol::from_str("Z")], State::from_str("p"))],
+            HashSet::new(),
+        );
+        assert!(growing.trace("", AcceptanceMode::FinalState, 500).is_err());
+    }
+}
@@ -0,0 +1,310 @@
+use super::{Transition, State, Symbol, Rng, Semiring, Probability, WeightedAutomaton};
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use serde_json::{Value, from_str, map::Map};
+
+/// Ecart toléré entre 1 et la somme des probabilités sortant d'un état
+const TOLERANCE : f64 = 1e-6;
+
+/// Transition probabiliste: (symbole produit, image, probabilité)
+pub type ProbabilisticArc = (Symbol, State, f64);
+
+/// Automate probabiliste: depuis chaque état, une transition est tirée selon sa probabilité
+///
+/// Les probabilités des transitions sortant d'un même état somment à 1: l'automate ne s'arrête jamais et
+/// engendre des mots infinis. Oublier les symboles donne une chaîne de Markov sur les états.
+#[derive(Debug, Clone)]
+pub struct ProbabilisticAutomaton {
+    start: State,
+    delta: HashMap<State, Vec<ProbabilisticArc>>,
+    states: HashSet<State>,
+    alphabet: HashSet<Symbol>,
+}
+
+impl ProbabilisticAutomaton {
+    /// Créer un automate probabiliste, en vérifiant que les probabilités sortant de chaque état somment à 1
+    ///
+    /// # Arguments
+    ///
+    /// * `_start` - L'état initial de l'automate
+    /// * `_delta` - Les transitions sortant de chaque état
+    ///
+    /// # Return
+    ///
+    /// * `Result<ProbabilisticAutomaton, String>` - L'automate, une erreur si une probabilité est hors de [0, 1] ou si les probabilités sortant d'un état ne somment pas à 1
+    ///
+    pub fn new(_start : State, _delta : HashMap<State, Vec<ProbabilisticArc>>) -> Result<Self, String> {
+        let mut states : HashSet<State> = HashSet::from([_start.clone()]);
+        let mut alphabet : HashSet<Symbol> = HashSet::new();
+        for (state, arcs) in &_delta {
+            states.insert(state.clone());
+            for (symbol, image, probability) in arcs {
+                if !(0.0..=1.0).contains(probability) {
+                    return Err(format!("probability {} of a transition leaving state {} is not between 0 and 1", probability, state.get_name()));
+                }
+                states.insert(image.clone());
+                alphabet.insert(symbol.clone());
+            }
+        }
+        let mut sorted_states : Vec<&State> = states.iter().collect();
+        sorted_states.sort();
+        for state in sorted_states {
+            let total : f64 = _delta.get(state).into_iter().flatten().fold(0.0, |total, (_, _, probability)| total + probability);
+            if (total - 1.0).abs() > TOLERANCE {
+                return Err(format!("probabilities leaving state {} sum to {} instead of 1", state.get_name(), total));
+            }
+        }
+        Ok(ProbabilisticAutomaton {
+            start: _start,
+            delta: _delta,
+            states,
+            alphabet,
+        })
+    }
+
+    /// Créer un automate probabiliste depuis un contenu json
+    ///
+    /// # Examples
+    ///
+    /// Le contenu du json
+    ///
+    /// ```json
+    /// {
+    ///     "start" : "A",
+    ///     "delta" : [
+    ///         {
+    ///             "state" : "A",
+    ///             "symbol" : "a",
+    ///             "image" : "A",
+    ///             "probability" : 0.5
+    ///         }
+    ///     ]
+    /// }
+    /// ```
+    ///
+    /// # Return
+    ///
+    /// * `Result<ProbabilisticAutomaton, String>` - L'automate, une erreur si le json est mal formé ou si les probabilités ne somment pas à 1, voir `new`
+    ///
+    pub fn from_json(content_json: &Map<String, Value>) -> Result<Self, String> {
+        let text = |value : Option<&Value>, key : &str| value.and_then(|value| value.as_str()).map(String::from).ok_or_else(|| format!("missing string {}", key));
+        let start : State = State::new(text(content_json.get("start"), "start")?);
+        let mut delta : HashMap<State, Vec<ProbabilisticArc>> = HashMap::new();
+        let elements : &Vec<Value> = content_json.get("delta").and_then(|delta| delta.as_array()).ok_or_else(|| String::from("missing array delta"))?;
+        for (index, element_delta) in elements.iter().enumerate() {
+            let transition_json : &Map<String, Value> = element_delta.as_object().ok_or_else(|| format!("transition {} is not an object", index))?;
+            let state : State = State::new(text(transition_json.get("state"), "state")?);
+            let symbol : Symbol = Symbol::new(text(transition_json.get("symbol"), "symbol")?);
+            let image : State = State::new(text(transition_json.get("image"), "image")?);
+            let probability : f64 = transition_json.get("probability").and_then(|probability| probability.as_f64()).ok_or_else(|| format!("transition {} has no numeric probability", index))?;
+            delta.entry(state).or_default().push((symbol, image, probability));
+        }
+        ProbabilisticAutomaton::new(start, delta)
+    }
+
+    /// Créer un automate probabiliste depuis un chemin vers un fichier json, voir `from_json`
+    pub fn from_json_file(path: &str) -> Result<Self, String> {
+        let content : String = fs::read_to_string(path).map_err(|error| format!("{}: {}", path, error))?;
+        let content_json : Value = from_str::<Value>(&content).map_err(|error| format!("{}: {}", path, error))?;
+        ProbabilisticAutomaton::from_json(content_json.as_object().ok_or_else(|| format!("{}: expected a json object", path))?)
+    }
+
+    /// Retourne l'état initial de l'automate
+    pub fn get_start(&self) -> &State {
+        &self.start
+    }
+
+    /// Retourne les transitions sortant de chaque état
+    pub fn get_delta(&self) -> &HashMap<State, Vec<ProbabilisticArc>> {
+        &self.delta
+    }
+
+    /// Retourne les états de l'automate
+    pub fn get_states(&self) -> &HashSet<State> {
+        &self.states
+    }
+
+    /// Retourne l'alphabet de l'automate
+    pub fn get_alphabet(&self) -> &HashSet<Symbol> {
+        &self.alphabet
+    }
+
+    /// Retourne l'automate pondéré sur le semi-anneau des probabilités, dont tous les états sont finaux avec poids 1
+    pub fn to_weighted(&self) -> WeightedAutomaton<Probability> {
+        let mut delta : HashMap<Transition<State>, Vec<(State, Probability)>> = HashMap::new();
+        for (state, arcs) in &self.delta {
+            for (symbol, image, probability) in arcs {
+                delta.entry(Transition::new(symbol.clone(), state.clone())).or_default().push((image.clone(), Probability(*probability)));
+            }
+        }
+        let ends : HashMap<State, Probability> = self.states.iter().map(|state| (state.clone(), Probability::one())).collect();
+        WeightedAutomaton::new(HashMap::from([(self.start.clone(), Probability::one())]), delta, ends)
+    }
+
+    /// Retourne la probabilité que les premiers symboles produits soient ceux de `word`, lu caractère par caractère
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use automate::*;
+    /// fn main() {
+    ///     let automaton : ProbabilisticAutomaton = ProbabilisticAutomaton::from_json_file("src/automates/PA1.json").unwrap();
+    ///     assert_eq!(automaton.probability(""), 1.0);
+    ///     assert_eq!(automaton.probability("ab"), 0.25);
+    ///     assert_eq!(automaton.probability("bb"), 0.375);
+    /// }
+    /// ```
+    ///
+    pub fn probability(&self, word : &str) -> f64 {
+        self.to_weighted().weight(word).0
+    }
+
+    /// Tire une exécution aléatoire de `length` transitions
+    ///
+    /// # Arguments
+    ///
+    /// * `length` - Le nombre de transitions tirées
+    /// * `rng` - Le générateur pseudo-aléatoire utilisé
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use automate::*;
+    /// fn main() {
+    ///     let automaton : ProbabilisticAutomaton = ProbabilisticAutomaton::from_json_file("src/automates/PA1.json").unwrap();
+    ///     let (word, states) : (Vec<Symbol>, Vec<State>) = automaton.sample(10, &mut Rng::new(7));
+    ///     assert_eq!(word.len(), 10);
+    ///     assert_eq!(states.len(), 11);
+    ///     assert_eq!(automaton.sample(10, &mut Rng::new(7)), (word, states));
+    /// }
+    /// ```
+    ///
+    /// # Return
+    ///
+    /// * `(Vec<Symbol>, Vec<State>)` - Les symboles produits et les états traversés, état initial compris
+    ///
+    pub fn sample(&self, length : usize, rng : &mut Rng) -> (Vec<Symbol>, Vec<State>) {
+        let mut word : Vec<Symbol> = Vec::new();
+        let mut states : Vec<State> = vec![self.start.clone()];
+        for _ in 0..length {
+            let arcs : &Vec<ProbabilisticArc> = &self.delta[states.last().unwrap()];
+            let mut draw : f64 = rng.next_f64();
+            //la dernière transition absorbe les erreurs d'arrondi
+            let (symbol, image, _) : &ProbabilisticArc = arcs.iter()
+                .find(|(_, _, probability)| {
+                    draw -= probability;
+                    draw < 0.0
+                })
+                .unwrap_or_else(|| arcs.iter().rfind(|(_, _, probability)| *probability > 0.0).unwrap());
+            word.push(symbol.clone());
+            states.push(image.clone());
+        }
+        (word, states)
+    }
+
+    /// Calcule la distribution stationnaire de la chaîne de Markov sur les états
+    ///
+    /// La distribution `π` vérifie `π P = π` et somme à 1, `P` étant la matrice des probabilités de passage d'un
+    /// état à un autre. Le système est résolu par la méthode de Gauss, une équation étant remplacée par la
+    /// normalisation.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use automate::*;
+    /// fn main() {
+    ///     let automaton : ProbabilisticAutomaton = ProbabilisticAutomaton::from_json_file("src/automates/PA1.json").unwrap();
+    ///     let stationary = automaton.stationary_distribution().unwrap();
+    ///     assert!((stationary[&State::from_str("A")] - 1.0 / 3.0).abs() < 1e-9);
+    ///     assert!((stationary[&State::from_str("B")] - 2.0 / 3.0).abs() < 1e-9);
+    /// }
+    /// ```
+    ///
+    /// # Return
+    ///
+    /// * `Result<HashMap<State, f64>, String>` - La probabilité stationnaire de chaque état, une erreur si elle n'est pas unique
+    ///
+    pub fn stationary_distribution(&self) -> Result<HashMap<State, f64>, String> {
+        let mut states : Vec<&State> = self.states.iter().collect();
+        states.sort();
+        let size : usize = states.len();
+        let indices : HashMap<&State, usize> = states.iter().enumerate().map(|(index, state)| (*state, index)).collect();
+        //système (P^T - I) π = 0, la dernière équation devenant la somme des π égale à 1
+        let mut matrix : Vec<Vec<f64>> = vec![vec![0.0; size + 1]; size];
+        for (state, arcs) in &self.delta {
+            for (_, image, probability) in arcs {
+                matrix[indices[image]][indices[state]] += probability;
+            }
+        }
+        for (index, row) in matrix.iter_mut().enumerate() {
+            row[index] -= 1.0;
+        }
+        matrix[size - 1] = vec![1.0; size + 1];
+        for column in 0..size {
+            let pivot : usize = (column..size).max_by(|first, second| matrix[*first][column].abs().total_cmp(&matrix[*second][column].abs())).unwrap();
+            if matrix[pivot][column].abs() < 1e-12 {
+                return Err(String::from("the chain has several stationary distributions"));
+            }
+            matrix.swap(column, pivot);
+            let pivot_row : Vec<f64> = matrix[column].clone();
+            for (row, values) in matrix.iter_mut().enumerate() {
+                if row != column {
+                    let factor : f64 = values[column] / pivot_row[column];
+                    for (value, pivot_value) in values.iter_mut().zip(&pivot_row).skip(column) {
+                        *value -= factor * pivot_value;
+                    }
+                }
+            }
+        }
+        Ok(states.iter().enumerate().map(|(index, state)| ((*state).clone(), matrix[index][size] / matrix[index][index])).collect())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use serde_json::json;
+    use super::super::test_words::enumerate_words;
+
+    #[test]
+    fn probabilities_and_sampling() {
+        let automaton : ProbabilisticAutomaton = ProbabilisticAutomaton::from_json_file("src/automates/PA1.json").unwrap();
+        //les mots d'une même longueur se partagent toute la probabilité
+        for length in 0..5 {
+            let total : f64 = enumerate_words("ab", length).iter().filter(|word| word.len() == length).map(|word| automaton.probability(word)).sum();
+            assert!((total - 1.0).abs() < 1e-9);
+        }
+        //en régime stationnaire, a est produit avec probabilité 1/3 * 1/2 + 2/3 * 1/4 = 1/3
+        let (word, _) : (Vec<Symbol>, Vec<State>) = automaton.sample(20_000, &mut Rng::new(2024));
+        let frequency : f64 = word.iter().filter(|symbol| symbol.get_value() == "a").count() as f64 / word.len() as f64;
+        assert!((frequency - 1.0 / 3.0).abs() < 0.02, "{}", frequency);
+        assert_ne!(automaton.sample(50, &mut Rng::new(1)), automaton.sample(50, &mut Rng::new(2)));
+    }
+
+    #[test]
+    fn validation() {
+        let unbalanced = json!({"start": "A", "delta": [
+            {"state": "A", "symbol": "a", "image": "B", "probability": 1.0},
+            {"state": "B", "symbol": "a", "image": "A", "probability": 0.5},
+            {"state": "B", "symbol": "b", "image": "B", "probability": 0.4}
+        ]});
+        assert_eq!(ProbabilisticAutomaton::from_json(unbalanced.as_object().unwrap()).unwrap_err(), "probabilities leaving state B sum to 0.9 instead of 1");
+        let dead_end = json!({"start": "A", "delta": [{"state": "A", "symbol": "a", "image": "B", "probability": 1.0}]});
+        assert_eq!(ProbabilisticAutomaton::from_json(dead_end.as_object().unwrap()).unwrap_err(), "probabilities leaving state B sum to 0 instead of 1");
+        let negative = json!({"start": "A", "delta": [{"state": "A", "symbol": "a", "image": "A", "probability": -1}]});
+        assert!(ProbabilisticAutomaton::from_json(negative.as_object().unwrap()).is_err());
+        let rounded = json!({"start": "A", "delta": [
+            {"state": "A", "symbol": "a", "image": "A", "probability": 0.3333333},
+            {"state": "A", "symbol": "b", "image": "A", "probability": 0.6666667}
+        ]});
+        assert!(ProbabilisticAutomaton::from_json(rounded.as_object().unwrap()).is_ok());
+        //deux classes fermées: la distribution stationnaire n'est pas unique
+        let split = json!({"start": "A", "delta": [
+            {"state": "A", "symbol": "a", "image": "B", "probability": 0.5},
+            {"state": "A", "symbol": "b", "image": "C", "probability": 0.5},
+            {"state": "B", "symbol": "a", "image": "B", "probability": 1.0},
+            {"state": "C", "symbol": "b", "image": "C", "probability": 1.0}
+        ]});
+        assert!(ProbabilisticAutomaton::from_json(split.as_object().unwrap()).unwrap().stationary_distribution().is_err());
+    }
+}
@@ -0,0 +1,247 @@
+use super::{Transition, State, Symbol, FiniteStateMachine, DeterministicFiniteAutomaton, NonDeterministicFiniteAutomaton, fresh_state};
+use super::paths::{self, Adjacency};
+use std::collections::HashSet;
+
+impl DeterministicFiniteAutomaton {
+    /// Quotient à gauche du langage par un mot: les mots `v` tels que `word`·`v` est accepté
+    ///
+    /// L'automate retourné est le même avec pour état initial l'état atteint en lisant `word`. Si la lecture
+    /// bloque, l'état initial est un nouvel état sans transition et le langage est vide.
+    ///
+    /// # Arguments
+    ///
+    /// * `word` - Le préfixe déjà lu
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use automate::*;
+    /// fn main() {
+    ///     let dfa : DeterministicFiniteAutomaton = DeterministicFiniteAutomaton::from_json_file("src/automates/DFA3.json");
+    ///     //{b, ab, abc} privé du préfixe "a": {b, bc}
+    ///     let derivative : DeterministicFiniteAutomaton = dfa.derivative("a");
+    ///     assert!(derivative.accept("bc"));
+    ///     assert!(!derivative.accept("ab"));
+    /// }
+    /// ```
+    ///
+    /// # Return
+    ///
+    /// * `DeterministicFiniteAutomaton` - L'automate reconnaissant le quotient à gauche
+    ///
+    pub fn derivative(&self, word : &str) -> DeterministicFiniteAutomaton {
+        let mut state : State = self.get_start().clone();
+        for lettre in word.chars() {
+            match self.apply_delta(Transition::new(Symbol::new(String::from(lettre)), state.clone())) {
+                Some(image) => state = image.clone(),
+                None => {
+                    //aucun mot ne commence par word: on part d'un etat mort
+                    let dead : State = fresh_state(self.get_states(), "dead");
+                    let mut states : HashSet<State> = self.get_states().clone();
+                    states.insert(dead.clone());
+                    let fsm : FiniteStateMachine = FiniteStateMachine::new(states, self.get_alphabet().clone(), self.get_ends().clone());
+                    return DeterministicFiniteAutomaton::new(dead, self.get_delta().clone(), fsm);
+                }
+            }
+        }
+        DeterministicFiniteAutomaton::new(state, self.get_delta().clone(), self.fsm_clone())
+    }
+
+    /// Quotient à droite par le langage de `other`: les mots `u` tels qu'il existe `v` accepté par `other` avec `u`·`v` accepté par self
+    ///
+    /// Seuls les états finaux changent: un état devient final si un mot de `other` y mène à un état final.
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - L'automate reconnaissant le langage des suffixes retirés
+    ///
+    /// # Return
+    ///
+    /// * `DeterministicFiniteAutomaton` - L'automate reconnaissant le quotient à droite
+    ///
+    pub fn right_quotient(&self, other : &DeterministicFiniteAutomaton) -> DeterministicFiniteAutomaton {
+        let starts : HashSet<State> = HashSet::from([other.get_start().clone()]);
+        let ends : HashSet<State> = right_quotient_ends(&self.adjacency(), self.get_ends(), &other.adjacency(), &starts, other.get_ends());
+        let fsm : FiniteStateMachine = FiniteStateMachine::new(self.get_states().clone(), self.get_alphabet().clone(), ends);
+        DeterministicFiniteAutomaton::new(self.get_start().clone(), self.get_delta().clone(), fsm)
+    }
+
+    /// Clôture par préfixe: les préfixes des mots acceptés
+    ///
+    /// Tout état depuis lequel un état final est accessible devient final.
+    ///
+    /// # Return
+    ///
+    /// * `DeterministicFiniteAutomaton` - L'automate reconnaissant les préfixes du langage
+    ///
+    pub fn prefix_closure(&self) -> DeterministicFiniteAutomaton {
+        let ends : HashSet<State> = paths::coreachable(&self.adjacency(), self.get_ends());
+        let fsm : FiniteStateMachine = FiniteStateMachine::new(self.get_states().clone(), self.get_alphabet().clone(), ends);
+        DeterministicFiniteAutomaton::new(self.get_start().clone(), self.get_delta().clone(), fsm)
+    }
+
+    /// Clôture par suffixe: les suffixes des mots acceptés
+    ///
+    /// La construction rend l'automate non déterministe, le résultat est donc déterminisé.
+    pub fn suffix_closure(&self) -> DeterministicFiniteAutomaton {
+        self.to_nfa().suffix_closure().determinize()
+    }
+
+    /// Clôture par facteur: les facteurs (sous-mots contigus) des mots acceptés
+    ///
+    /// La construction rend l'automate non déterministe, le résultat est donc déterminisé.
+    pub fn factor_closure(&self) -> DeterministicFiniteAutomaton {
+        self.to_nfa().factor_closure().determinize()
+    }
+
+    /// Retourne une copie de la machine à état fini de l'automate
+    fn fsm_clone(&self) -> FiniteStateMachine {
+        FiniteStateMachine::new(self.get_states().clone(), self.get_alphabet().clone(), self.get_ends().clone())
+    }
+}
+
+impl NonDeterministicFiniteAutomaton {
+    /// Quotient à gauche du langage par un mot: les mots `v` tels que `word`·`v` est accepté
+    ///
+    /// Les états initiaux de l'automate retourné sont ceux atteints en lisant `word`.
+    ///
+    /// # Arguments
+    ///
+    /// * `word` - Le préfixe déjà lu
+    ///
+    /// # Return
+    ///
+    /// * `NonDeterministicFiniteAutomaton` - L'automate reconnaissant le quotient à gauche
+    ///
+    pub fn derivative(&self, word : &str) -> NonDeterministicFiniteAutomaton {
+        let mut currents : HashSet<State> = self.get_starts().clone();
+        for lettre in word.chars() {
+            currents = self.apply_deltas(Transition::new(Symbol::new(String::from(lettre)), currents)).unwrap_or_default();
+        }
+        NonDeterministicFiniteAutomaton::new(currents, self.get_delta().clone(), self.fsm_clone())
+    }
+
+    /// Quotient à droite par le langage de `other`: les mots `u` tels qu'il existe `v` accepté par `other` avec `u`·`v` accepté par self
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - L'automate reconnaissant le langage des suffixes retirés
+    ///
+    /// # Return
+    ///
+    /// * `NonDeterministicFiniteAutomaton` - L'automate reconnaissant le quotient à droite
+    ///
+    pub fn right_quotient(&self, other : &NonDeterministicFiniteAutomaton) -> NonDeterministicFiniteAutomaton {
+        let ends : HashSet<State> = right_quotient_ends(&self.adjacency(), self.get_ends(), &other.adjacency(), other.get_starts(), other.get_ends());
+        self.with_starts_ends(self.get_starts().clone(), ends)
+    }
+
+    /// Clôture par préfixe: les préfixes des mots acceptés
+    pub fn prefix_closure(&self) -> NonDeterministicFiniteAutomaton {
+        let ends : HashSet<State> = paths::coreachable(&self.adjacency(), self.get_ends());
+        self.with_starts_ends(self.get_starts().clone(), ends)
+    }
+
+    /// Clôture par suffixe: les suffixes des mots acceptés
+    ///
+    /// Tout état accessible devient initial.
+    pub fn suffix_closure(&self) -> NonDeterministicFiniteAutomaton {
+        let starts : HashSet<State> = paths::reachable(&self.adjacency(), self.get_starts());
+        self.with_starts_ends(starts, self.get_ends().clone())
+    }
+
+    /// Clôture par facteur: les facteurs (sous-mots contigus) des mots acceptés
+    ///
+    /// Tout état accessible devient initial et tout état co-accessible devient final.
+    pub fn factor_closure(&self) -> NonDeterministicFiniteAutomaton {
+        self.suffix_closure().prefix_closure()
+    }
+
+    /// Retourne une copie de l'automate avec d'autres états initiaux et finaux
+    fn with_starts_ends(&self, starts : HashSet<State>, ends : HashSet<State>) -> NonDeterministicFiniteAutomaton {
+        let fsm : FiniteStateMachine = FiniteStateMachine::new(self.get_states().clone(), self.get_alphabet().clone(), ends);
+        NonDeterministicFiniteAutomaton::new(starts, self.get_delta().clone(), fsm)
+    }
+
+    /// Retourne une copie de la machine à état fini de l'automate
+    fn fsm_clone(&self) -> FiniteStateMachine {
+        FiniteStateMachine::new(self.get_states().clone(), self.get_alphabet().clone(), self.get_ends().clone())
+    }
+}
+
+/// Etats finaux du quotient à droite: les états depuis lesquels un mot du second automate mène à un état final
+fn right_quotient_ends(adjacency : &Adjacency, ends : &HashSet<State>, other_adjacency : &Adjacency, other_starts : &HashSet<State>, other_ends : &HashSet<State>) -> HashSet<State> {
+    let coreachable : HashSet<(State, State)> = paths::product_coreachable(adjacency, other_adjacency, ends, other_ends);
+    let mut result : HashSet<State> = HashSet::new();
+    for (state, other_state) in coreachable {
+        if other_starts.contains(&other_state) {
+            result.insert(state);
+        }
+    }
+    result
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use super::super::test_words::enumerate_words;
+
+    #[test]
+    fn derivatives_and_quotients() {
+        let dfa1 : DeterministicFiniteAutomaton = DeterministicFiniteAutomaton::from_json_file("src/automates/DFA1.json");
+        let dfa3 : DeterministicFiniteAutomaton = DeterministicFiniteAutomaton::from_json_file("src/automates/DFA3.json");
+        let nfa3 : NonDeterministicFiniteAutomaton = NonDeterministicFiniteAutomaton::from_json_file("src/automates/NFA3.json");
+        let words : Vec<String> = enumerate_words("abc", 5);
+        //suffixes possibles: DFA3 est fini et ses mots sont de longueur au plus 3
+        let suffixes : Vec<String> = enumerate_words("abc", 3);
+
+        for prefix in ["", "a", "ab", "b", "ba", "c"] {
+            let dfa_derivative : DeterministicFiniteAutomaton = dfa1.derivative(prefix);
+            let nfa_derivative : NonDeterministicFiniteAutomaton = nfa3.derivative(prefix);
+            for word in &words {
+                assert_eq!(dfa_derivative.accept(word), dfa1.accept(&format!("{}{}", prefix, word)));
+                assert_eq!(nfa_derivative.accept(word), nfa3.accept(&format!("{}{}", prefix, word)));
+            }
+        }
+
+        let dfa_quotient : DeterministicFiniteAutomaton = dfa1.right_quotient(&dfa3);
+        let nfa_quotient : NonDeterministicFiniteAutomaton = dfa1.to_nfa().right_quotient(&nfa3);
+        for word in &words {
+            let expected : bool = suffixes.iter().any(|suffix| dfa3.accept(suffix) && dfa1.accept(&format!("{}{}", word, suffix)));
+            assert_eq!(dfa_quotient.accept(word), expected, "{}", word);
+            let expected : bool = suffixes.iter().any(|suffix| nfa3.accept(suffix) && dfa1.accept(&format!("{}{}", word, suffix)));
+            assert_eq!(nfa_quotient.accept(word), expected, "{}", word);
+        }
+    }
+
+    #[test]
+    fn closures() {
+        let dfa1 : DeterministicFiniteAutomaton = DeterministicFiniteAutomaton::from_json_file("src/automates/DFA1.json");
+        let dfa3 : DeterministicFiniteAutomaton = DeterministicFiniteAutomaton::from_json_file("src/automates/DFA3.json");
+        let nfa3 : NonDeterministicFiniteAutomaton = NonDeterministicFiniteAutomaton::from_json_file("src/automates/NFA3.json");
+        let words : Vec<String> = enumerate_words("abc", 4);
+        //dans ces langages, tout facteur se complete en un mot accepté avec au plus deux symboles de chaque côté
+        let complements : Vec<String> = enumerate_words("abc", 2);
+
+        let is_prefix = |accept : &dyn Fn(&str) -> bool, word : &str| complements.iter().any(|right| accept(&format!("{}{}", word, right)));
+        let is_suffix = |accept : &dyn Fn(&str) -> bool, word : &str| complements.iter().any(|left| accept(&format!("{}{}", left, word)));
+        let is_factor = |accept : &dyn Fn(&str) -> bool, word : &str| complements.iter().any(|left| complements.iter().any(|right| accept(&format!("{}{}{}", left, word, right))));
+
+        for dfa in [&dfa1, &dfa3] {
+            let accept = |word : &str| dfa.accept(word);
+            let (prefix, suffix, factor) = (dfa.prefix_closure(), dfa.suffix_closure(), dfa.factor_closure());
+            for word in &words {
+                assert_eq!(prefix.accept(word), is_prefix(&accept, word), "prefix {}", word);
+                assert_eq!(suffix.accept(word), is_suffix(&accept, word), "suffix {}", word);
+                assert_eq!(factor.accept(word), is_factor(&accept, word), "factor {}", word);
+            }
+        }
+        let accept = |word : &str| nfa3.accept(word);
+        let (prefix, suffix, factor) = (nfa3.prefix_closure(), nfa3.suffix_closure(), nfa3.factor_closure());
+        for word in &words {
+            assert_eq!(prefix.accept(word), is_prefix(&accept, word), "prefix {}", word);
+            assert_eq!(suffix.accept(word), is_suffix(&accept, word), "suffix {}", word);
+            assert_eq!(factor.accept(word), is_factor(&accept, word), "factor {}", word);
+        }
+    }
+}
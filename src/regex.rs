@@ -0,0 +1,233 @@
+use super::{Transition, State, Symbol, FiniteStateMachine, DeterministicFiniteAutomaton, NonDeterministicFiniteAutomaton};
+use std::collections::{BTreeSet, HashMap, HashSet};
+
+/// Automate reconnaissant les mots d'une lettre de `symbols`
+fn symbols_nfa(symbols : &BTreeSet<Symbol>) -> NonDeterministicFiniteAutomaton {
+    let start : State = State::from_str("0");
+    let end : State = State::from_str("1");
+    let mut delta : HashMap<Transition<State>, HashSet<State>> = HashMap::new();
+    for symbol in symbols {
+        delta.insert(Transition::new(symbol.clone(), start.clone()), HashSet::from([end.clone()]));
+    }
+    let fsm : FiniteStateMachine = FiniteStateMachine::new(
+        HashSet::from([start.clone(), end.clone()]),
+        symbols.iter().cloned().collect(),
+        HashSet::from([end]),
+    );
+    NonDeterministicFiniteAutomaton::new(HashSet::from([start]), delta, fsm)
+}
+
+/// Automate reconnaissant uniquement le mot vide
+fn epsilon_nfa() -> NonDeterministicFiniteAutomaton {
+    let start : State = State::from_str("0");
+    let fsm : FiniteStateMachine = FiniteStateMachine::new(HashSet::from([start.clone()]), HashSet::new(), HashSet::from([start.clone()]));
+    NonDeterministicFiniteAutomaton::new(HashSet::from([start]), HashMap::new(), fsm)
+}
+
+/// Renomme les états en `0`, `1`, ... afin que les préfixes ajoutés par les opérations rationnelles ne s'accumulent pas
+fn compact(nfa : &NonDeterministicFiniteAutomaton) -> NonDeterministicFiniteAutomaton {
+    let mut sorted : Vec<&State> = nfa.get_states().iter().collect();
+    sorted.sort();
+    let names : HashMap<&State, State> = sorted.into_iter().enumerate().map(|(index, state)| (state, State::new(index.to_string()))).collect();
+    let rename = |states : &HashSet<State>| -> HashSet<State> { states.iter().map(|state| names[state].clone()).collect() };
+    let delta : HashMap<Transition<State>, HashSet<State>> = nfa.get_delta().iter()
+        .map(|(transition, images)| (Transition::new(transition.get_symbol().clone(), names[transition.get_content()].clone()), rename(images)))
+        .collect();
+    let fsm : FiniteStateMachine = FiniteStateMachine::new(rename(nfa.get_states()), nfa.get_alphabet().clone(), rename(nfa.get_ends()));
+    NonDeterministicFiniteAutomaton::new(rename(nfa.get_starts()), delta, fsm)
+}
+
+/// Analyseur descendant récursif d'une expression rationnelle
+struct Parser {
+    chars: Vec<char>,
+    position: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.position).copied()
+    }
+
+    fn error(&self, message : &str) -> String {
+        format!("{} at position {}", message, self.position)
+    }
+
+    /// alternative := concatenation ('|' concatenation)*
+    fn alternative(&mut self) -> Result<NonDeterministicFiniteAutomaton, String> {
+        let mut nfa : NonDeterministicFiniteAutomaton = self.concatenation()?;
+        while self.peek() == Some('|') {
+            self.position += 1;
+            nfa = compact(&nfa.union_nfa(&self.concatenation()?));
+        }
+        Ok(nfa)
+    }
+
+    /// concatenation := repetition*, le mot vide si aucune repetition
+    fn concatenation(&mut self) -> Result<NonDeterministicFiniteAutomaton, String> {
+        let mut nfa : NonDeterministicFiniteAutomaton = epsilon_nfa();
+        while self.peek().is_some_and(|letter| letter != '|' && letter != ')') {
+            nfa = compact(&nfa.concat(&self.repetition()?));
+        }
+        Ok(nfa)
+    }
+
+    /// repetition := atom ('*' | '+' | '?')*
+    fn repetition(&mut self) -> Result<NonDeterministicFiniteAutomaton, String> {
+        let mut nfa : NonDeterministicFiniteAutomaton = self.atom()?;
+        while let Some(operator) = self.peek().filter(|letter| "*+?".contains(*letter)) {
+            self.position += 1;
+            nfa = compact(&match operator {
+                '*' => nfa.star(),
+                '+' => nfa.plus(),
+                _ => nfa.union_nfa(&epsilon_nfa()),
+            });
+        }
+        Ok(nfa)
+    }
+
+    /// atom := lettre | '\' lettre | '(' alternative ')' | '[' classe ']'
+    fn atom(&mut self) -> Result<NonDeterministicFiniteAutomaton, String> {
+        let letter : char = self.peek().ok_or_else(|| self.error("unexpected end of pattern"))?;
+        match letter {
+            '(' => {
+                self.position += 1;
+                let nfa : NonDeterministicFiniteAutomaton = self.alternative()?;
+                if self.peek() != Some(')') {
+                    return Err(self.error("expected ')'"));
+                }
+                self.position += 1;
+                Ok(nfa)
+            },
+            '[' => {
+                self.position += 1;
+                let symbols : BTreeSet<Symbol> = self.class()?;
+                Ok(symbols_nfa(&symbols))
+            },
+            '.' => Err(self.error("wildcard '.' is not supported, use '\\.' for a literal dot")),
+            '*' | '+' | '?' | ']' => Err(self.error(&format!("unexpected '{}'", letter))),
+            _ => {
+                let letter : char = self.literal()?;
+                Ok(symbols_nfa(&BTreeSet::from([Symbol::new(String::from(letter))])))
+            },
+        }
+    }
+
+    /// Lit une lettre, éventuellement échappée par `\`
+    fn literal(&mut self) -> Result<char, String> {
+        let mut letter : char = self.peek().ok_or_else(|| self.error("unexpected end of pattern"))?;
+        if letter == '\\' {
+            self.position += 1;
+            letter = self.peek().ok_or_else(|| self.error("dangling '\\'"))?;
+        }
+        self.position += 1;
+        Ok(letter)
+    }
+
+    /// classe := (lettre | lettre '-' lettre)+ ']', le '[' ayant déjà été lu
+    fn class(&mut self) -> Result<BTreeSet<Symbol>, String> {
+        if self.peek() == Some('^') {
+            return Err(self.error("negated classes are not supported"));
+        }
+        let mut symbols : BTreeSet<Symbol> = BTreeSet::new();
+        while self.peek() != Some(']') {
+            let first : char = self.literal()?;
+            let mut last : char = first;
+            if self.peek() == Some('-') && self.chars.get(self.position + 1).is_some_and(|letter| *letter != ']') {
+                self.position += 1;
+                last = self.literal()?;
+                if last < first {
+                    return Err(self.error(&format!("invalid range {}-{}", first, last)));
+                }
+            }
+            symbols.extend((first..=last).map(|letter| Symbol::new(String::from(letter))));
+        }
+        if symbols.is_empty() {
+            return Err(self.error("empty class"));
+        }
+        self.position += 1;
+        Ok(symbols)
+    }
+}
+
+impl NonDeterministicFiniteAutomaton {
+    /// Créer un automate depuis une expression rationnelle
+    ///
+    /// La syntaxe reconnue comprend l'alternative `|`, les répétitions `*`, `+` et `?`, les parenthèses et les
+    /// classes de lettres `[a-z_]`. Les caractères `|*+?()[]\.` doivent être échappés par `\`. Le joker `.` et les
+    /// classes complémentaires ne sont pas acceptés car l'automate n'a pas d'alphabet de référence.
+    /// L'automate est construit par les opérations rationnelles `union_nfa`, `concat`, `star` et `plus`.
+    ///
+    /// # Arguments
+    ///
+    /// * `pattern` - L'expression rationnelle
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use automate::*;
+    /// fn main() {
+    ///     let nfa : NonDeterministicFiniteAutomaton = NonDeterministicFiniteAutomaton::from_regex("[0-9]+(\\.[0-9]+)?").unwrap();
+    ///     assert!(nfa.accept("3.14"));
+    ///     assert!(nfa.accept("42"));
+    ///     assert!(!nfa.accept("3."));
+    ///     assert!(NonDeterministicFiniteAutomaton::from_regex("(ab").is_err());
+    /// }
+    /// ```
+    ///
+    /// # Return
+    ///
+    /// * `Result<NonDeterministicFiniteAutomaton, String>` - L'automate reconnaissant le langage de l'expression, une erreur indiquant la position fautive
+    ///
+    pub fn from_regex(pattern : &str) -> Result<NonDeterministicFiniteAutomaton, String> {
+        let mut parser : Parser = Parser { chars: pattern.chars().collect(), position: 0 };
+        let nfa : NonDeterministicFiniteAutomaton = parser.alternative()?;
+        if parser.peek().is_some() {
+            return Err(parser.error(&format!("unexpected '{}'", parser.peek().unwrap())));
+        }
+        Ok(nfa)
+    }
+}
+
+impl DeterministicFiniteAutomaton {
+    /// Créer l'automate minimal d'une expression rationnelle, voir `NonDeterministicFiniteAutomaton::from_regex`
+    pub fn from_regex(pattern : &str) -> Result<DeterministicFiniteAutomaton, String> {
+        Ok(NonDeterministicFiniteAutomaton::from_regex(pattern)?.determinize().minimize())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use super::super::test_words::enumerate_words;
+
+    /// Appartenance attendue d'un mot au langage d'une expression
+    type Expected = fn(&str) -> bool;
+
+    #[test]
+    fn regex_languages() {
+        let cases : [(&str, Expected); 6] = [
+            ("a(b|c)*", |word| word.starts_with('a') && word[1..].chars().all(|letter| letter == 'b' || letter == 'c')),
+            ("(ab)+|c?", |word| word.is_empty() || word == "c" || (word.len() % 2 == 0 && word.as_bytes().chunks(2).all(|pair| pair == b"ab"))),
+            ("[a-b]c[c]", |word| word == "acc" || word == "bcc"),
+            ("()|a\\|", |word| word.is_empty() || word == "a|"),
+            ("(a|)(b|)", |word| ["", "a", "b", "ab"].contains(&word)),
+            ("a**b?+", |word| word.trim_start_matches('a').chars().all(|letter| letter == 'b')),
+        ];
+        for (pattern, expected) in cases {
+            let nfa : NonDeterministicFiniteAutomaton = NonDeterministicFiniteAutomaton::from_regex(pattern).unwrap();
+            let dfa : DeterministicFiniteAutomaton = DeterministicFiniteAutomaton::from_regex(pattern).unwrap();
+            for word in enumerate_words("abc|", 5) {
+                assert_eq!(nfa.accept(&word), expected(&word), "{} {}", pattern, word);
+                assert_eq!(dfa.accept(&word), expected(&word), "{} {}", pattern, word);
+            }
+        }
+    }
+
+    #[test]
+    fn regex_errors() {
+        for (pattern, position) in [("(ab", 3), ("ab)", 2), ("*a", 0), ("a.b", 1), ("[]", 1), ("[^a]", 1), ("[b-a]", 4), ("a\\", 2)] {
+            let error : String = NonDeterministicFiniteAutomaton::from_regex(pattern).unwrap_err();
+            assert!(error.ends_with(&format!("at position {}", position)), "{} {}", pattern, error);
+        }
+    }
+}
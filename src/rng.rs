@@ -0,0 +1,44 @@
+/// Générateur pseudo-aléatoire initialisé par une graine (SplitMix64)
+///
+/// Une même graine produit toujours la même suite de nombres, ce qui rend les tirages reproductibles.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    /// Créer un générateur depuis une graine
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use automate::*;
+    /// fn main() {
+    ///     let mut rng : Rng = Rng::new(42);
+    ///     let mut same : Rng = Rng::new(42);
+    ///     assert_eq!(rng.next_u64(), same.next_u64());
+    ///     let value : f64 = rng.next_f64();
+    ///     assert!((0.0..1.0).contains(&value));
+    /// }
+    /// ```
+    ///
+    pub fn new(_seed : u64) -> Self {
+        Rng {
+            state: _seed,
+        }
+    }
+
+    /// Retourne un entier pseudo-aléatoire uniforme sur 64 bits
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut value : u64 = self.state;
+        value = (value ^ (value >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        value = (value ^ (value >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        value ^ (value >> 31)
+    }
+
+    /// Retourne un flottant pseudo-aléatoire uniforme dans [0, 1[
+    pub fn next_f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+}
@@ -0,0 +1,445 @@
+use super::{Transition, State, Symbol, DeterministicFiniteAutomaton, NonDeterministicFiniteAutomaton};
+use std::collections::{BTreeMap, BTreeSet, HashSet};
+
+/// Sémantique utilisée pour choisir une occurrence parmi celles qui commencent au même endroit
+///
+/// Les états finaux d'un automate n'ont pas de priorité entre eux; la priorité entre les mots d'un dictionnaire
+/// est donnée par `KeywordMatchKind`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MatchKind {
+    /// L'occurrence la plus à gauche, puis la plus longue
+    LeftmostLongest,
+    /// L'occurrence la plus à gauche, puis la plus courte
+    LeftmostShortest,
+}
+
+impl MatchKind {
+    /// Indique si `candidate`, trouvée après `current` au même départ, la remplace selon la sémantique
+    pub(crate) fn prefers(self, candidate : &Match, current : &Match) -> bool {
+        match self {
+            MatchKind::LeftmostLongest => candidate.end > current.end,
+            MatchKind::LeftmostShortest => false,
+        }
+    }
+}
+
+/// Une occurrence d'un mot accepté dans un texte
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Match {
+    start: usize,
+    end: usize,
+    states: BTreeSet<State>,
+    pattern: Option<usize>,
+}
+
+impl Match {
+    /// Retourne la position (en octets) du début de l'occurrence
+    pub fn get_start(&self) -> usize {
+        self.start
+    }
+
+    /// Retourne la position (en octets) de la fin de l'occurrence, exclue
+    pub fn get_end(&self) -> usize {
+        self.end
+    }
+
+    /// Retourne les états finaux atteints à la fin de l'occurrence
+    pub fn get_states(&self) -> &BTreeSet<State> {
+        &self.states
+    }
+
+    /// Retourne l'indice du motif reconnu, pour une recherche par dictionnaire (`AhoCorasick`)
+    pub fn get_pattern(&self) -> Option<usize> {
+        self.pattern
+    }
+
+    /// Créer une occurrence d'un motif d'un dictionnaire
+    pub(crate) fn with_pattern(start : usize, end : usize, state : State, pattern : usize) -> Self {
+        Match { start, end, states: BTreeSet::from([state]), pattern: Some(pattern) }
+    }
+}
+
+/// Enchaîne les occurrences sans chevauchement de `text`, `find_at` cherchant celle qui commence à partir d'une position
+///
+/// Après une occurrence, la recherche reprend à sa fin; une occurrence vide fait avancer d'un caractère pour ne pas boucler.
+pub(crate) fn find_non_overlapping(text : &str, find_at : impl Fn(usize) -> Option<Match>) -> Vec<Match> {
+    let mut matches : Vec<Match> = Vec::new();
+    let mut from : usize = 0;
+    while from <= text.len() {
+        let found : Match = match find_at(from) {
+            Some(found) => found,
+            None => break,
+        };
+        from = if found.end > found.start {
+            found.end
+        } else {
+            match text[found.end..].chars().next() {
+                Some(letter) => found.end + letter.len_utf8(),
+                None => text.len() + 1,
+            }
+        };
+        matches.push(found);
+    }
+    matches
+}
+
+/// Fonction de transition utilisée par la recherche: les images d'un état par un symbole
+type Step<'a> = dyn Fn(&State, &Symbol) -> Vec<State> + 'a;
+
+/// Moteur de recherche commun aux automates déterministes et non déterministes
+///
+/// Le texte est lu une seule fois de gauche à droite; chaque état actif retient la position de départ de
+/// l'occurrence en cours. Deux occurrences qui atteignent le même état ont le même futur, seule la plus à gauche
+/// est donc conservée. Les états depuis lesquels aucun état final n'est accessible (le puits d'un automate
+/// complet par exemple) sont abandonnés dès qu'ils sont atteints, pour que la lecture s'arrête après une occurrence.
+pub(crate) struct Searcher<'a> {
+    starts: HashSet<State>,
+    ends: HashSet<State>,
+    live: HashSet<State>,
+    step: Box<Step<'a>>,
+    kind: MatchKind,
+}
+
+impl<'a> Searcher<'a> {
+    /// Créer un moteur de recherche
+    ///
+    /// # Arguments
+    ///
+    /// * `starts` - Les états initiaux
+    /// * `ends` - Les états finaux
+    /// * `live` - Les états depuis lesquels un état final est accessible, les autres ne sont jamais actifs
+    /// * `step` - Les images d'un état par un symbole
+    /// * `kind` - La sémantique de choix des occurrences
+    ///
+    pub(crate) fn new(starts : HashSet<State>, ends : HashSet<State>, live : HashSet<State>, step : Box<Step<'a>>, kind : MatchKind) -> Self {
+        Searcher { starts, ends, live, step, kind }
+    }
+
+    /// Etats initiaux depuis lesquels une occurrence peut être trouvée
+    fn live_starts(&self) -> impl Iterator<Item = &State> {
+        self.starts.iter().filter(|start| self.live.contains(*start))
+    }
+
+    /// Etats finaux parmi les états actifs partant de `start`
+    fn accepting(&self, actives : &BTreeMap<State, usize>, start : usize) -> BTreeSet<State> {
+        actives.iter()
+            .filter(|(state, from)| **from == start && self.ends.contains(*state))
+            .map(|(state, _)| state.clone())
+            .collect()
+    }
+
+    /// Avance tous les états actifs d'un symbole, en gardant pour chaque état le départ le plus à gauche
+    fn advance(&self, actives : &BTreeMap<State, usize>, symbol : &Symbol) -> BTreeMap<State, usize> {
+        let mut next : BTreeMap<State, usize> = BTreeMap::new();
+        for (state, start) in actives {
+            for image in (self.step)(state, symbol).into_iter().filter(|image| self.live.contains(image)) {
+                let entry : &mut usize = next.entry(image).or_insert(*start);
+                *entry = (*entry).min(*start);
+            }
+        }
+        next
+    }
+
+    /// Cherche l'occurrence la plus à gauche commençant à partir de la position `from`
+    fn find_at(&self, text : &str, from : usize) -> Option<Match> {
+        let mut actives : BTreeMap<State, usize> = BTreeMap::new();
+        let mut best : Option<Match> = None;
+        let mut chars = text[from..].char_indices().map(|(offset, letter)| (from + offset, letter)).peekable();
+        let mut position : usize = from;
+        loop {
+            //une nouvelle occurrence ne peut commencer que si aucune n'a encore été trouvée
+            if best.is_none() {
+                for start in self.live_starts() {
+                    actives.entry(start.clone()).or_insert(position);
+                }
+            }
+            //mise à jour de la meilleure occurrence avec les etats finaux atteints
+            let candidate : Option<usize> = actives.iter().filter(|(state, _)| self.ends.contains(*state)).map(|(_, start)| *start).min();
+            if let Some(start) = candidate {
+                let found : Match = Match { start, end: position, states: self.accepting(&actives, start), pattern: None };
+                let improves : bool = match &best {
+                    None => true,
+                    Some(best) => start < best.start || (start == best.start && self.kind.prefers(&found, best)),
+                };
+                if improves {
+                    best = Some(found);
+                }
+            }
+            //les occurrences qui commencent après la meilleure ne peuvent plus l'emporter
+            if let Some(best) = &best {
+                actives.retain(|_, start| match self.kind {
+                    MatchKind::LeftmostLongest => *start <= best.start,
+                    MatchKind::LeftmostShortest => *start < best.start,
+                });
+                if actives.is_empty() {
+                    return Some(best.clone());
+                }
+            }
+            match chars.next() {
+                Some((_, letter)) => {
+                    actives = self.advance(&actives, &Symbol::new(String::from(letter)));
+                    position = chars.peek().map_or(text.len(), |(offset, _)| *offset);
+                }
+                None => return best,
+            }
+        }
+    }
+
+    /// Occurrences sans chevauchement, de gauche à droite
+    fn find_all(&self, text : &str) -> Vec<Match> {
+        find_non_overlapping(text, |from| self.find_at(text, from))
+    }
+
+    /// Pour chaque position de départ, l'occurrence choisie par la sémantique, en une seule lecture du texte
+    fn find_overlapping(&self, text : &str) -> Vec<Match> {
+        //pour chaque etat, les positions de départ des occurrences en cours
+        let mut actives : BTreeMap<State, BTreeSet<usize>> = BTreeMap::new();
+        let mut found : BTreeMap<usize, Match> = BTreeMap::new();
+        let positions : Vec<usize> = text.char_indices().map(|(offset, _)| offset).chain(std::iter::once(text.len())).collect();
+        for (index, position) in positions.iter().enumerate() {
+            for start in self.live_starts() {
+                actives.entry(start.clone()).or_default().insert(*position);
+            }
+            //etats finaux atteints par chaque depart
+            let mut accepted : BTreeMap<usize, BTreeSet<State>> = BTreeMap::new();
+            for (state, starts) in &actives {
+                if self.ends.contains(state) {
+                    for start in starts {
+                        accepted.entry(*start).or_default().insert(state.clone());
+                    }
+                }
+            }
+            for (start, states) in accepted {
+                let candidate : Match = Match { start, end: *position, states, pattern: None };
+                if found.get(&start).is_none_or(|current| self.kind.prefers(&candidate, current)) {
+                    found.insert(start, candidate);
+                }
+            }
+            //en semantique shortest, un depart qui a trouvé son occurrence est terminé
+            if self.kind == MatchKind::LeftmostShortest {
+                for starts in actives.values_mut() {
+                    starts.retain(|start| !found.contains_key(start));
+                }
+            }
+            let letter : char = match text[*position..].chars().next() {
+                Some(letter) if index + 1 < positions.len() => letter,
+                _ => break,
+            };
+            let symbol : Symbol = Symbol::new(String::from(letter));
+            let mut next : BTreeMap<State, BTreeSet<usize>> = BTreeMap::new();
+            for (state, starts) in &actives {
+                if starts.is_empty() {
+                    continue;
+                }
+                for image in (self.step)(state, &symbol).into_iter().filter(|image| self.live.contains(image)) {
+                    next.entry(image).or_default().extend(starts.iter().copied());
+                }
+            }
+            actives = next;
+        }
+        found.into_values().collect()
+    }
+}
+
+impl DeterministicFiniteAutomaton {
+    /// Retourne le moteur de recherche de l'automate
+    pub(crate) fn searcher(&self, kind : MatchKind) -> Searcher<'_> {
+        let step = move |state : &State, symbol : &Symbol| self.apply_delta(Transition::new(symbol.clone(), state.clone())).into_iter().cloned().collect();
+        let live : HashSet<State> = self.distances_to_acceptance().into_keys().collect();
+        Searcher::new(HashSet::from([self.get_start().clone()]), self.get_ends().clone(), live, Box::new(step), kind)
+    }
+
+    /// Retourne la première occurrence (la plus à gauche) d'un mot accepté dans `text`
+    ///
+    /// # Arguments
+    ///
+    /// * `text` - Le texte à parcourir
+    /// * `kind` - La sémantique de choix de l'occurrence
+    ///
+    /// # Return
+    ///
+    /// * `Option<Match>` - L'occurrence trouvée, `None` si aucun facteur de `text` n'est accepté
+    ///
+    pub fn find(&self, text : &str, kind : MatchKind) -> Option<Match> {
+        self.searcher(kind).find_at(text, 0)
+    }
+
+    /// Retourne toutes les occurrences sans chevauchement de mots acceptés dans `text`
+    ///
+    /// Le texte est parcouru de gauche à droite en une seule passe par occurrence, sans appeler `accept` sur
+    /// chaque facteur. Après une occurrence, la recherche reprend à sa fin.
+    ///
+    /// # Arguments
+    ///
+    /// * `text` - Le texte à parcourir
+    /// * `kind` - La sémantique de choix des occurrences
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use automate::*;
+    /// fn main() {
+    ///     //(ab)*: le mot vide est accepté partout, on ne garde que les occurrences non vides
+    ///     let dfa : DeterministicFiniteAutomaton = DeterministicFiniteAutomaton::from_json_file("src/automates/DFA1.json");
+    ///     let spans : Vec<(usize, usize)> = dfa.find_all("xabababx", MatchKind::LeftmostLongest).iter()
+    ///         .filter(|found| found.get_end() > found.get_start())
+    ///         .map(|found| (found.get_start(), found.get_end()))
+    ///         .collect();
+    ///     assert_eq!(spans, vec![(1, 7)]);
+    /// }
+    /// ```
+    ///
+    /// # Return
+    ///
+    /// * `Vec<Match>` - Les occurrences, triées par position
+    ///
+    pub fn find_all(&self, text : &str, kind : MatchKind) -> Vec<Match> {
+        self.searcher(kind).find_all(text)
+    }
+
+    /// Retourne, pour chaque position de départ, l'occurrence choisie par `kind`, les occurrences pouvant se chevaucher
+    ///
+    /// # Arguments
+    ///
+    /// * `text` - Le texte à parcourir
+    /// * `kind` - La sémantique de choix des occurrences
+    ///
+    /// # Return
+    ///
+    /// * `Vec<Match>` - Les occurrences, triées par position de départ
+    ///
+    pub fn find_overlapping(&self, text : &str, kind : MatchKind) -> Vec<Match> {
+        self.searcher(kind).find_overlapping(text)
+    }
+}
+
+impl NonDeterministicFiniteAutomaton {
+    /// Retourne le moteur de recherche de l'automate
+    pub(crate) fn searcher(&self, kind : MatchKind) -> Searcher<'_> {
+        let step = move |state : &State, symbol : &Symbol| self.apply_delta(Transition::new(symbol.clone(), state.clone())).into_iter().flatten().collect();
+        let live : HashSet<State> = self.distances_to_acceptance().into_keys().collect();
+        Searcher::new(self.get_starts().clone(), self.get_ends().clone(), live, Box::new(step), kind)
+    }
+
+    /// Retourne la première occurrence (la plus à gauche) d'un mot accepté dans `text`
+    ///
+    /// # Arguments
+    ///
+    /// * `text` - Le texte à parcourir
+    /// * `kind` - La sémantique de choix de l'occurrence
+    ///
+    /// # Return
+    ///
+    /// * `Option<Match>` - L'occurrence trouvée, `None` si aucun facteur de `text` n'est accepté
+    ///
+    pub fn find(&self, text : &str, kind : MatchKind) -> Option<Match> {
+        self.searcher(kind).find_at(text, 0)
+    }
+
+    /// Retourne toutes les occurrences sans chevauchement de mots acceptés dans `text`
+    ///
+    /// # Arguments
+    ///
+    /// * `text` - Le texte à parcourir
+    /// * `kind` - La sémantique de choix des occurrences
+    ///
+    /// # Return
+    ///
+    /// * `Vec<Match>` - Les occurrences, triées par position
+    ///
+    pub fn find_all(&self, text : &str, kind : MatchKind) -> Vec<Match> {
+        self.searcher(kind).find_all(text)
+    }
+
+    /// Retourne, pour chaque position de départ, l'occurrence choisie par `kind`, les occurrences pouvant se chevaucher
+    ///
+    /// # Arguments
+    ///
+    /// * `text` - Le texte à parcourir
+    /// * `kind` - La sémantique de choix des occurrences
+    ///
+    /// # Return
+    ///
+    /// * `Vec<Match>` - Les occurrences, triées par position de départ
+    ///
+    pub fn find_overlapping(&self, text : &str, kind : MatchKind) -> Vec<Match> {
+        self.searcher(kind).find_overlapping(text)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use super::super::test_words::enumerate_words;
+
+    /// Recherche naïve: appelle accept sur chaque facteur
+    fn naive(accept : &dyn Fn(&str) -> bool, text : &str, kind : MatchKind, overlapping : bool) -> Vec<(usize, usize)> {
+        let mut matches : Vec<(usize, usize)> = Vec::new();
+        let mut start : usize = 0;
+        while start <= text.len() {
+            let ends : Vec<usize> = (start..=text.len()).filter(|end| accept(&text[start..*end])).collect();
+            let end : Option<usize> = match kind {
+                MatchKind::LeftmostLongest => ends.last().copied(),
+                MatchKind::LeftmostShortest => ends.first().copied(),
+            };
+            match end {
+                Some(end) => {
+                    matches.push((start, end));
+                    start = if overlapping || end == start { start + 1 } else { end };
+                }
+                None => start += 1,
+            }
+        }
+        matches
+    }
+
+    fn spans(matches : &[Match]) -> Vec<(usize, usize)> {
+        matches.iter().map(|found| (found.get_start(), found.get_end())).collect()
+    }
+
+    #[test]
+    fn search_matches_naive() {
+        let dfa3 : DeterministicFiniteAutomaton = DeterministicFiniteAutomaton::from_json_file("src/automates/DFA3.json");
+        let nfa3 : NonDeterministicFiniteAutomaton = NonDeterministicFiniteAutomaton::from_json_file("src/automates/NFA3.json");
+        let dfa1 : DeterministicFiniteAutomaton = DeterministicFiniteAutomaton::from_json_file("src/automates/DFA1.json");
+        for text in enumerate_words("abc", 6) {
+            for kind in [MatchKind::LeftmostLongest, MatchKind::LeftmostShortest] {
+                for overlapping in [false, true] {
+                    let expected = |accept : &dyn Fn(&str) -> bool| naive(accept, &text, kind, overlapping);
+                    let (dfa3_found, nfa3_found, dfa1_found) = if overlapping {
+                        (dfa3.find_overlapping(&text, kind), nfa3.find_overlapping(&text, kind), dfa1.find_overlapping(&text, kind))
+                    } else {
+                        (dfa3.find_all(&text, kind), nfa3.find_all(&text, kind), dfa1.find_all(&text, kind))
+                    };
+                    assert_eq!(spans(&dfa3_found), expected(&|word| dfa3.accept(word)), "{} {:?} {}", text, kind, overlapping);
+                    assert_eq!(spans(&nfa3_found), expected(&|word| nfa3.accept(word)), "{} {:?} {}", text, kind, overlapping);
+                    assert_eq!(spans(&dfa1_found), expected(&|word| dfa1.accept(word)), "{} {:?} {}", text, kind, overlapping);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn search_semantics() {
+        //{b, ab, abc}
+        let dfa3 : DeterministicFiniteAutomaton = DeterministicFiniteAutomaton::from_json_file("src/automates/DFA3.json");
+        assert_eq!(spans(&dfa3.find_all("xxabcab", MatchKind::LeftmostLongest)), vec![(2, 5), (5, 7)]);
+        assert_eq!(spans(&dfa3.find_all("xxabcab", MatchKind::LeftmostShortest)), vec![(2, 4), (5, 7)]);
+        assert_eq!(spans(&dfa3.find_overlapping("xxabcab", MatchKind::LeftmostLongest)), vec![(2, 5), (3, 4), (5, 7), (6, 7)]);
+        let found : Match = dfa3.find("xxabc", MatchKind::LeftmostLongest).unwrap();
+        assert_eq!(found.get_states(), &BTreeSet::from([State::from_str("q_3")]));
+        assert_eq!(dfa3.find("cccc", MatchKind::LeftmostShortest), None);
+    }
+
+    #[test]
+    fn complete_dfa_stops_after_each_match() {
+        //l'automate minimal est complet: sans abandon du puits, chaque recherche lirait tout le texte
+        let dfa : DeterministicFiniteAutomaton = DeterministicFiniteAutomaton::from_regex("ab").unwrap().minimize();
+        assert!(dfa.is_complete());
+        let text : String = "ab".repeat(50_000);
+        let found : Vec<Match> = dfa.find_all(&text, MatchKind::LeftmostLongest);
+        assert_eq!(found.len(), 50_000);
+        assert_eq!(spans(&found[49_999..]), vec![(99_998, 100_000)]);
+        assert_eq!(dfa.find_overlapping(&text, MatchKind::LeftmostLongest).len(), 50_000);
+    }
+}
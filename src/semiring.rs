@@ -0,0 +1,133 @@
+use std::fmt::Debug;
+
+/// Semi-anneau des poids d'un automate pondéré
+///
+/// `plus` combine les poids de chemins concurrents et `times` ceux des transitions successives d'un chemin;
+/// `zero` est neutre pour `plus` et absorbant pour `times`, `one` est neutre pour `times`.
+pub trait Semiring: Clone + PartialEq + Debug {
+    /// Poids d'un ensemble vide de chemins
+    fn zero() -> Self;
+    /// Poids du chemin vide
+    fn one() -> Self;
+    /// Combine les poids de deux chemins concurrents
+    fn plus(&self, other : &Self) -> Self;
+    /// Combine les poids de deux parties successives d'un chemin
+    fn times(&self, other : &Self) -> Self;
+}
+
+/// Semi-anneau sélectif: `plus` retient le meilleur des deux poids, ce qui permet de chercher les meilleurs chemins
+///
+/// Les recherches de meilleurs chemins supposent de plus qu'allonger un chemin ne l'améliore jamais, par exemple
+/// des coûts positifs pour le semi-anneau tropical ou des probabilités au plus 1.
+pub trait Selective: Semiring {
+    /// Indique si `self` est strictement meilleur que `other`
+    fn better(&self, other : &Self) -> bool;
+}
+
+/// Semi-anneau tropical (min, +): le poids d'un chemin est la somme de ses coûts, le meilleur chemin le moins coûteux
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Tropical(pub f64);
+
+impl Semiring for Tropical {
+    fn zero() -> Self {
+        Tropical(f64::INFINITY)
+    }
+
+    fn one() -> Self {
+        Tropical(0.0)
+    }
+
+    fn plus(&self, other : &Self) -> Self {
+        Tropical(self.0.min(other.0))
+    }
+
+    fn times(&self, other : &Self) -> Self {
+        Tropical(self.0 + other.0)
+    }
+}
+
+impl Selective for Tropical {
+    fn better(&self, other : &Self) -> bool {
+        self.0 < other.0
+    }
+}
+
+/// Semi-anneau des probabilités (+, ×): le poids d'un mot est la somme des probabilités de ses chemins
+///
+/// Comme semi-anneau sélectif, le meilleur chemin est le plus probable (algorithme de Viterbi).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Probability(pub f64);
+
+impl Semiring for Probability {
+    fn zero() -> Self {
+        Probability(0.0)
+    }
+
+    fn one() -> Self {
+        Probability(1.0)
+    }
+
+    fn plus(&self, other : &Self) -> Self {
+        Probability(self.0 + other.0)
+    }
+
+    fn times(&self, other : &Self) -> Self {
+        Probability(self.0 * other.0)
+    }
+}
+
+impl Selective for Probability {
+    fn better(&self, other : &Self) -> bool {
+        self.0 > other.0
+    }
+}
+
+/// Semi-anneau booléen (ou, et): le poids d'un mot indique s'il est accepté
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Boolean(pub bool);
+
+impl Semiring for Boolean {
+    fn zero() -> Self {
+        Boolean(false)
+    }
+
+    fn one() -> Self {
+        Boolean(true)
+    }
+
+    fn plus(&self, other : &Self) -> Self {
+        Boolean(self.0 || other.0)
+    }
+
+    fn times(&self, other : &Self) -> Self {
+        Boolean(self.0 && other.0)
+    }
+}
+
+impl Selective for Boolean {
+    fn better(&self, other : &Self) -> bool {
+        self.0 && !other.0
+    }
+}
+
+/// Semi-anneau de comptage (+, ×) sur les entiers: avec des poids 1, le poids d'un mot est son nombre de chemins acceptants
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Counting(pub u64);
+
+impl Semiring for Counting {
+    fn zero() -> Self {
+        Counting(0)
+    }
+
+    fn one() -> Self {
+        Counting(1)
+    }
+
+    fn plus(&self, other : &Self) -> Self {
+        Counting(self.0 + other.0)
+    }
+
+    fn times(&self, other : &Self) -> Self {
+        Counting(self.0 * other.0)
+    }
+}
@@ -1,7 +1,7 @@
 use std::str::FromStr;
 
 //string n'implemente pas copy, donc on peut juste utiliser clone
-#[derive(Debug, Clone, Hash, Eq)]
+#[derive(Debug, Clone, Hash, PartialEq, Eq, PartialOrd, Ord)]
 pub struct State {
     name: String,
 }
@@ -32,6 +32,7 @@ impl State {
         State { name: _name }
     }
 
+    #[allow(clippy::should_implement_trait)]
     pub fn from_str(_name : &str) -> Self {
         State { name: String::from_str(_name).unwrap() }
     }
@@ -59,16 +60,6 @@ impl State {
     }
 }
 
-impl PartialEq<State> for State {
-    fn eq(&self, other: &State) -> bool {
-        self.get_name() == other.get_name()
-    }
-
-    fn ne(&self, other: &State) -> bool {
-        self.get_name() != other.get_name()
-    }
-}
-
 #[cfg(test)]
 mod test {
     use super::*;
@@ -1,6 +1,6 @@
 use std::str::FromStr;
 //string n'implemente pas copy, donc on peut juste utiliser clone
-#[derive(Debug, Clone, Eq, Hash)]
+#[derive(Debug, Clone, Hash, PartialEq, Eq, PartialOrd, Ord)]
 pub struct Symbol {
     value: String,
 }
@@ -32,6 +32,7 @@ impl Symbol {
         Symbol { value: _value }
     }
 
+    #[allow(clippy::should_implement_trait)]
     pub fn from_str(_value : &str) -> Self {
         Symbol { value: String::from_str(_value).unwrap() }
     }
@@ -58,16 +59,6 @@ impl Symbol {
     }
 }
 
-impl PartialEq<Symbol> for Symbol {
-    fn eq(&self, other: &Symbol) -> bool {
-        self.get_value() == other.get_value()
-    }
-
-    fn ne(&self, other: &Symbol) -> bool {
-        self.get_value() != other.get_value()
-    }
-}
-
 #[cfg(test)]
 mod test {
     use super::*;
@@ -0,0 +1,423 @@
+use super::{State, Symbol, Transition, CharSet, FiniteStateMachine, DeterministicFiniteAutomaton, SetNames};
+use super::paths::{build_adjacency, coreachable};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet, VecDeque};
+use std::fs;
+use serde_json::{Value, from_str, map::Map};
+
+/// Transition symbolique: (état, ensemble des caractères lus, image)
+pub type SymbolicArc = (State, CharSet, State);
+
+/// Automate symbolique: les transitions portent des ensembles de caractères plutôt qu'un symbole
+///
+/// Une transition `(p, [a-z], q)` remplace les 26 transitions d'un automate classique, ce qui permet de
+/// représenter des classes comme « toute lettre Unicode ». L'automate peut être non déterministe.
+#[derive(Debug, Clone)]
+pub struct SymbolicAutomaton {
+    starts: HashSet<State>,
+    arcs: Vec<SymbolicArc>,
+    ends: HashSet<State>,
+    states: HashSet<State>,
+}
+
+impl SymbolicAutomaton {
+    /// Créer un automate symbolique
+    ///
+    /// Les transitions d'ensemble vide sont ignorées; les états sont déduits des transitions, des états initiaux et finaux.
+    ///
+    /// # Arguments
+    ///
+    /// * `_starts` - Les états initiaux de l'automate
+    /// * `_arcs` - Les transitions de l'automate
+    /// * `_ends` - Les états finaux de l'automate
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use automate::*;
+    /// use std::collections::HashSet;
+    /// fn main() {
+    ///     let q_0 : State = State::from_str("q_0");
+    ///     let q_1 : State = State::from_str("q_1");
+    ///     let digits : CharSet = CharSet::new(&[('0', '9')]);
+    ///     let number : SymbolicAutomaton = SymbolicAutomaton::new(
+    ///         HashSet::from([q_0.clone()]),
+    ///         vec![(q_0.clone(), digits.clone(), q_1.clone()), (q_1.clone(), digits, q_1.clone())],
+    ///         HashSet::from([q_1]),
+    ///     );
+    ///     assert!(number.accept("2024"));
+    ///     assert!(!number.accept("20a4"));
+    ///     assert!(!number.accept(""));
+    /// }
+    /// ```
+    ///
+    pub fn new(_starts : HashSet<State>, _arcs : Vec<SymbolicArc>, _ends : HashSet<State>) -> Self {
+        let arcs : Vec<SymbolicArc> = _arcs.into_iter().filter(|(_, set, _)| !set.is_empty()).collect();
+        let mut states : HashSet<State> = _starts.union(&_ends).cloned().collect();
+        for (state, _, image) in &arcs {
+            states.insert(state.clone());
+            states.insert(image.clone());
+        }
+        SymbolicAutomaton {
+            starts: _starts,
+            arcs,
+            ends: _ends,
+            states,
+        }
+    }
+
+    /// Créer un automate symbolique depuis un objet json
+    ///
+    /// L'objet contient `starts` et `ends`, des listes d'états, et `delta`, une liste de transitions
+    /// `{"state": "q_0", "ranges": [["a", "z"], ["_", "_"]], "image": "q_1"}` dont les intervalles sont bornes comprises.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use automate::*;
+    /// fn main() {
+    ///     let identifier : SymbolicAutomaton = SymbolicAutomaton::from_json_file("src/automates/SYM1.json");
+    ///     assert!(identifier.accept("_total2"));
+    ///     assert!(identifier.accept("élève"));
+    ///     assert!(!identifier.accept("2total"));
+    /// }
+    /// ```
+    ///
+    pub fn from_json(content_json: &Map<String, Value>) -> Self {
+        let state_set = |key : &str| -> HashSet<State> {
+            content_json.get(key).unwrap().as_array().unwrap().iter().map(|state| State::new(String::from(state.as_str().unwrap()))).collect()
+        };
+        let letter = |value : &Value| -> char { value.as_str().unwrap().chars().next().unwrap() };
+        let mut arcs : Vec<SymbolicArc> = Vec::new();
+        for element_delta in content_json.get("delta").unwrap().as_array().unwrap() {
+            let transition_json : &Map<String, Value> = element_delta.as_object().unwrap();
+            let state : State = State::new(String::from(transition_json.get("state").unwrap().as_str().unwrap()));
+            let image : State = State::new(String::from(transition_json.get("image").unwrap().as_str().unwrap()));
+            let ranges : Vec<(char, char)> = transition_json.get("ranges").unwrap().as_array().unwrap().iter().map(|range| {
+                let bounds : &Vec<Value> = range.as_array().unwrap();
+                (letter(&bounds[0]), letter(&bounds[1]))
+            }).collect();
+            arcs.push((state, CharSet::new(&ranges), image));
+        }
+        SymbolicAutomaton::new(state_set("starts"), arcs, state_set("ends"))
+    }
+
+    /// Créer un automate symbolique depuis un chemin vers un fichier json, voir `from_json`
+    pub fn from_json_file(path: &str) -> Self {
+        let content : String = fs::read_to_string(path).unwrap();
+        SymbolicAutomaton::from_json(from_str::<Value>(&content).unwrap().as_object().unwrap())
+    }
+
+    /// Retourne les états initiaux de l'automate
+    pub fn get_starts(&self) -> &HashSet<State> {
+        &self.starts
+    }
+
+    /// Retourne les transitions de l'automate
+    pub fn get_arcs(&self) -> &Vec<SymbolicArc> {
+        &self.arcs
+    }
+
+    /// Retourne les états finaux de l'automate
+    pub fn get_ends(&self) -> &HashSet<State> {
+        &self.ends
+    }
+
+    /// Retourne les états de l'automate
+    pub fn get_states(&self) -> &HashSet<State> {
+        &self.states
+    }
+
+    /// Retourne les images de l'ensemble d'états `states` par le caractère `letter`
+    pub fn apply_delta(&self, states : &HashSet<State>, letter : char) -> HashSet<State> {
+        self.arcs.iter()
+            .filter(|(state, set, _)| states.contains(state) && set.contains(letter))
+            .map(|(_, _, image)| image.clone())
+            .collect()
+    }
+
+    /// indique si un mot est accepté dans la langue de l'automate
+    pub fn accept(&self, _word : &str) -> bool {
+        let mut current : HashSet<State> = self.starts.clone();
+        for letter in _word.chars() {
+            current = self.apply_delta(&current, letter);
+            if current.is_empty() {
+                return false;
+            }
+        }
+        current.iter().any(|state| self.ends.contains(state))
+    }
+
+    /// Indique si l'automate est déterministe: un seul état initial et des transitions sortantes disjointes pour chaque état
+    pub fn is_deterministic(&self) -> bool {
+        self.starts.len() == 1 && self.arcs.iter().enumerate().all(|(index, (state, set, _))| {
+            self.arcs[index + 1..].iter().all(|(other, other_set, _)| other != state || set.intersection(other_set).is_empty())
+        })
+    }
+
+    /// Retourne les minterms de l'automate: la partition de tous les caractères en classes que les transitions ne distinguent pas
+    ///
+    /// Chaque ensemble d'une transition est une union de minterms; deux caractères d'un même minterm ont donc
+    /// toujours les mêmes images. Les minterms sont non vides, disjoints et triés.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use automate::*;
+    /// fn main() {
+    ///     let identifier : SymbolicAutomaton = SymbolicAutomaton::from_json_file("src/automates/SYM1.json");
+    ///     //les chiffres, les premiers caractères d'un identifiant et le reste
+    ///     assert_eq!(identifier.minterms().len(), 3);
+    /// }
+    /// ```
+    ///
+    pub fn minterms(&self) -> Vec<CharSet> {
+        let predicates : BTreeSet<&CharSet> = self.arcs.iter().map(|(_, set, _)| set).collect();
+        let mut minterms : Vec<CharSet> = vec![CharSet::full()];
+        for predicate in predicates {
+            minterms = minterms.iter()
+                .flat_map(|minterm| [minterm.intersection(predicate), minterm.difference(predicate)])
+                .filter(|minterm| !minterm.is_empty())
+                .collect();
+        }
+        minterms.sort();
+        minterms
+    }
+
+    /// Regroupe les transitions de même origine et de même image en une seule, d'ensemble l'union des ensembles
+    fn merge_arcs(arcs : Vec<SymbolicArc>) -> Vec<SymbolicArc> {
+        let mut merged : BTreeMap<(State, State), CharSet> = BTreeMap::new();
+        for (state, set, image) in arcs {
+            let entry : &mut CharSet = merged.entry((state, image)).or_insert_with(CharSet::empty);
+            *entry = entry.union(&set);
+        }
+        merged.into_iter().map(|((state, image), set)| (state, set, image)).collect()
+    }
+
+    /// Déterminise l'automate par la méthode des sous-ensembles, en lisant un minterm à la fois
+    ///
+    /// Les états de l'automate obtenu sont nommés d'après les ensembles d'états, par exemple `{q_0,q_1}`; seuls les
+    /// ensembles accessibles et non vides sont construits, l'automate obtenu peut donc être incomplet.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use automate::*;
+    /// use std::collections::HashSet;
+    /// fn main() {
+    ///     //les mots qui finissent par un chiffre
+    ///     let q : State = State::from_str("q");
+    ///     let f : State = State::from_str("f");
+    ///     let automaton : SymbolicAutomaton = SymbolicAutomaton::new(
+    ///         HashSet::from([q.clone()]),
+    ///         vec![(q.clone(), CharSet::full(), q.clone()), (q.clone(), CharSet::new(&[('0', '9')]), f.clone())],
+    ///         HashSet::from([f]),
+    ///     );
+    ///     let deterministic : SymbolicAutomaton = automaton.determinize();
+    ///     assert!(!automaton.is_deterministic());
+    ///     assert!(deterministic.is_deterministic());
+    ///     assert_eq!(deterministic.get_states().len(), 2);
+    ///     assert!(deterministic.accept("ligne 42"));
+    ///     assert!(!deterministic.accept("42 lignes"));
+    /// }
+    /// ```
+    ///
+    pub fn determinize(&self) -> SymbolicAutomaton {
+        let minterms : Vec<CharSet> = self.minterms();
+        let start : BTreeSet<State> = self.starts.iter().cloned().collect();
+        let mut seen : HashSet<BTreeSet<State>> = HashSet::from([start.clone()]);
+        let mut queue : VecDeque<BTreeSet<State>> = VecDeque::from([start.clone()]);
+        let mut arcs : Vec<SymbolicArc> = Vec::new();
+        let mut ends : HashSet<State> = HashSet::new();
+        let mut names : SetNames = SetNames::default();
+        while let Some(subset) = queue.pop_front() {
+            if subset.iter().any(|state| self.ends.contains(state)) {
+                ends.insert(names.name(&subset));
+            }
+            for minterm in &minterms {
+                //un minterm est inclus dans chaque ensemble qu'il rencontre
+                let image : BTreeSet<State> = self.arcs.iter()
+                    .filter(|(state, set, _)| subset.contains(state) && !set.intersection(minterm).is_empty())
+                    .map(|(_, _, image)| image.clone())
+                    .collect();
+                if image.is_empty() {
+                    continue;
+                }
+                arcs.push((names.name(&subset), minterm.clone(), names.name(&image)));
+                if seen.insert(image.clone()) {
+                    queue.push_back(image);
+                }
+            }
+        }
+        SymbolicAutomaton::new(HashSet::from([names.name(&start)]), SymbolicAutomaton::merge_arcs(arcs), ends)
+    }
+
+    /// Minimise l'automate: déterminisation puis raffinement de Moore des classes d'états sur les minterms
+    ///
+    /// Les états qui ne mènent à aucun état final sont supprimés, l'automate obtenu est donc le plus petit
+    /// automate déterministe émondé reconnaissant le même langage. Ses états sont nommés d'après les classes.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use automate::*;
+    /// use std::collections::HashSet;
+    /// fn main() {
+    ///     //deux chemins équivalents vers les nombres
+    ///     let states : Vec<State> = ["q_0", "q_1", "q_2"].iter().map(|name| State::from_str(name)).collect();
+    ///     let digits : CharSet = CharSet::new(&[('0', '9')]);
+    ///     let automaton : SymbolicAutomaton = SymbolicAutomaton::new(
+    ///         HashSet::from([states[0].clone()]),
+    ///         vec![
+    ///             (states[0].clone(), CharSet::new(&[('0', '4')]), states[1].clone()),
+    ///             (states[0].clone(), CharSet::new(&[('5', '9')]), states[2].clone()),
+    ///             (states[1].clone(), digits.clone(), states[2].clone()),
+    ///             (states[2].clone(), digits, states[1].clone()),
+    ///         ],
+    ///         HashSet::from([states[1].clone(), states[2].clone()]),
+    ///     );
+    ///     let minimal : SymbolicAutomaton = automaton.minimize();
+    ///     assert_eq!(minimal.get_states().len(), 2);
+    ///     assert!(minimal.accept("1789"));
+    ///     assert!(!minimal.accept(""));
+    /// }
+    /// ```
+    ///
+    pub fn minimize(&self) -> SymbolicAutomaton {
+        let deterministic : SymbolicAutomaton = self.determinize();
+        //suppression des états qui ne mènent à aucun état final
+        let adjacency = build_adjacency(deterministic.arcs.iter().map(|(state, _, image)| (state.clone(), Symbol::new(String::new()), image.clone())));
+        let useful : HashSet<State> = coreachable(&adjacency, &deterministic.ends);
+        let arcs : Vec<&SymbolicArc> = deterministic.arcs.iter().filter(|(state, _, image)| useful.contains(state) && useful.contains(image)).collect();
+        let mut states : Vec<State> = useful.iter().cloned().collect();
+        states.sort();
+        if states.is_empty() {
+            return SymbolicAutomaton::new(HashSet::new(), Vec::new(), HashSet::new());
+        }
+        let index : HashMap<&State, usize> = states.iter().enumerate().map(|(position, state)| (state, position)).collect();
+        //image de chaque état par chaque minterm, représenté par son premier caractère
+        let minterms : Vec<CharSet> = deterministic.minterms();
+        let images : Vec<Vec<Option<usize>>> = states.iter().map(|state| {
+            minterms.iter().map(|minterm| {
+                let letter : char = minterm.get_ranges()[0].0;
+                arcs.iter().find(|(source, set, _)| source == state && set.contains(letter)).map(|(_, _, image)| index[image])
+            }).collect()
+        }).collect();
+        let mut classes : Vec<usize> = states.iter().map(|state| usize::from(deterministic.ends.contains(state))).collect();
+        loop {
+            let mut signatures : HashMap<(usize, Vec<Option<usize>>), usize> = HashMap::new();
+            let refined : Vec<usize> = images.iter().zip(classes.iter()).map(|(successors, class)| {
+                let signature : (usize, Vec<Option<usize>>) = (*class, successors.iter().map(|image| image.map(|image| classes[image])).collect());
+                let next : usize = signatures.len();
+                *signatures.entry(signature).or_insert(next)
+            }).collect();
+            let stable : bool = signatures.len() == classes.iter().collect::<HashSet<&usize>>().len();
+            classes = refined;
+            if stable {
+                break;
+            }
+        }
+        let mut members : HashMap<usize, BTreeSet<State>> = HashMap::new();
+        for (state, class) in states.iter().zip(classes.iter()) {
+            members.entry(*class).or_default().insert(state.clone());
+        }
+        let mut blocks : Vec<(&usize, &BTreeSet<State>)> = members.iter().collect();
+        blocks.sort_by_key(|(_, block)| *block);
+        let mut names : SetNames = SetNames::default();
+        let blocks : HashMap<usize, State> = blocks.into_iter().map(|(class, block)| (*class, names.name(block))).collect();
+        let name = |state : &State| -> State { blocks[&classes[index[state]]].clone() };
+        let starts : HashSet<State> = deterministic.starts.iter().filter(|state| useful.contains(state)).map(name).collect();
+        let ends : HashSet<State> = deterministic.ends.iter().filter(|state| useful.contains(state)).map(name).collect();
+        let arcs : Vec<SymbolicArc> = arcs.iter().map(|(state, set, image)| (name(state), set.clone(), name(image))).collect();
+        SymbolicAutomaton::new(starts, SymbolicAutomaton::merge_arcs(arcs), ends)
+    }
+
+    /// Convertit l'automate en automate déterministe classique, dont chaque symbole est un caractère
+    ///
+    /// L'alphabet est l'ensemble des caractères apparaissant sur une transition; la conversion n'a de sens que s'il est petit.
+    ///
+    /// # Arguments
+    ///
+    /// * `limit` - Le nombre maximal de caractères de l'alphabet
+    ///
+    /// # Return
+    ///
+    /// * `Result<DeterministicFiniteAutomaton, String>` - L'automate déterminisé, une erreur si l'alphabet dépasse `limit` caractères
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use automate::*;
+    /// fn main() {
+    ///     let identifier : SymbolicAutomaton = SymbolicAutomaton::from_json_file("src/automates/SYM1.json");
+    ///     assert!(identifier.to_dfa(100).is_err());
+    ///     let binary : SymbolicAutomaton = SymbolicAutomaton::from_json_file("src/automates/SYM2.json");
+    ///     let dfa : DeterministicFiniteAutomaton = binary.to_dfa(2).unwrap();
+    ///     assert_eq!(dfa.get_alphabet().len(), 2);
+    ///     assert!(dfa.accept("110"));
+    /// }
+    /// ```
+    ///
+    pub fn to_dfa(&self, limit : usize) -> Result<DeterministicFiniteAutomaton, String> {
+        let letters : CharSet = self.arcs.iter().fold(CharSet::empty(), |letters, (_, set, _)| letters.union(set));
+        if letters.len() > limit {
+            return Err(format!("the alphabet has {} characters, more than the limit of {}", letters.len(), limit));
+        }
+        let deterministic : SymbolicAutomaton = self.determinize();
+        let mut delta : HashMap<Transition<State>, State> = HashMap::new();
+        for (state, set, image) in &deterministic.arcs {
+            for letter in set.chars() {
+                delta.insert(Transition::new(Symbol::new(String::from(letter)), state.clone()), image.clone());
+            }
+        }
+        let alphabet : HashSet<Symbol> = letters.chars().map(|letter| Symbol::new(String::from(letter))).collect();
+        let start : State = deterministic.starts.iter().next().unwrap().clone();
+        Ok(DeterministicFiniteAutomaton::new(start, delta, FiniteStateMachine::new(deterministic.states, alphabet, deterministic.ends)))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use super::super::test_words::enumerate_words;
+
+    #[test]
+    fn determinize_and_minimize_preserve_language() {
+        //mots sur [a-c] dont l'avant-dernier caractère est un 'a'
+        let q : Vec<State> = ["q_0", "q_1", "q_2"].iter().map(|name| State::from_str(name)).collect();
+        let automaton : SymbolicAutomaton = SymbolicAutomaton::new(
+            HashSet::from([q[0].clone()]),
+            vec![
+                (q[0].clone(), CharSet::new(&[('a', 'c')]), q[0].clone()),
+                (q[0].clone(), CharSet::from_char('a'), q[1].clone()),
+                (q[1].clone(), CharSet::new(&[('a', 'c')]), q[2].clone()),
+            ],
+            HashSet::from([q[2].clone()]),
+        );
+        let deterministic : SymbolicAutomaton = automaton.determinize();
+        let minimal : SymbolicAutomaton = automaton.minimize();
+        assert!(deterministic.is_deterministic());
+        assert!(minimal.is_deterministic());
+        assert_eq!(minimal.get_states().len(), 4);
+        let dfa : DeterministicFiniteAutomaton = automaton.to_dfa(3).unwrap();
+        for word in enumerate_words("abcd", 4) {
+            let expected : bool = automaton.accept(&word);
+            assert_eq!(deterministic.accept(&word), expected, "{}", word);
+            assert_eq!(minimal.accept(&word), expected, "{}", word);
+            assert_eq!(dfa.accept(&word), expected, "{}", word);
+        }
+    }
+
+    #[test]
+    fn minimize_empty_language() {
+        let q_0 : State = State::from_str("q_0");
+        let automaton : SymbolicAutomaton = SymbolicAutomaton::new(
+            HashSet::from([q_0.clone()]),
+            vec![(q_0.clone(), CharSet::full(), q_0.clone())],
+            HashSet::new(),
+        );
+        let minimal : SymbolicAutomaton = automaton.minimize();
+        assert!(minimal.get_states().is_empty());
+        assert!(!minimal.accept(""));
+        assert_eq!(automaton.minterms(), vec![CharSet::full()]);
+    }
+}
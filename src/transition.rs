@@ -1,7 +1,7 @@
 use super::Symbol;
 
 //string n'implemente pas copy, donc on peut juste utiliser clone
-#[derive(Debug, Clone, Eq, Hash)]
+#[derive(Debug, Clone, Hash, PartialEq, Eq)]
 pub struct Transition<T: Clone> {
     symbol: Symbol,
     content: T
@@ -115,19 +115,6 @@ impl<T: Clone> Transition<T> {
     }
 }
 
-impl<T> PartialEq for Transition<T>
-    where
-        T: PartialEq,
-        T: Clone
-    {
-    fn eq(&self, other: &Self) -> bool {
-        self.get_symbol() == other.get_symbol() && self.get_content() == other.get_content()
-    }
-    fn ne(&self, other: &Self) -> bool {
-        self.get_symbol() != other.get_symbol() || self.get_content() != other.get_content()
-    }
-}
-
 #[cfg(test)]
 mod test {
     use super::super::{State, Symbol, Transition};
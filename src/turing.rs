@@ -0,0 +1,394 @@
+use super::{Transition, State, Symbol, to_symbols};
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use serde_json::{Value, from_str, map::Map};
+
+/// Déplacement de la tête de lecture d'une machine de Turing
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Move {
+    /// Une case vers la gauche
+    Left,
+    /// Une case vers la droite
+    Right,
+    /// La tête reste sur place
+    Stay,
+}
+
+impl Move {
+    /// Retourne le déplacement noté `L`, `R` ou `S` dans les fichiers json, panique sur toute autre lettre
+    pub(crate) fn from_letter(letter : &str) -> Move {
+        match letter {
+            "L" => Move::Left,
+            "R" => Move::Right,
+            "S" => Move::Stay,
+            other => panic!("unknown move {}", other),
+        }
+    }
+}
+
+/// Action d'une transition de machine de Turing: (image, symbole écrit, déplacement de la tête)
+pub type TuringAction = (State, Symbol, Move);
+
+/// Configuration d'une machine de Turing: l'état courant, les cases visitées du ruban et la position de la tête
+///
+/// Les positions sont relatives à la première case du mot d'entrée, numérotée 0; les cases à sa gauche ont
+/// des positions négatives.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct TuringConfiguration {
+    state: State,
+    tape: Vec<Symbol>,
+    origin: isize,
+    head: isize,
+}
+
+impl TuringConfiguration {
+    /// Retourne l'état courant
+    pub fn get_state(&self) -> &State {
+        &self.state
+    }
+
+    /// Retourne les cases visitées du ruban, de gauche à droite
+    pub fn get_tape(&self) -> &Vec<Symbol> {
+        &self.tape
+    }
+
+    /// Retourne la position de la première case de `get_tape`
+    pub fn get_origin(&self) -> isize {
+        self.origin
+    }
+
+    /// Retourne la position de la tête
+    pub fn get_head(&self) -> isize {
+        self.head
+    }
+
+    /// Retourne le symbole sous la tête
+    pub fn get_read(&self) -> &Symbol {
+        &self.tape[(self.head - self.origin) as usize]
+    }
+
+    /// Retourne une représentation ASCII de la configuration: le ruban sur une ligne, puis la tête et l'état courant
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use automate::*;
+    /// fn main() {
+    ///     let machine : TuringMachine = TuringMachine::from_json_file("src/automates/TM1.json");
+    ///     let (_, trace) : (bool, Vec<TuringConfiguration>) = machine.trace("ab", 100).unwrap();
+    ///     assert_eq!(trace[1].render(), "| X | b |\n      ^ q_1");
+    /// }
+    /// ```
+    ///
+    pub fn render(&self) -> String {
+        let mut tape : String = String::from("|");
+        let mut column : usize = 0;
+        for (index, symbol) in self.tape.iter().enumerate() {
+            if index as isize == self.head - self.origin {
+                column = tape.chars().count() + 1;
+            }
+            tape.push_str(&format!(" {} |", symbol.get_value()));
+        }
+        format!("{}\n{}^ {}", tape, " ".repeat(column), self.state.get_name())
+    }
+}
+
+/// Machine de Turing déterministe à un ruban infini dans les deux sens
+#[derive(Debug, Clone)]
+pub struct TuringMachine {
+    start: State,
+    accept: State,
+    reject: State,
+    blank: Symbol,
+    delta: HashMap<Transition<State>, TuringAction>,
+    states: HashSet<State>,
+    alphabet: HashSet<Symbol>,
+    tape_alphabet: HashSet<Symbol>,
+}
+
+impl TuringMachine {
+    /// Créer une machine de Turing
+    ///
+    /// Les états et l'alphabet du ruban sont déduits des transitions. La machine s'arrête dès qu'elle atteint
+    /// l'état `_accept` ou `_reject`, et refuse le mot si aucune transition n'est définie.
+    ///
+    /// # Arguments
+    ///
+    /// * `_start` - L'état initial de la machine
+    /// * `_accept` - L'état d'acceptation
+    /// * `_reject` - L'état de refus
+    /// * `_blank` - Le symbole des cases vides du ruban
+    /// * `_alphabet` - L'alphabet d'entrée
+    /// * `_delta` - Une HashMap associant à un état et un symbole lu l'image, le symbole écrit et le déplacement
+    ///
+    /// # Return
+    ///
+    /// * `TuringMachine` - La machine de Turing correspondante
+    ///
+    pub fn new(_start : State, _accept : State, _reject : State, _blank : Symbol, _alphabet : HashSet<Symbol>, _delta : HashMap<Transition<State>, TuringAction>) -> Self {
+        let mut states : HashSet<State> = HashSet::from([_start.clone(), _accept.clone(), _reject.clone()]);
+        let mut tape_alphabet : HashSet<Symbol> = _alphabet.clone();
+        tape_alphabet.insert(_blank.clone());
+        for (transition, (image, write, _)) in &_delta {
+            states.insert(transition.get_content().clone());
+            states.insert(image.clone());
+            tape_alphabet.insert(transition.get_symbol().clone());
+            tape_alphabet.insert(write.clone());
+        }
+        TuringMachine {
+            start: _start,
+            accept: _accept,
+            reject: _reject,
+            blank: _blank,
+            delta: _delta,
+            states,
+            alphabet: _alphabet,
+            tape_alphabet,
+        }
+    }
+
+    /// Créer une machine de Turing depuis un contenu json
+    ///
+    /// # Examples
+    ///
+    /// Le contenu du json: chaque transition indique en plus le symbole écrit et le déplacement de la tête,
+    /// `L`, `R` ou `S`
+    ///
+    /// ```json
+    /// {
+    ///     "alphabet" : ["a","b"],
+    ///     "blank" : "_",
+    ///     "start" : "q_0",
+    ///     "accept" : "yes",
+    ///     "reject" : "no",
+    ///     "delta" : [
+    ///         {
+    ///             "state" : "q_0",
+    ///             "symbol" : "a",
+    ///             "image" : "q_1",
+    ///             "write" : "X",
+    ///             "move" : "R"
+    ///         }
+    ///     ]
+    /// }
+    /// ```
+    ///
+    /// # Return
+    ///
+    /// * `TuringMachine` - La machine de Turing correspondante
+    ///
+    pub fn from_json(content_json: &Map<String, Value>) -> Self {
+        let name = |value : &Value| String::from(value.as_str().unwrap());
+        let state = |key : &str| State::new(name(content_json.get(key).unwrap()));
+        let alphabet : HashSet<Symbol> = content_json.get("alphabet").unwrap().as_array().unwrap().iter().map(|symbol| Symbol::new(name(symbol))).collect();
+        let mut delta : HashMap<Transition<State>, TuringAction> = HashMap::new();
+        for element_delta in content_json.get("delta").unwrap().as_array().unwrap() {
+            let transition_json : &Map<String, Value> = element_delta.as_object().unwrap();
+            let head_move : Move = Move::from_letter(transition_json.get("move").unwrap().as_str().unwrap());
+            delta.insert(
+                Transition::new(Symbol::new(name(transition_json.get("symbol").unwrap())), State::new(name(transition_json.get("state").unwrap()))),
+                (State::new(name(transition_json.get("image").unwrap())), Symbol::new(name(transition_json.get("write").unwrap())), head_move),
+            );
+        }
+        TuringMachine::new(state("start"), state("accept"), state("reject"), Symbol::new(name(content_json.get("blank").unwrap())), alphabet, delta)
+    }
+
+    /// Créer une machine de Turing depuis un chemin vers un fichier json, voir `from_json`
+    pub fn from_json_file(path: &str) -> Self {
+        let content_json: Value = {
+            // Charge le contenu du fichier en tant que String
+            let content : String = fs::read_to_string(path).unwrap();
+            // Parse le texte en structure Json
+            from_str::<Value>(&content).unwrap()
+        };
+        TuringMachine::from_json(content_json.as_object().unwrap())
+    }
+
+    /// Retourne l'état initial de la machine
+    pub fn get_start(&self) -> &State {
+        &self.start
+    }
+
+    /// Retourne l'état d'acceptation
+    pub fn get_accept(&self) -> &State {
+        &self.accept
+    }
+
+    /// Retourne l'état de refus
+    pub fn get_reject(&self) -> &State {
+        &self.reject
+    }
+
+    /// Retourne le symbole des cases vides
+    pub fn get_blank(&self) -> &Symbol {
+        &self.blank
+    }
+
+    /// Retourne les transitions de la machine
+    pub fn get_delta(&self) -> &HashMap<Transition<State>, TuringAction> {
+        &self.delta
+    }
+
+    /// Retourne les états de la machine
+    pub fn get_states(&self) -> &HashSet<State> {
+        &self.states
+    }
+
+    /// Retourne l'alphabet d'entrée
+    pub fn get_alphabet(&self) -> &HashSet<Symbol> {
+        &self.alphabet
+    }
+
+    /// Retourne l'alphabet du ruban, symbole blanc compris
+    pub fn get_tape_alphabet(&self) -> &HashSet<Symbol> {
+        &self.tape_alphabet
+    }
+
+    pub fn apply_delta(&self, transition : Transition<State>) -> Option<&TuringAction> {
+        self.delta.get(&transition)
+    }
+
+    /// Retourne la configuration suivante, `None` si aucune transition n'est définie
+    fn step(&self, configuration : &TuringConfiguration) -> Option<TuringConfiguration> {
+        let (image, write, head_move) : &TuringAction = self.apply_delta(Transition::new(configuration.get_read().clone(), configuration.state.clone()))?;
+        let mut next : TuringConfiguration = TuringConfiguration {
+            state: image.clone(),
+            tape: configuration.tape.clone(),
+            origin: configuration.origin,
+            head: configuration.head,
+        };
+        next.tape[(next.head - next.origin) as usize] = write.clone();
+        match head_move {
+            Move::Left => next.head -= 1,
+            Move::Right => next.head += 1,
+            Move::Stay => {},
+        }
+        //le ruban s'étend d'une case vide quand la tête en sort
+        if next.head < next.origin {
+            next.tape.insert(0, self.blank.clone());
+            next.origin -= 1;
+        } else if next.head - next.origin == next.tape.len() as isize {
+            next.tape.push(self.blank.clone());
+        }
+        Some(next)
+    }
+
+    /// Exécute la machine pendant au plus `limit` transitions, en passant à `visit` chaque configuration traversée
+    ///
+    /// Retourne l'acceptation du mot, `None` si la machine ne s'est pas arrêtée.
+    fn run(&self, input : &[Symbol], limit : usize, mut visit : impl FnMut(&TuringConfiguration)) -> Option<bool> {
+        let tape : Vec<Symbol> = if input.is_empty() { vec![self.blank.clone()] } else { input.to_vec() };
+        let mut configuration : TuringConfiguration = TuringConfiguration { state: self.start.clone(), tape, origin: 0, head: 0 };
+        let mut steps : usize = 0;
+        loop {
+            visit(&configuration);
+            if configuration.state == self.accept || configuration.state == self.reject {
+                return Some(configuration.state == self.accept);
+            }
+            if steps == limit {
+                return None;
+            }
+            match self.step(&configuration) {
+                Some(next) => configuration = next,
+                None => return Some(false),
+            }
+            steps += 1;
+        }
+    }
+
+    /// Exécute la machine sur une suite de symboles, voir `trace`
+    pub fn trace_symbols(&self, input : &[Symbol], limit : usize) -> Result<(bool, Vec<TuringConfiguration>), String> {
+        let mut trace : Vec<TuringConfiguration> = Vec::new();
+        match self.run(input, limit, |configuration| trace.push(configuration.clone())) {
+            Some(accepted) => Ok((accepted, trace)),
+            None => Err(format!("step limit of {} reached", limit)),
+        }
+    }
+
+    /// Exécute la machine sur `word` pendant au plus `limit` transitions
+    ///
+    /// La tête part de la première lettre du mot; le ruban est vide ailleurs. La machine accepte si elle atteint
+    /// l'état d'acceptation, et refuse si elle atteint l'état de refus ou une configuration sans transition.
+    ///
+    /// # Arguments
+    ///
+    /// * `word` - Le mot écrit sur le ruban, caractère par caractère
+    /// * `limit` - Le nombre maximal de transitions
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use automate::*;
+    /// fn main() {
+    ///     //la machine reconnait les mots a^n b^n
+    ///     let machine : TuringMachine = TuringMachine::from_json_file("src/automates/TM1.json");
+    ///     let (accepted, trace) : (bool, Vec<TuringConfiguration>) = machine.trace("aabb", 100).unwrap();
+    ///     assert!(accepted);
+    ///     assert_eq!(trace.last().unwrap().get_tape(), &["X", "X", "Y", "Y", "_"].map(Symbol::from_str).to_vec());
+    ///     assert!(!machine.trace("aab", 100).unwrap().0);
+    ///     assert!(machine.trace("aabb", 5).is_err());
+    /// }
+    /// ```
+    ///
+    /// # Return
+    ///
+    /// * `Result<(bool, Vec<TuringConfiguration>), String>` - L'acceptation du mot et les configurations traversées, une erreur si la machine ne s'arrête pas en `limit` transitions
+    ///
+    pub fn trace(&self, word : &str, limit : usize) -> Result<(bool, Vec<TuringConfiguration>), String> {
+        let input : Vec<Symbol> = to_symbols(word);
+        self.trace_symbols(&input, limit)
+    }
+
+    /// Indique si la machine accepte `word` en au plus `limit` transitions, une erreur si elle ne s'arrête pas avant
+    ///
+    /// Contrairement à `trace`, les configurations traversées ne sont pas conservées.
+    pub fn accept(&self, word : &str, limit : usize) -> Result<bool, String> {
+        let input : Vec<Symbol> = to_symbols(word);
+        self.run(&input, limit, |_| {}).ok_or_else(|| format!("step limit of {} reached", limit))
+    }
+
+    /// Retourne la représentation ASCII de chaque étape de l'exécution sur `word`, voir `TuringConfiguration::render`
+    ///
+    /// Les étapes sont numérotées et séparées par une ligne vide. Si la machine ne s'arrête pas, les étapes 0 à `limit`
+    /// sont affichées.
+    pub fn render_trace(&self, word : &str, limit : usize) -> String {
+        let input : Vec<Symbol> = to_symbols(word);
+        let mut steps : Vec<String> = Vec::new();
+        self.run(&input, limit, |configuration| steps.push(format!("{}:\n{}", steps.len(), configuration.render())));
+        steps.join("\n\n")
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use super::super::test_words::enumerate_words;
+
+    #[test]
+    fn anbn() {
+        let machine : TuringMachine = TuringMachine::from_json_file("src/automates/TM1.json");
+        assert_eq!(machine.get_states().len(), 6);
+        assert_eq!(machine.get_tape_alphabet().len(), 5);
+        for word in enumerate_words("ab", 8) {
+            let half : usize = word.len() / 2;
+            let expected : bool = word.len() % 2 == 0 && word[..half].chars().all(|letter| letter == 'a') && word[half..].chars().all(|letter| letter == 'b');
+            assert_eq!(machine.accept(&word, 1000), Ok(expected), "{}", word);
+        }
+        assert_eq!(machine.render_trace("ab", 100), "0:\n| a | b |\n  ^ q_0\n\n1:\n| X | b |\n      ^ q_1\n\n2:\n| X | Y |\n  ^ q_2\n\n3:\n| X | Y |\n      ^ q_0\n\n4:\n| X | Y | _ |\n          ^ q_3\n\n5:\n| X | Y | _ |\n          ^ yes");
+    }
+
+    #[test]
+    fn tape_grows_and_budget() {
+        //la machine remplace chaque case par 1 en allant vers la gauche, sans jamais s'arrêter
+        let delta : HashMap<Transition<State>, TuringAction> = HashMap::from([
+            (Transition::new(Symbol::from_str("0"), State::from_str("q")), (State::from_str("q"), Symbol::from_str("1"), Move::Left)),
+        ]);
+        let machine : TuringMachine = TuringMachine::new(State::from_str("q"), State::from_str("yes"), State::from_str("no"), Symbol::from_str("0"), HashSet::from([Symbol::from_str("0")]), delta);
+        assert_eq!(machine.accept("00", 10), Err(String::from("step limit of 10 reached")));
+        let steps : Vec<String> = machine.render_trace("0", 3).split("\n\n").map(String::from).collect();
+        assert_eq!(steps.len(), 4);
+        assert_eq!(steps[3], "3:\n| 0 | 1 | 1 | 1 |\n  ^ q");
+        //sans transition, le mot est refusé
+        assert_eq!(machine.accept("1", 10), Ok(false));
+    }
+}
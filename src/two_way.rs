@@ -0,0 +1,424 @@
+use super::{Transition, State, Symbol, Move, FiniteStateMachine, DeterministicFiniteAutomaton, to_symbols};
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::fs;
+use serde_json::{Value, from_str, map::Map};
+
+/// Marqueur de début de ruban d'un automate bidirectionnel, lu avant la première lettre du mot
+pub const LEFT_ENDMARKER : &str = "⊢";
+
+/// Marqueur de fin de ruban d'un automate bidirectionnel, lu après la dernière lettre du mot
+pub const RIGHT_ENDMARKER : &str = "⊣";
+
+/// Action d'une transition d'automate bidirectionnel: (image, déplacement de la tête)
+pub type TwoWayAction = (State, Move);
+
+/// Résultat de l'exécution d'un automate bidirectionnel
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TwoWayOutcome {
+    /// L'automate a atteint l'état d'acceptation
+    Accepted,
+    /// L'automate a atteint l'état de refus, une configuration sans transition ou est sorti du ruban
+    Rejected,
+    /// L'automate est revenu dans une configuration déjà visitée et ne s'arrêtera jamais
+    Looping,
+}
+
+/// Issue du parcours d'un préfixe du ruban: la tête en sort par la droite dans un état, ou l'automate s'arrête
+/// en acceptant ou non (les boucles et les sorties par la gauche refusent)
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+enum Exit {
+    Right(State),
+    Halt(bool),
+}
+
+/// Table de Shepherdson d'un préfixe du ruban: l'issue depuis la configuration initiale, puis pour chaque état
+/// l'issue quand la tête entre dans le préfixe par sa dernière case
+type Table = (Exit, Vec<Exit>);
+
+/// Automate fini déterministe bidirectionnel: la tête lit le mot entre deux marqueurs et se déplace dans les deux sens
+///
+/// Le ruban contient `⊢`, le mot puis `⊣`; la tête part de `⊢` dans l'état initial. L'automate s'arrête dès qu'il
+/// atteint l'état d'acceptation ou de refus; il refuse aussi s'il n'a pas de transition ou sort du ruban.
+#[derive(Debug, Clone)]
+pub struct TwoWayAutomaton {
+    start: State,
+    accept: State,
+    reject: State,
+    delta: HashMap<Transition<State>, TwoWayAction>,
+    states: HashSet<State>,
+    alphabet: HashSet<Symbol>,
+}
+
+impl TwoWayAutomaton {
+    /// Créer un automate bidirectionnel
+    ///
+    /// Les états sont déduits des transitions. Les transitions lisant les marqueurs utilisent les symboles
+    /// `LEFT_ENDMARKER` et `RIGHT_ENDMARKER`, qui ne doivent pas appartenir à l'alphabet.
+    ///
+    /// # Arguments
+    ///
+    /// * `_start` - L'état initial de l'automate
+    /// * `_accept` - L'état d'acceptation
+    /// * `_reject` - L'état de refus
+    /// * `_alphabet` - L'alphabet d'entrée, sans les marqueurs
+    /// * `_delta` - Une HashMap associant à un état et un symbole lu l'image et le déplacement de la tête
+    ///
+    /// # Return
+    ///
+    /// * `TwoWayAutomaton` - L'automate bidirectionnel correspondant
+    ///
+    pub fn new(_start : State, _accept : State, _reject : State, _alphabet : HashSet<Symbol>, _delta : HashMap<Transition<State>, TwoWayAction>) -> Self {
+        let mut states : HashSet<State> = HashSet::from([_start.clone(), _accept.clone(), _reject.clone()]);
+        for (transition, (image, _)) in &_delta {
+            states.insert(transition.get_content().clone());
+            states.insert(image.clone());
+        }
+        TwoWayAutomaton {
+            start: _start,
+            accept: _accept,
+            reject: _reject,
+            delta: _delta,
+            states,
+            alphabet: _alphabet,
+        }
+    }
+
+    /// Créer un automate bidirectionnel depuis un contenu json
+    ///
+    /// # Examples
+    ///
+    /// Le contenu du json: chaque transition indique le déplacement de la tête, `L`, `R` ou `S`, et les
+    /// marqueurs s'écrivent `⊢` et `⊣`
+    ///
+    /// ```json
+    /// {
+    ///     "alphabet" : ["a","b"],
+    ///     "start" : "s",
+    ///     "accept" : "yes",
+    ///     "reject" : "no",
+    ///     "delta" : [
+    ///         {
+    ///             "state" : "s",
+    ///             "symbol" : "⊢",
+    ///             "image" : "s",
+    ///             "move" : "R"
+    ///         }
+    ///     ]
+    /// }
+    /// ```
+    ///
+    /// # Return
+    ///
+    /// * `TwoWayAutomaton` - L'automate bidirectionnel correspondant
+    ///
+    pub fn from_json(content_json: &Map<String, Value>) -> Self {
+        let name = |value : &Value| String::from(value.as_str().unwrap());
+        let state = |key : &str| State::new(name(content_json.get(key).unwrap()));
+        let alphabet : HashSet<Symbol> = content_json.get("alphabet").unwrap().as_array().unwrap().iter().map(|symbol| Symbol::new(name(symbol))).collect();
+        let mut delta : HashMap<Transition<State>, TwoWayAction> = HashMap::new();
+        for element_delta in content_json.get("delta").unwrap().as_array().unwrap() {
+            let transition_json : &Map<String, Value> = element_delta.as_object().unwrap();
+            let head_move : Move = Move::from_letter(transition_json.get("move").unwrap().as_str().unwrap());
+            delta.insert(
+                Transition::new(Symbol::new(name(transition_json.get("symbol").unwrap())), State::new(name(transition_json.get("state").unwrap()))),
+                (State::new(name(transition_json.get("image").unwrap())), head_move),
+            );
+        }
+        TwoWayAutomaton::new(state("start"), state("accept"), state("reject"), alphabet, delta)
+    }
+
+    /// Créer un automate bidirectionnel depuis un chemin vers un fichier json, voir `from_json`
+    pub fn from_json_file(path: &str) -> Self {
+        let content : String = fs::read_to_string(path).unwrap();
+        TwoWayAutomaton::from_json(from_str::<Value>(&content).unwrap().as_object().unwrap())
+    }
+
+    /// Retourne l'état initial de l'automate
+    pub fn get_start(&self) -> &State {
+        &self.start
+    }
+
+    /// Retourne l'état d'acceptation
+    pub fn get_accept(&self) -> &State {
+        &self.accept
+    }
+
+    /// Retourne l'état de refus
+    pub fn get_reject(&self) -> &State {
+        &self.reject
+    }
+
+    /// Retourne les transitions de l'automate
+    pub fn get_delta(&self) -> &HashMap<Transition<State>, TwoWayAction> {
+        &self.delta
+    }
+
+    /// Retourne les états de l'automate
+    pub fn get_states(&self) -> &HashSet<State> {
+        &self.states
+    }
+
+    /// Retourne l'alphabet d'entrée, sans les marqueurs
+    pub fn get_alphabet(&self) -> &HashSet<Symbol> {
+        &self.alphabet
+    }
+
+    pub fn apply_delta(&self, transition : Transition<State>) -> Option<&TwoWayAction> {
+        self.delta.get(&transition)
+    }
+
+    /// Indique si `state` arrête l'automate, et s'il accepte alors
+    fn halts(&self, state : &State) -> Option<bool> {
+        if *state == self.accept {
+            Some(true)
+        } else if *state == self.reject {
+            Some(false)
+        } else {
+            None
+        }
+    }
+
+    /// Exécute l'automate sur une suite de symboles, voir `trace`
+    pub fn trace_symbols(&self, input : &[Symbol]) -> (TwoWayOutcome, Vec<(State, usize)>) {
+        let mut tape : Vec<Symbol> = vec![Symbol::from_str(LEFT_ENDMARKER)];
+        tape.extend(input.iter().cloned());
+        tape.push(Symbol::from_str(RIGHT_ENDMARKER));
+        let mut visited : HashSet<(State, usize)> = HashSet::new();
+        let mut trace : Vec<(State, usize)> = Vec::new();
+        let mut state : State = self.start.clone();
+        let mut head : usize = 0;
+        loop {
+            //l'automate étant déterministe, revoir une configuration signifie qu'il boucle
+            if !visited.insert((state.clone(), head)) {
+                return (TwoWayOutcome::Looping, trace);
+            }
+            trace.push((state.clone(), head));
+            match self.halts(&state) {
+                Some(true) => return (TwoWayOutcome::Accepted, trace),
+                Some(false) => return (TwoWayOutcome::Rejected, trace),
+                None => {},
+            }
+            let (image, head_move) : &TwoWayAction = match self.apply_delta(Transition::new(tape[head].clone(), state.clone())) {
+                Some(action) => action,
+                None => return (TwoWayOutcome::Rejected, trace),
+            };
+            head = match head_move {
+                Move::Left if head == 0 => return (TwoWayOutcome::Rejected, trace),
+                Move::Right if head == tape.len() - 1 => return (TwoWayOutcome::Rejected, trace),
+                Move::Left => head - 1,
+                Move::Right => head + 1,
+                Move::Stay => head,
+            };
+            state = image.clone();
+        }
+    }
+
+    /// Exécute l'automate sur `word`, en détectant les boucles
+    ///
+    /// Les configurations traversées sont des couples (état, position de la tête), la position 0 étant celle de `⊢`.
+    /// En cas de boucle, la trace s'arrête avant la première configuration répétée.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use automate::*;
+    /// fn main() {
+    ///     //la troisième lettre avant la fin est un a
+    ///     let automaton : TwoWayAutomaton = TwoWayAutomaton::from_json_file("src/automates/2DFA1.json");
+    ///     let (outcome, trace) : (TwoWayOutcome, Vec<(State, usize)>) = automaton.trace("abb");
+    ///     assert_eq!(outcome, TwoWayOutcome::Accepted);
+    ///     //aller jusqu'à ⊣, puis revenir de trois cases
+    ///     assert_eq!(trace.iter().map(|(_, head)| *head).collect::<Vec<usize>>(), vec![0, 1, 2, 3, 4, 3, 2, 1, 1]);
+    ///     assert_eq!(automaton.trace("ab").0, TwoWayOutcome::Rejected);
+    /// }
+    /// ```
+    ///
+    pub fn trace(&self, word : &str) -> (TwoWayOutcome, Vec<(State, usize)>) {
+        let input : Vec<Symbol> = to_symbols(word);
+        self.trace_symbols(&input)
+    }
+
+    /// indique si un mot, donné comme une suite de symboles, est accepté par l'automate
+    pub fn accept_symbols(&self, word : &[Symbol]) -> bool {
+        self.trace_symbols(word).0 == TwoWayOutcome::Accepted
+    }
+
+    /// indique si un mot est accepté par l'automate, chaque caractère étant un symbole
+    pub fn accept(&self, _word : &str) -> bool {
+        self.trace(_word).0 == TwoWayOutcome::Accepted
+    }
+
+    /// Retourne la table du préfixe `x symbol` à partir de la table du préfixe `x`
+    ///
+    /// La tête entre sur la case de `symbol` par la gauche; chaque passage vers la gauche est résolu par la table de
+    /// `x`, qui indique dans quel état la tête revient, jusqu'à ce qu'elle sorte par la droite ou que l'automate s'arrête.
+    fn extend(&self, table : &Table, symbol : &Symbol, index : &HashMap<&State, usize>) -> Table {
+        let resolve = |entry : &State| -> Exit {
+            let mut state : State = entry.clone();
+            let mut visited : HashSet<State> = HashSet::new();
+            loop {
+                if let Some(accepted) = self.halts(&state) {
+                    return Exit::Halt(accepted);
+                }
+                //revenir sur la même case dans le même état est une boucle
+                if !visited.insert(state.clone()) {
+                    return Exit::Halt(false);
+                }
+                let (image, head_move) : &TwoWayAction = match self.apply_delta(Transition::new(symbol.clone(), state.clone())) {
+                    Some(action) => action,
+                    None => return Exit::Halt(false),
+                };
+                //sortir du ruban refuse, même vers l'état d'acceptation, comme dans `trace_symbols`
+                let leaves : bool = match head_move {
+                    Move::Left => *symbol == Symbol::from_str(LEFT_ENDMARKER),
+                    Move::Right => *symbol == Symbol::from_str(RIGHT_ENDMARKER),
+                    Move::Stay => false,
+                };
+                if leaves {
+                    return Exit::Halt(false);
+                }
+                if let Some(accepted) = self.halts(image) {
+                    return Exit::Halt(accepted);
+                }
+                match head_move {
+                    Move::Right => return Exit::Right(image.clone()),
+                    Move::Stay => state = image.clone(),
+                    Move::Left => match &table.1[index[image]] {
+                        Exit::Right(back) => state = back.clone(),
+                        Exit::Halt(accepted) => return Exit::Halt(*accepted),
+                    },
+                }
+            }
+        };
+        let initial : Exit = match &table.0 {
+            Exit::Right(state) => resolve(state),
+            Exit::Halt(accepted) => Exit::Halt(*accepted),
+        };
+        let mut states : Vec<(&State, usize)> = index.iter().map(|(state, position)| (*state, *position)).collect();
+        states.sort_by_key(|(_, position)| *position);
+        (initial, states.into_iter().map(|(state, _)| resolve(state)).collect())
+    }
+
+    /// Convertit l'automate en automate déterministe classique par la construction de Shepherdson
+    ///
+    /// Après lecture d'un préfixe `⊢x`, l'automate obtenu mémorise sa table de traversée: l'état dans lequel la tête
+    /// franchit pour la première fois la frontière droite du préfixe, et pour chaque état l'état dans lequel elle
+    /// la refranchit après y être revenue. Ces tables résument les suites de franchissements (crossing sequences)
+    /// de la frontière; leur nombre est au plus `(n+2)^(n+1)` pour `n` états. Les états obtenus sont nommés
+    /// `t_0`, `t_1`... dans l'ordre de découverte, et l'automate obtenu est complet.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use automate::*;
+    /// fn main() {
+    ///     let automaton : TwoWayAutomaton = TwoWayAutomaton::from_json_file("src/automates/2DFA1.json");
+    ///     let dfa : DeterministicFiniteAutomaton = automaton.to_dfa();
+    ///     assert!(dfa.accept("babb"));
+    ///     assert!(!dfa.accept("bbab"));
+    ///     //l'automate minimal mémorise les trois dernières lettres
+    ///     assert_eq!(dfa.minimize().get_states().len(), 8);
+    /// }
+    /// ```
+    ///
+    pub fn to_dfa(&self) -> DeterministicFiniteAutomaton {
+        let mut states : Vec<&State> = self.states.iter().collect();
+        states.sort();
+        let index : HashMap<&State, usize> = states.iter().enumerate().map(|(position, state)| (*state, position)).collect();
+        let mut alphabet : Vec<&Symbol> = self.alphabet.iter().collect();
+        alphabet.sort();
+        //table du préfixe vide: la tête entre sur ⊢ dans l'état initial, et sortir par la gauche refuse
+        let initial : Exit = match self.halts(&self.start) {
+            Some(accepted) => Exit::Halt(accepted),
+            None => Exit::Right(self.start.clone()),
+        };
+        let empty : Table = (initial, vec![Exit::Halt(false); states.len()]);
+        let start : Table = self.extend(&empty, &Symbol::from_str(LEFT_ENDMARKER), &index);
+        let mut names : HashMap<Table, State> = HashMap::from([(start.clone(), State::from_str("t_0"))]);
+        let mut queue : VecDeque<Table> = VecDeque::from([start.clone()]);
+        let mut delta : HashMap<Transition<State>, State> = HashMap::new();
+        let mut ends : HashSet<State> = HashSet::new();
+        while let Some(table) = queue.pop_front() {
+            let name : State = names[&table].clone();
+            if self.extend(&table, &Symbol::from_str(RIGHT_ENDMARKER), &index).0 == Exit::Halt(true) {
+                ends.insert(name.clone());
+            }
+            for symbol in &alphabet {
+                let next : Table = self.extend(&table, symbol, &index);
+                if !names.contains_key(&next) {
+                    names.insert(next.clone(), State::new(format!("t_{}", names.len())));
+                    queue.push_back(next.clone());
+                }
+                delta.insert(Transition::new((*symbol).clone(), name.clone()), names[&next].clone());
+            }
+        }
+        let dfa_states : HashSet<State> = names.into_values().collect();
+        DeterministicFiniteAutomaton::new(State::from_str("t_0"), delta, FiniteStateMachine::new(dfa_states, self.alphabet.clone(), ends))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use super::super::test_words::enumerate_words;
+
+    #[test]
+    fn third_letter_from_the_end() {
+        let automaton : TwoWayAutomaton = TwoWayAutomaton::from_json_file("src/automates/2DFA1.json");
+        let dfa : DeterministicFiniteAutomaton = automaton.to_dfa();
+        for word in enumerate_words("ab", 7) {
+            let expected : bool = word.len() >= 3 && word.as_bytes()[word.len() - 3] == b'a';
+            assert_eq!(automaton.accept(&word), expected, "{}", word);
+            assert_eq!(dfa.accept(&word), expected, "{}", word);
+        }
+    }
+
+    #[test]
+    fn loops_are_detected_and_rejected() {
+        //sur un b, la tête repart vers ⊢ puis revient: l'automate boucle; sur un a, il accepte
+        let s : State = State::from_str("s");
+        let delta : HashMap<Transition<State>, TwoWayAction> = HashMap::from([
+            (Transition::new(Symbol::from_str(LEFT_ENDMARKER), s.clone()), (s.clone(), Move::Right)),
+            (Transition::new(Symbol::from_str("a"), s.clone()), (State::from_str("check"), Move::Stay)),
+            (Transition::new(Symbol::from_str("a"), State::from_str("check")), (State::from_str("yes"), Move::Right)),
+            (Transition::new(Symbol::from_str("b"), s.clone()), (s.clone(), Move::Left)),
+        ]);
+        let alphabet : HashSet<Symbol> = HashSet::from([Symbol::from_str("a"), Symbol::from_str("b")]);
+        let automaton : TwoWayAutomaton = TwoWayAutomaton::new(s.clone(), State::from_str("yes"), State::from_str("no"), alphabet, delta);
+        let (outcome, trace) : (TwoWayOutcome, Vec<(State, usize)>) = automaton.trace("ba");
+        assert_eq!(outcome, TwoWayOutcome::Looping);
+        assert_eq!(trace, vec![(s.clone(), 0), (s.clone(), 1)]);
+        assert_eq!(automaton.trace("").0, TwoWayOutcome::Rejected);
+        let dfa : DeterministicFiniteAutomaton = automaton.to_dfa();
+        for word in enumerate_words("ab", 5) {
+            let expected : bool = word.starts_with('a');
+            assert_eq!(automaton.accept(&word), expected, "{}", word);
+            assert_eq!(dfa.accept(&word), expected, "{}", word);
+        }
+    }
+
+    #[test]
+    fn leaving_the_tape_rejects() {
+        //la tête parcourt le mot puis sort du ruban par ⊣ vers l'état d'acceptation, ou par ⊢ sur un b
+        let s : State = State::from_str("s");
+        let back : State = State::from_str("back");
+        let yes : State = State::from_str("yes");
+        let delta : HashMap<Transition<State>, TwoWayAction> = HashMap::from([
+            (Transition::new(Symbol::from_str(LEFT_ENDMARKER), s.clone()), (s.clone(), Move::Right)),
+            (Transition::new(Symbol::from_str("a"), s.clone()), (s.clone(), Move::Right)),
+            (Transition::new(Symbol::from_str("b"), s.clone()), (back.clone(), Move::Left)),
+            (Transition::new(Symbol::from_str(RIGHT_ENDMARKER), s.clone()), (yes.clone(), Move::Right)),
+            (Transition::new(Symbol::from_str("a"), back.clone()), (back.clone(), Move::Left)),
+            (Transition::new(Symbol::from_str(LEFT_ENDMARKER), back.clone()), (yes.clone(), Move::Left)),
+        ]);
+        let alphabet : HashSet<Symbol> = HashSet::from([Symbol::from_str("a"), Symbol::from_str("b")]);
+        let automaton : TwoWayAutomaton = TwoWayAutomaton::new(s, yes, State::from_str("no"), alphabet, delta);
+        assert_eq!(automaton.trace("a").0, TwoWayOutcome::Rejected);
+        assert_eq!(automaton.trace("ab").0, TwoWayOutcome::Rejected);
+        let dfa : DeterministicFiniteAutomaton = automaton.to_dfa();
+        for word in enumerate_words("ab", 5) {
+            assert!(!automaton.accept(&word), "{}", word);
+            assert!(!dfa.accept(&word), "{}", word);
+        }
+    }
+}
@@ -0,0 +1,416 @@
+use super::{Transition, State, Symbol, Semiring, Selective, DeterministicFiniteAutomaton, NonDeterministicFiniteAutomaton, to_symbols};
+use std::collections::{HashMap, HashSet};
+
+/// Chemin acceptant d'un automate pondéré: les états traversés, le mot lu et le poids du chemin
+#[derive(Debug, Clone, PartialEq)]
+pub struct WeightedPath<W : Semiring> {
+    states: Vec<State>,
+    word: Vec<Symbol>,
+    weight: W,
+}
+
+impl<W : Semiring> WeightedPath<W> {
+    /// Retourne les états traversés, un de plus que de symboles lus
+    pub fn get_states(&self) -> &Vec<State> {
+        &self.states
+    }
+
+    /// Retourne le mot lu le long du chemin
+    pub fn get_word(&self) -> &Vec<Symbol> {
+        &self.word
+    }
+
+    /// Retourne le poids du chemin: poids initial, poids des transitions puis poids final
+    pub fn get_weight(&self) -> &W {
+        &self.weight
+    }
+}
+
+/// Automate pondéré sur un semi-anneau
+///
+/// Chaque état initial porte un poids initial, chaque transition un poids et chaque état final un poids final.
+/// Le poids d'un chemin est le produit (`times`) de ses poids, celui d'un mot la somme (`plus`) des poids de
+/// ses chemins.
+#[derive(Debug, Clone)]
+pub struct WeightedAutomaton<W : Semiring> {
+    starts: HashMap<State, W>,
+    delta: HashMap<Transition<State>, Vec<(State, W)>>,
+    ends: HashMap<State, W>,
+    states: HashSet<State>,
+    alphabet: HashSet<Symbol>,
+}
+
+impl<W : Semiring> WeightedAutomaton<W> {
+    /// Créer un automate pondéré
+    ///
+    /// Les états et l'alphabet sont déduits des poids.
+    ///
+    /// # Arguments
+    ///
+    /// * `_starts` - Les états initiaux et leurs poids initiaux
+    /// * `_delta` - Une HashMap associant à un état et un symbole ses images et les poids des transitions
+    /// * `_ends` - Les états finaux et leurs poids finaux
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use automate::*;
+    /// use std::collections::HashMap;
+    /// fn main() {
+    ///     //chaque a coûte 1, chaque b coûte 2
+    ///     let q : State = State::from_str("q");
+    ///     let delta : HashMap<Transition<State>, Vec<(State, Tropical)>> = HashMap::from([
+    ///         (Transition::new(Symbol::from_str("a"), q.clone()), vec![(q.clone(), Tropical(1.0))]),
+    ///         (Transition::new(Symbol::from_str("b"), q.clone()), vec![(q.clone(), Tropical(2.0))]),
+    ///     ]);
+    ///     let weighted : WeightedAutomaton<Tropical> = WeightedAutomaton::new(HashMap::from([(q.clone(), Tropical(0.0))]), delta, HashMap::from([(q, Tropical(0.0))]));
+    ///     assert_eq!(weighted.weight("abba"), Tropical(6.0));
+    /// }
+    /// ```
+    ///
+    /// # Return
+    ///
+    /// * `WeightedAutomaton<W>` - L'automate pondéré correspondant
+    ///
+    pub fn new(_starts : HashMap<State, W>, _delta : HashMap<Transition<State>, Vec<(State, W)>>, _ends : HashMap<State, W>) -> Self {
+        let mut states : HashSet<State> = _starts.keys().chain(_ends.keys()).cloned().collect();
+        let mut alphabet : HashSet<Symbol> = HashSet::new();
+        for (transition, images) in &_delta {
+            states.insert(transition.get_content().clone());
+            states.extend(images.iter().map(|(image, _)| image.clone()));
+            alphabet.insert(transition.get_symbol().clone());
+        }
+        WeightedAutomaton {
+            starts: _starts,
+            delta: _delta,
+            ends: _ends,
+            states,
+            alphabet,
+        }
+    }
+
+    /// Créer l'automate pondéré d'un automate non déterministe, tous les poids valant `one`
+    ///
+    /// Sur le semi-anneau booléen, le poids d'un mot indique s'il est accepté; sur le semi-anneau de comptage,
+    /// il donne son nombre de chemins acceptants.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use automate::*;
+    /// fn main() {
+    ///     let nfa : NonDeterministicFiniteAutomaton = NonDeterministicFiniteAutomaton::from_json_file("src/automates/NFA1.json");
+    ///     assert_eq!(WeightedAutomaton::<Boolean>::from_nfa(&nfa).weight("aab"), Boolean(true));
+    ///     assert_eq!(WeightedAutomaton::<Counting>::from_nfa(&nfa).weight("aab"), Counting(1));
+    ///     assert_eq!(WeightedAutomaton::<Counting>::from_nfa(&nfa).weight("ba"), Counting(0));
+    /// }
+    /// ```
+    ///
+    pub fn from_nfa(nfa : &NonDeterministicFiniteAutomaton) -> Self {
+        let starts : HashMap<State, W> = nfa.get_starts().iter().map(|state| (state.clone(), W::one())).collect();
+        let delta : HashMap<Transition<State>, Vec<(State, W)>> = nfa.get_delta().iter()
+            .map(|(transition, images)| {
+                let mut images : Vec<&State> = images.iter().collect();
+                images.sort();
+                (transition.clone(), images.into_iter().map(|image| (image.clone(), W::one())).collect())
+            })
+            .collect();
+        let ends : HashMap<State, W> = nfa.get_ends().iter().map(|state| (state.clone(), W::one())).collect();
+        let mut weighted : WeightedAutomaton<W> = WeightedAutomaton::new(starts, delta, ends);
+        weighted.states.extend(nfa.get_states().iter().cloned());
+        weighted.alphabet.extend(nfa.get_alphabet().iter().cloned());
+        weighted
+    }
+
+    /// Créer l'automate pondéré d'un automate déterministe, tous les poids valant `one`, voir `from_nfa`
+    pub fn from_dfa(dfa : &DeterministicFiniteAutomaton) -> Self {
+        WeightedAutomaton::from_nfa(&dfa.to_nfa())
+    }
+
+    /// Retourne les états initiaux et leurs poids
+    pub fn get_starts(&self) -> &HashMap<State, W> {
+        &self.starts
+    }
+
+    /// Retourne les transitions et leurs poids
+    pub fn get_delta(&self) -> &HashMap<Transition<State>, Vec<(State, W)>> {
+        &self.delta
+    }
+
+    /// Retourne les états finaux et leurs poids
+    pub fn get_ends(&self) -> &HashMap<State, W> {
+        &self.ends
+    }
+
+    /// Retourne les états de l'automate
+    pub fn get_states(&self) -> &HashSet<State> {
+        &self.states
+    }
+
+    /// Retourne l'alphabet de l'automate
+    pub fn get_alphabet(&self) -> &HashSet<Symbol> {
+        &self.alphabet
+    }
+
+    pub fn apply_delta(&self, transition : Transition<State>) -> Option<&Vec<(State, W)>> {
+        self.delta.get(&transition)
+    }
+
+    /// Retourne le poids d'une suite de symboles, voir `weight`
+    pub fn weight_symbols(&self, word : &[Symbol]) -> W {
+        //poids cumulé des chemins menant à chaque état
+        let mut currents : HashMap<State, W> = self.starts.clone();
+        for symbol in word {
+            let mut nexts : HashMap<State, W> = HashMap::new();
+            for (state, weight) in &currents {
+                for (image, arc_weight) in self.apply_delta(Transition::new(symbol.clone(), state.clone())).into_iter().flatten() {
+                    let path : W = weight.times(arc_weight);
+                    let total : W = nexts.get(image).map_or(path.clone(), |total| total.plus(&path));
+                    nexts.insert(image.clone(), total);
+                }
+            }
+            currents = nexts;
+        }
+        currents.iter()
+            .filter_map(|(state, weight)| self.ends.get(state).map(|end| weight.times(end)))
+            .fold(W::zero(), |total, weight| total.plus(&weight))
+    }
+
+    /// Retourne le poids de `word`, lu caractère par caractère: la somme des poids de ses chemins acceptants
+    ///
+    /// # Return
+    ///
+    /// * `W` - Le poids du mot, `zero` s'il n'a aucun chemin acceptant
+    ///
+    pub fn weight(&self, word : &str) -> W {
+        let word : Vec<Symbol> = to_symbols(word);
+        self.weight_symbols(&word)
+    }
+
+    /// Retourne les transitions sortant de chaque état, triées par symbole puis par image
+    fn successors(&self) -> HashMap<&State, Vec<(&Symbol, &State, &W)>> {
+        let mut successors : HashMap<&State, Vec<(&Symbol, &State, &W)>> = HashMap::new();
+        for (transition, images) in &self.delta {
+            for (image, weight) in images {
+                successors.entry(transition.get_content()).or_default().push((transition.get_symbol(), image, weight));
+            }
+        }
+        for arcs in successors.values_mut() {
+            arcs.sort_by(|(symbol, image, _), (other_symbol, other_image, _)| (symbol, image).cmp(&(other_symbol, other_image)));
+        }
+        successors
+    }
+}
+
+impl<W : Selective> WeightedAutomaton<W> {
+    /// Retourne les `n` meilleurs chemins acceptants, du meilleur au moins bon
+    ///
+    /// Les chemins partiels sont explorés du meilleur au moins bon, comme dans l'algorithme de Dijkstra, et
+    /// chaque état est développé au plus `n` fois. Les poids doivent être tels qu'allonger un chemin ne
+    /// l'améliore jamais, voir `Selective`. A poids égal, les chemins sont rendus dans l'ordre de leur découverte.
+    ///
+    /// # Arguments
+    ///
+    /// * `n` - Le nombre de chemins voulus
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use automate::*;
+    /// fn main() {
+    ///     let nfa : NonDeterministicFiniteAutomaton = NonDeterministicFiniteAutomaton::from_json_file("src/automates/NFA1.json");
+    ///     let paths : Vec<WeightedPath<Boolean>> = WeightedAutomaton::<Boolean>::from_nfa(&nfa).n_best_paths(3);
+    ///     let words : Vec<String> = paths.iter().map(|path| path.get_word().iter().map(|symbol| symbol.get_value().as_str()).collect()).collect();
+    ///     assert_eq!(words, ["", "a", "aa"]);
+    /// }
+    /// ```
+    ///
+    /// # Return
+    ///
+    /// * `Vec<WeightedPath<W>>` - Au plus `n` chemins acceptants de poids différent de `zero`
+    ///
+    pub fn n_best_paths(&self, n : usize) -> Vec<WeightedPath<W>> {
+        let successors : HashMap<&State, Vec<(&Symbol, &State, &W)>> = self.successors();
+        //chemins candidats, complets (poids final compris) ou partiels
+        let mut candidates : Vec<(WeightedPath<W>, bool)> = Vec::new();
+        let mut starts : Vec<(&State, &W)> = self.starts.iter().collect();
+        starts.sort_by_key(|(state, _)| *state);
+        for (state, weight) in starts {
+            if *weight != W::zero() {
+                candidates.push((WeightedPath { states: vec![state.clone()], word: Vec::new(), weight: weight.clone() }, false));
+            }
+        }
+        let mut expanded : HashMap<&State, usize> = HashMap::new();
+        let mut paths : Vec<WeightedPath<W>> = Vec::new();
+        while paths.len() < n && !candidates.is_empty() {
+            let mut best : usize = 0;
+            for index in 1..candidates.len() {
+                if candidates[index].0.weight.better(&candidates[best].0.weight) {
+                    best = index;
+                }
+            }
+            let (path, complete) : (WeightedPath<W>, bool) = candidates.remove(best);
+            if complete {
+                paths.push(path);
+                continue;
+            }
+            let state : &State = self.states.get(path.states.last().unwrap()).unwrap();
+            let count : &mut usize = expanded.entry(state).or_default();
+            if *count >= n {
+                continue;
+            }
+            *count += 1;
+            if let Some(end) = self.ends.get(state) {
+                let weight : W = path.weight.times(end);
+                if weight != W::zero() {
+                    candidates.push((WeightedPath { states: path.states.clone(), word: path.word.clone(), weight }, true));
+                }
+            }
+            for (symbol, image, arc_weight) in successors.get(state).into_iter().flatten() {
+                let weight : W = path.weight.times(arc_weight);
+                if weight == W::zero() {
+                    continue;
+                }
+                let mut next : WeightedPath<W> = WeightedPath { states: path.states.clone(), word: path.word.clone(), weight };
+                next.states.push((*image).clone());
+                next.word.push((*symbol).clone());
+                candidates.push((next, false));
+            }
+        }
+        paths
+    }
+
+    /// Retourne le meilleur chemin acceptant, quel que soit le mot lu, voir `n_best_paths`
+    pub fn best_path(&self) -> Option<WeightedPath<W>> {
+        self.n_best_paths(1).pop()
+    }
+
+    /// Retourne le meilleur chemin acceptant une suite de symboles, voir `viterbi`
+    pub fn viterbi_symbols(&self, word : &[Symbol]) -> Option<WeightedPath<W>> {
+        //meilleur chemin menant à chaque état après chaque préfixe
+        let mut bests : HashMap<State, (W, Vec<State>)> = self.starts.iter().map(|(state, weight)| (state.clone(), (weight.clone(), vec![state.clone()]))).collect();
+        for symbol in word {
+            let mut currents : Vec<(&State, &(W, Vec<State>))> = bests.iter().collect();
+            currents.sort_by_key(|(state, _)| *state);
+            let mut nexts : HashMap<State, (W, Vec<State>)> = HashMap::new();
+            for (state, (weight, states)) in currents {
+                for (image, arc_weight) in self.apply_delta(Transition::new(symbol.clone(), state.clone())).into_iter().flatten() {
+                    let candidate : W = weight.times(arc_weight);
+                    if nexts.get(image).is_none_or(|(best, _)| candidate.better(best)) {
+                        let mut path : Vec<State> = states.clone();
+                        path.push(image.clone());
+                        nexts.insert(image.clone(), (candidate, path));
+                    }
+                }
+            }
+            bests = nexts;
+        }
+        let mut finals : Vec<(W, Vec<State>)> = bests.into_iter()
+            .filter_map(|(state, (weight, states))| self.ends.get(&state).map(|end| (weight.times(end), states)))
+            .collect();
+        finals.sort_by(|(_, states), (_, other)| states.cmp(other));
+        let (weight, states) : (W, Vec<State>) = finals.into_iter().reduce(|best, candidate| if candidate.0.better(&best.0) { candidate } else { best })?;
+        if weight == W::zero() {
+            return None;
+        }
+        Some(WeightedPath { states, word: word.to_vec(), weight })
+    }
+
+    /// Retourne le meilleur chemin acceptant `word`, lu caractère par caractère, par l'algorithme de Viterbi
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use automate::*;
+    /// use std::collections::HashMap;
+    /// fn main() {
+    ///     //après un a, l'automate est dans x avec probabilité 0.6 et dans y avec probabilité 0.4
+    ///     let (s, x, y) : (State, State, State) = (State::from_str("s"), State::from_str("x"), State::from_str("y"));
+    ///     let delta : HashMap<Transition<State>, Vec<(State, Probability)>> = HashMap::from([
+    ///         (Transition::new(Symbol::from_str("a"), s.clone()), vec![(x.clone(), Probability(0.6)), (y.clone(), Probability(0.4))]),
+    ///     ]);
+    ///     let ends : HashMap<State, Probability> = HashMap::from([(x.clone(), Probability(0.5)), (y.clone(), Probability(1.0))]);
+    ///     let weighted : WeightedAutomaton<Probability> = WeightedAutomaton::new(HashMap::from([(s.clone(), Probability(1.0))]), delta, ends);
+    ///     let path : WeightedPath<Probability> = weighted.viterbi("a").unwrap();
+    ///     assert_eq!(path.get_states(), &vec![s, y]);
+    ///     assert_eq!(path.get_weight(), &Probability(0.4));
+    ///     assert_eq!(weighted.weight("a"), Probability(0.7));
+    /// }
+    /// ```
+    ///
+    /// # Return
+    ///
+    /// * `Option<WeightedPath<W>>` - Le meilleur chemin, `None` si le mot n'a aucun chemin acceptant
+    ///
+    pub fn viterbi(&self, word : &str) -> Option<WeightedPath<W>> {
+        let word : Vec<Symbol> = to_symbols(word);
+        self.viterbi_symbols(&word)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use super::super::{Tropical, Boolean, Counting};
+    use super::super::test_words::{enumerate_words, word_to_string};
+
+    /// Nombre de chemins acceptants de `word` dans `nfa`, par énumération des chemins
+    fn count_runs(nfa : &NonDeterministicFiniteAutomaton, state : &State, word : &[Symbol]) -> u64 {
+        match word.split_first() {
+            None => u64::from(nfa.get_ends().contains(state)),
+            Some((symbol, rest)) => nfa.apply_delta(Transition::new(symbol.clone(), state.clone())).into_iter().flatten()
+                .map(|image| count_runs(nfa, &image, rest))
+                .sum(),
+        }
+    }
+
+    #[test]
+    fn unweighted_conversions() {
+        let nfa : NonDeterministicFiniteAutomaton = NonDeterministicFiniteAutomaton::from_json_file("src/automates/NFA1.json");
+        let boolean : WeightedAutomaton<Boolean> = WeightedAutomaton::from_nfa(&nfa);
+        let counting : WeightedAutomaton<Counting> = WeightedAutomaton::from_nfa(&nfa);
+        assert_eq!(boolean.get_states(), nfa.get_states());
+        for word in enumerate_words("ab", 7) {
+            let symbols : Vec<Symbol> = to_symbols(&word);
+            let runs : u64 = nfa.get_starts().iter().map(|start| count_runs(&nfa, start, &symbols)).sum();
+            assert_eq!(boolean.weight(&word), Boolean(nfa.accept(&word)), "{}", word);
+            assert_eq!(counting.weight(&word), Counting(runs), "{}", word);
+        }
+        //l'union d'un automate avec lui-même double le nombre de chemins
+        let doubled : WeightedAutomaton<Counting> = WeightedAutomaton::from_nfa(&nfa.union_nfa(&nfa));
+        assert_eq!(doubled.weight("ab"), Counting(2));
+        let dfa : DeterministicFiniteAutomaton = DeterministicFiniteAutomaton::from_json_file("src/automates/DFA1.json");
+        let from_dfa : WeightedAutomaton<Counting> = WeightedAutomaton::from_dfa(&dfa);
+        assert_eq!(from_dfa.weight("abab"), Counting(1));
+        assert_eq!(from_dfa.weight("aba"), Counting(0));
+    }
+
+    #[test]
+    fn tropical_paths() {
+        let arc = |state : &str, symbol : &str, image : &str, cost : f64| (Transition::new(Symbol::from_str(symbol), State::from_str(state)), vec![(State::from_str(image), Tropical(cost))]);
+        let mut delta : HashMap<Transition<State>, Vec<(State, Tropical)>> = HashMap::from([
+            arc("0", "b", "1", 4.0),
+            arc("1", "a", "2", 2.0),
+            arc("1", "b", "1", 1.0),
+        ]);
+        delta.insert(Transition::new(Symbol::from_str("a"), State::from_str("0")), vec![(State::from_str("1"), Tropical(1.0)), (State::from_str("2"), Tropical(5.0))]);
+        let weighted : WeightedAutomaton<Tropical> = WeightedAutomaton::new(HashMap::from([(State::from_str("0"), Tropical(0.0))]), delta, HashMap::from([(State::from_str("2"), Tropical(0.5))]));
+        assert_eq!(weighted.weight("aba"), Tropical(4.5));
+        assert_eq!(weighted.weight("b"), Tropical(f64::INFINITY));
+        let best : WeightedPath<Tropical> = weighted.best_path().unwrap();
+        assert_eq!(word_to_string(best.get_word()), "aa");
+        assert_eq!(best.get_states(), &["0", "1", "2"].map(State::from_str).to_vec());
+        let paths : Vec<WeightedPath<Tropical>> = weighted.n_best_paths(6);
+        let weights : Vec<f64> = paths.iter().map(|path| path.get_weight().0).collect();
+        assert_eq!(weights, [3.5, 4.5, 5.5, 5.5, 6.5, 6.5]);
+        assert_eq!(word_to_string(paths[1].get_word()), "aba");
+        //le poids tropical d'un mot est celui de son meilleur chemin
+        for word in enumerate_words("ab", 6) {
+            let weight : Tropical = weighted.weight(&word);
+            assert_eq!(weighted.viterbi(&word).map_or(Tropical(f64::INFINITY), |path| *path.get_weight()), weight, "{}", word);
+        }
+        for path in &paths {
+            assert_eq!(&weighted.weight_symbols(path.get_word()), path.get_weight());
+        }
+    }
+}